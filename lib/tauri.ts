@@ -47,10 +47,12 @@ export async function applyProfile(
     eqEnabled?: boolean
 ): Promise<void> {
     return invoke("apply_profile", {
-        bands: toRustBands(bands),
-        preamp,
-        configPath,
-        eqEnabled: eqEnabled ?? true,
+        request: {
+            bands: toRustBands(bands),
+            preamp,
+            config_path: configPath ?? null,
+            eq_enabled: eqEnabled ?? true,
+        },
     });
 }
 
@@ -82,11 +84,13 @@ export async function updateSettings(
     eqEnabled?: boolean
 ): Promise<void> {
     return invoke("update_settings", {
-        bands: toRustBands(bands),
-        preamp,
-        currentProfile,
-        configPath,
-        eqEnabled,
+        update: {
+            bands: toRustBands(bands),
+            preamp,
+            current_profile: currentProfile,
+            config_path: configPath,
+            eq_enabled: eqEnabled ?? null,
+        },
     });
 }
 
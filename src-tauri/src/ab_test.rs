@@ -550,8 +550,10 @@ mod tests {
                     frequency: 1000.0,
                     gain,
                     q_factor: 1.0,
+                    enabled: true,
                 })
                 .collect(),
+            ..Default::default()
         }
     }
 
@@ -0,0 +1,616 @@
+//! Shared EQ actions for the tray and other quick controls.
+//!
+//! These functions perform a user-facing action end to end: they update
+//! [`AppState`], write the EqualizerAPO config, persist settings, notify the
+//! frontend and refresh the tray menu. The tray menu, forwarded command-line
+//! arguments, the local automation API, OSC controllers and global hotkeys
+//! all go through this module so that
+//! an action behaves the same no matter where it was triggered.
+//!
+//! # Events
+//!
+//! | Event                       | Payload         | Emitted when                 |
+//! |-----------------------------|-----------------|------------------------------|
+//! | `settings-changed`          | [`AppSettings`] | Any setting changed          |
+//! | `profile-changed-from-tray` | Profile name    | A profile was applied        |
+//! | `ab-option-changed`         | Option name     | An A/B option was switched   |
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::apply_ab_option;
+use crate::loudness;
+use crate::profile::{list_profiles, load_profile, save_settings, write_config};
+use crate::tray::{update_tray_menu, update_tray_status};
+use crate::types::{
+    AppSettings, AppState, EqProfile, FilterType, ParametricBand, MAX_FAVORITES,
+    MAX_RECENT_PROFILES,
+};
+use crate::validation;
+
+/// An EQ action that can be triggered from any control surface.
+///
+/// Serialized with an `action` tag, e.g. `{"action": "set_preamp", "preamp": -3.0}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    /// Apply a saved profile and make it current.
+    ApplyProfile { name: String },
+
+    /// Enable (`true`) or bypass (`false`) EQ processing.
+    SetEqEnabled { enabled: bool },
+
+    /// Toggle between enabled and bypassed.
+    ToggleEq,
+
+    /// Apply the profile after the current one, wrapping around.
+    NextProfile,
+
+    /// Apply the profile before the current one, wrapping around.
+    PreviousProfile,
+
+    /// Apply the favorite profile in the given slot (1–9).
+    ApplyFavorite { slot: usize },
+
+    /// Set the preamp gain in dB.
+    SetPreamp { preamp: f32 },
+
+    /// Nudge the preamp by `delta` dB, clamped to ±[`PREAMP_LIMIT_DB`].
+    AdjustPreamp { delta: f32 },
+
+    /// Change one or more parameters of a band in the current settings.
+    UpdateBand {
+        index: usize,
+        #[serde(flatten)]
+        patch: BandPatch,
+    },
+
+    /// Switch the active A/B test option (`"A"`, `"B"`, `"X"`, `"1"`, `"2"`).
+    ///
+    /// Requires an active A/B session; see [`apply_ab_option`].
+    ApplyAbOption { option: String },
+
+    /// Show and focus the main window.
+    ShowWindow,
+}
+
+impl Action {
+    /// Returns a short human-readable description, used in error reports.
+    pub fn describe(&self) -> String {
+        match self {
+            Action::ApplyProfile { name } => format!("Apply profile \"{}\"", name),
+            Action::SetEqEnabled { enabled: true } => "Enable EQ".to_string(),
+            Action::SetEqEnabled { enabled: false } => "Bypass EQ".to_string(),
+            Action::ToggleEq => "Toggle EQ".to_string(),
+            Action::NextProfile => "Next profile".to_string(),
+            Action::PreviousProfile => "Previous profile".to_string(),
+            Action::ApplyFavorite { slot } => format!("Apply favorite {}", slot),
+            Action::SetPreamp { preamp } => format!("Set preamp to {:.1} dB", preamp),
+            Action::AdjustPreamp { delta } => format!("Adjust preamp by {:+.1} dB", delta),
+            Action::UpdateBand { index, .. } => format!("Update band {}", index + 1),
+            Action::ApplyAbOption { option } => format!("Switch to A/B option {}", option),
+            Action::ShowWindow => "Show window".to_string(),
+        }
+    }
+}
+
+/// Largest preamp magnitude reachable with [`Action::AdjustPreamp`], matching
+/// the range of the preamp control in the UI.
+pub const PREAMP_LIMIT_DB: f32 = 20.0;
+
+/// A partial update to a [`ParametricBand`]; `None` fields are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BandPatch {
+    #[serde(default)]
+    pub filter_type: Option<FilterType>,
+    #[serde(default)]
+    pub frequency: Option<f32>,
+    #[serde(default)]
+    pub gain: Option<f32>,
+    #[serde(default)]
+    pub q_factor: Option<f32>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+impl BandPatch {
+    /// Applies the patch to a band.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a numeric value is not finite, or if the
+    /// frequency or Q is not positive. The band is left unchanged on error.
+    pub fn apply_to(&self, band: &mut ParametricBand) -> Result<(), String> {
+        let positive = |value: Option<f32>, field: &str| match value {
+            Some(v) if !v.is_finite() || v <= 0.0 => {
+                Err(format!("{} must be a positive number, got {}", field, v))
+            }
+            _ => Ok(()),
+        };
+        positive(self.frequency, "frequency")?;
+        positive(self.q_factor, "q_factor")?;
+        if let Some(gain) = self.gain {
+            if !gain.is_finite() {
+                return Err(format!("gain must be a finite number, got {}", gain));
+            }
+        }
+
+        if let Some(filter_type) = &self.filter_type {
+            band.filter_type = filter_type.clone();
+        }
+        if let Some(frequency) = self.frequency {
+            band.frequency = frequency;
+        }
+        if let Some(gain) = self.gain {
+            band.gain = gain;
+        }
+        if let Some(q_factor) = self.q_factor {
+            band.q_factor = q_factor;
+        }
+        if let Some(enabled) = self.enabled {
+            band.enabled = enabled;
+        }
+        Ok(())
+    }
+}
+
+/// Performs an action and returns the resulting settings.
+///
+/// Settings-only actions are first applied to a copy of the settings; the
+/// live config is written from that copy, and the shared state is only
+/// updated once the write succeeds.
+///
+/// # Errors
+///
+/// Returns an error if the action is invalid (e.g. band index out of range),
+/// a profile cannot be loaded, or the config cannot be written.
+pub fn dispatch(app: &AppHandle, action: Action) -> Result<AppSettings, String> {
+    let state = app.state::<AppState>();
+
+    match action {
+        Action::ApplyProfile { name } => apply_profile_by_name(app, &name)?,
+        Action::NextProfile | Action::PreviousProfile => {
+            let profiles = list_profiles()?;
+            let current = state.settings.lock().current_profile.clone();
+            let forward = action == Action::NextProfile;
+            let name = cycle_profile(&profiles, current.as_deref(), forward)
+                .ok_or("No profiles to switch to")?;
+            apply_profile_by_name(app, &name)?;
+        }
+        Action::ApplyFavorite { slot } => {
+            let name = favorite_profile(&state.settings.lock(), slot)?;
+            apply_profile_by_name(app, &name)?;
+        }
+        Action::ShowWindow => show_main_window(app),
+        Action::ApplyAbOption { option } => {
+            apply_ab_option(option.clone(), state.clone())?;
+            let _ = app.emit("ab-option-changed", option);
+        }
+        action => {
+            // Resolved before locking the settings, which it locks itself
+            let sample_rate = validation::resolve_sample_rate(&state);
+            {
+                let mut settings = state.settings.lock();
+                let mut updated = settings.clone();
+                update_settings_for(&mut updated, &action)?;
+                apply_settings(&mut updated, sample_rate)?;
+                *settings = updated;
+                save_settings(&settings)?;
+            }
+            notify_settings_changed(app);
+        }
+    }
+
+    let settings = state.settings.lock().clone();
+    Ok(settings)
+}
+
+/// Applies a settings-only action to `settings` without any I/O.
+///
+/// # Errors
+///
+/// Returns an error for invalid values or actions that need I/O
+/// (such as [`Action::ApplyProfile`]).
+pub fn update_settings_for(settings: &mut AppSettings, action: &Action) -> Result<(), String> {
+    match action {
+        Action::SetEqEnabled { enabled } => settings.eq_enabled = *enabled,
+        Action::ToggleEq => settings.eq_enabled = !settings.eq_enabled,
+        Action::SetPreamp { preamp } => {
+            if !preamp.is_finite() {
+                return Err(format!("preamp must be a finite number, got {}", preamp));
+            }
+            settings.preamp = *preamp;
+        }
+        Action::AdjustPreamp { delta } => {
+            if !delta.is_finite() {
+                return Err(format!("delta must be a finite number, got {}", delta));
+            }
+            settings.preamp = (settings.preamp + delta).clamp(-PREAMP_LIMIT_DB, PREAMP_LIMIT_DB);
+        }
+        Action::UpdateBand { index, patch } => {
+            let count = settings.bands.len();
+            let band = settings
+                .bands
+                .get_mut(*index)
+                .ok_or_else(|| format!("Band {} does not exist ({} bands)", index, count))?;
+            patch.apply_to(band)?;
+        }
+        Action::ApplyProfile { .. }
+        | Action::NextProfile
+        | Action::PreviousProfile
+        | Action::ApplyFavorite { .. }
+        | Action::ApplyAbOption { .. }
+        | Action::ShowWindow => {
+            return Err("Action does not change settings directly".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Returns the profile after (or before) `current` in `profiles`, wrapping around.
+///
+/// If there is no current profile, or it is not in the list, cycling forward
+/// starts at the first profile and cycling backward at the last.
+pub fn cycle_profile(profiles: &[String], current: Option<&str>, forward: bool) -> Option<String> {
+    if profiles.is_empty() {
+        return None;
+    }
+
+    let len = profiles.len();
+    let position = current.and_then(|name| profiles.iter().position(|p| p == name));
+    let index = match (position, forward) {
+        (Some(i), true) => (i + 1) % len,
+        (Some(i), false) => (i + len - 1) % len,
+        (None, true) => 0,
+        (None, false) => len - 1,
+    };
+    Some(profiles[index].clone())
+}
+
+/// Returns the profile name stored in a favorite slot (1–9).
+///
+/// # Errors
+///
+/// Returns an error if the slot is out of range or empty.
+pub fn favorite_profile(settings: &AppSettings, slot: usize) -> Result<String, String> {
+    if !(1..=MAX_FAVORITES).contains(&slot) {
+        return Err(format!(
+            "Favorite slot must be between 1 and {}, got {}",
+            MAX_FAVORITES, slot
+        ));
+    }
+    settings
+        .favorites
+        .get(slot - 1)
+        .filter(|name| !name.is_empty())
+        .cloned()
+        .ok_or_else(|| format!("Favorite slot {} is empty", slot))
+}
+
+/// Moves `name` to the front of the recent profiles list.
+pub fn push_recent_profile(settings: &mut AppSettings, name: &str) {
+    settings.recent_profiles.retain(|recent| recent != name);
+    settings.recent_profiles.insert(0, name.to_string());
+    settings.recent_profiles.truncate(MAX_RECENT_PROFILES);
+}
+
+/// Builds the profile written to the live config for the given settings.
+///
+/// While the EQ is enabled, the bands are first validated against
+/// `sample_rate` (see [`validation::enforce_bands`]); with `auto_clamp` set,
+/// invalid values are clamped in `settings` so that the stored bands match
+/// the config. Loudness compensation is then appended if enabled. In bypass
+/// neither happens: no band is written, so the EQ can always be bypassed.
+///
+/// # Errors
+///
+/// Returns an error if a band is invalid and not clamped, or the loudness
+/// settings are out of range.
+pub fn live_config_profile(
+    settings: &mut AppSettings,
+    sample_rate: u32,
+) -> Result<EqProfile, String> {
+    if !settings.eq_enabled {
+        return Ok(settings.live_profile());
+    }
+    let clamp = settings.validation.auto_clamp;
+    validation::enforce_bands(&mut settings.bands, sample_rate, clamp)?;
+    let mut profile = settings.live_profile();
+    loudness::apply_compensation(&mut profile, &settings.loudness)?;
+    Ok(profile)
+}
+
+/// Writes the live config for the given settings.
+///
+/// See [`live_config_profile`] for the validation and loudness
+/// compensation applied first. `sample_rate` should come from
+/// [`validation::resolve_sample_rate`], called before the settings are
+/// locked.
+///
+/// # Errors
+///
+/// Returns an error if a band is invalid and not clamped, the loudness
+/// settings are out of range, or the config cannot be written.
+pub fn apply_settings(settings: &mut AppSettings, sample_rate: u32) -> Result<(), String> {
+    let profile = live_config_profile(settings, sample_rate)?;
+    write_config(
+        &profile,
+        settings.config_path.clone(),
+        settings.eq_enabled,
+        &settings.config_precision,
+        sample_rate,
+    )
+}
+
+/// Emits `settings-changed` with the current settings and refreshes the
+/// tray menu, icon and tooltip.
+pub fn notify_settings_changed(app: &AppHandle) {
+    let settings = app.state::<AppState>().settings.lock().clone();
+    let _ = app.emit("settings-changed", settings);
+    let _ = update_tray_menu(app);
+    let _ = update_tray_status(app);
+}
+
+/// Applies a profile by name and makes it the current profile.
+///
+/// Loads the profile, applies it to EqualizerAPO, updates the application
+/// state, persists settings, and notifies the frontend of the change.
+///
+/// The profile is written to the configured EqualizerAPO config path. If
+/// writing the config or saving settings fails, the previous settings
+/// (including `current_profile`) are restored so the app never reports a
+/// profile that isn't actually applied.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle
+/// * `name` - The name of the profile to apply
+///
+/// # Errors
+///
+/// Returns an error if the profile cannot be loaded, has invalid bands (see
+/// [`live_config_profile`]) or cannot be applied, or if the settings cannot
+/// be saved.
+pub fn apply_profile_by_name(app: &AppHandle, name: &str) -> Result<(), String> {
+    let profile = load_profile(name.to_string())?;
+    let state = app.state::<AppState>();
+    // Resolved before locking the settings, which it locks itself
+    let sample_rate = validation::resolve_sample_rate(&state);
+
+    {
+        let mut settings = state.settings.lock();
+        let previous = settings.clone();
+
+        let mut updated = settings.clone();
+        updated.current_profile = Some(name.to_string());
+        push_recent_profile(&mut updated, name);
+        updated.load_live_profile(profile);
+
+        apply_settings(&mut updated, sample_rate)?;
+
+        *settings = updated;
+        if let Err(e) = save_settings(&settings) {
+            *settings = previous;
+            // Best effort: put the previous profile back in the live config
+            let _ = apply_settings(&mut settings, sample_rate);
+            return Err(e);
+        }
+    }
+
+    // Emit event to frontend so it can sync its state
+    let _ = app.emit("profile-changed-from-tray", name.to_string());
+
+    // Notify listeners and update tray menu to show new selection
+    notify_settings_changed(app);
+
+    Ok(())
+}
+
+/// Shows, unminimizes and focuses the main window.
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_band, LoudnessSettings};
+
+    fn settings_with_bands(count: usize) -> AppSettings {
+        let bands = (0..count)
+            .map(|i| test_band(FilterType::Peaking, 100.0 * (i + 1) as f32, 0.0, 1.0))
+            .collect();
+        AppSettings {
+            bands,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn describe_names_the_profile() {
+        let action = Action::ApplyProfile {
+            name: "Rock".to_string(),
+        };
+        assert_eq!(action.describe(), "Apply profile \"Rock\"");
+        assert_eq!(
+            Action::AdjustPreamp { delta: -0.5 }.describe(),
+            "Adjust preamp by -0.5 dB"
+        );
+    }
+
+    #[test]
+    fn live_config_profile_validates_bands_against_sample_rate() {
+        let mut settings = settings_with_bands(1);
+        settings.bands[0].frequency = 22000.0;
+
+        assert!(live_config_profile(&mut settings.clone(), 48000).is_ok());
+        let err = live_config_profile(&mut settings.clone(), 44100).unwrap_err();
+        assert!(err.contains("Nyquist"));
+
+        // In bypass the bands are not checked and no loudness bands are added
+        let mut bypassed = settings.clone();
+        bypassed.eq_enabled = false;
+        bypassed.loudness = LoudnessSettings {
+            enabled: true,
+            volume: -30.0,
+            ..Default::default()
+        };
+        let profile = live_config_profile(&mut bypassed, 44100).unwrap();
+        assert_eq!(profile.bands.len(), 1);
+        assert_eq!(profile.preamp, bypassed.preamp);
+
+        // With auto-clamp the stored bands are clamped too
+        settings.validation.auto_clamp = true;
+        let profile = live_config_profile(&mut settings, 44100).unwrap();
+        assert!(settings.bands[0].frequency < 22000.0);
+        assert_eq!(profile.bands[0].frequency, settings.bands[0].frequency);
+    }
+
+    #[test]
+    fn toggle_eq_flips_state() {
+        let mut settings = AppSettings::default();
+        update_settings_for(&mut settings, &Action::ToggleEq).unwrap();
+        assert!(!settings.eq_enabled);
+        update_settings_for(&mut settings, &Action::ToggleEq).unwrap();
+        assert!(settings.eq_enabled);
+    }
+
+    #[test]
+    fn set_preamp_rejects_nan() {
+        let mut settings = AppSettings::default();
+        let action = Action::SetPreamp { preamp: f32::NAN };
+        assert!(update_settings_for(&mut settings, &action).is_err());
+        assert_eq!(settings.preamp, 0.0);
+    }
+
+    #[test]
+    fn update_band_applies_patch() {
+        let mut settings = settings_with_bands(2);
+        let action = Action::UpdateBand {
+            index: 1,
+            patch: BandPatch {
+                gain: Some(-4.5),
+                enabled: Some(false),
+                ..Default::default()
+            },
+        };
+        update_settings_for(&mut settings, &action).unwrap();
+
+        assert_eq!(settings.bands[1].gain, -4.5);
+        assert!(!settings.bands[1].enabled);
+        assert_eq!(settings.bands[1].frequency, 200.0);
+        assert_eq!(settings.bands[0].gain, 0.0);
+    }
+
+    #[test]
+    fn update_band_out_of_range() {
+        let mut settings = settings_with_bands(1);
+        let action = Action::UpdateBand {
+            index: 3,
+            patch: BandPatch::default(),
+        };
+        assert!(update_settings_for(&mut settings, &action).is_err());
+    }
+
+    #[test]
+    fn band_patch_rejects_invalid_values_without_partial_update() {
+        let mut band = settings_with_bands(1).bands.remove(0);
+        let patch = BandPatch {
+            gain: Some(3.0),
+            q_factor: Some(0.0),
+            ..Default::default()
+        };
+        assert!(patch.apply_to(&mut band).is_err());
+        assert_eq!(band.gain, 0.0);
+    }
+
+    #[test]
+    fn adjust_preamp_clamps_to_limit() {
+        let mut settings = AppSettings {
+            preamp: 19.5,
+            ..Default::default()
+        };
+        let action = Action::AdjustPreamp { delta: 1.0 };
+        update_settings_for(&mut settings, &action).unwrap();
+        assert_eq!(settings.preamp, PREAMP_LIMIT_DB);
+
+        let action = Action::AdjustPreamp { delta: -0.5 };
+        update_settings_for(&mut settings, &action).unwrap();
+        assert_eq!(settings.preamp, 19.5);
+    }
+
+    #[test]
+    fn cycle_profile_wraps_around() {
+        let profiles = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        assert_eq!(
+            cycle_profile(&profiles, Some("C"), true).as_deref(),
+            Some("A")
+        );
+        assert_eq!(
+            cycle_profile(&profiles, Some("A"), false).as_deref(),
+            Some("C")
+        );
+        assert_eq!(
+            cycle_profile(&profiles, Some("A"), true).as_deref(),
+            Some("B")
+        );
+    }
+
+    #[test]
+    fn cycle_profile_without_current() {
+        let profiles = vec!["A".to_string(), "B".to_string()];
+        assert_eq!(cycle_profile(&profiles, None, true).as_deref(), Some("A"));
+        assert_eq!(
+            cycle_profile(&profiles, Some("Gone"), false).as_deref(),
+            Some("B")
+        );
+        assert_eq!(cycle_profile(&[], None, true), None);
+    }
+
+    #[test]
+    fn favorite_profile_slots() {
+        let settings = AppSettings {
+            favorites: vec!["Studio".to_string(), String::new()],
+            ..Default::default()
+        };
+        assert_eq!(favorite_profile(&settings, 1).unwrap(), "Studio");
+        assert!(favorite_profile(&settings, 2).is_err());
+        assert!(favorite_profile(&settings, 3).is_err());
+        assert!(favorite_profile(&settings, 0).is_err());
+        assert!(favorite_profile(&settings, 10).is_err());
+    }
+
+    #[test]
+    fn push_recent_profile_moves_to_front_and_truncates() {
+        let mut settings = AppSettings::default();
+        for name in ["A", "B", "C", "D", "E", "F"] {
+            push_recent_profile(&mut settings, name);
+        }
+        push_recent_profile(&mut settings, "D");
+        assert_eq!(settings.recent_profiles, vec!["D", "F", "E", "C", "B"]);
+    }
+
+    #[test]
+    fn action_deserializes_from_tagged_json() {
+        let action: Action =
+            serde_json::from_str(r#"{"action": "update_band", "index": 0, "gain": 2.0}"#).unwrap();
+        assert_eq!(
+            action,
+            Action::UpdateBand {
+                index: 0,
+                patch: BandPatch {
+                    gain: Some(2.0),
+                    ..Default::default()
+                },
+            }
+        );
+    }
+}
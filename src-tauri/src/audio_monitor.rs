@@ -657,12 +657,8 @@ where
             // Calculate peak from samples
             // SAFETY: buffer_ptr is valid and contains `frames_available * channels` samples.
             // The buffer format matches what we detected from GetMixFormat.
-            let max_sample = calculate_peak_from_buffer(
-                buffer_ptr,
-                sample_count,
-                bytes_per_sample,
-                is_float,
-            );
+            let max_sample =
+                calculate_peak_from_buffer(buffer_ptr, sample_count, bytes_per_sample, is_float);
 
             // Update peak state with fast attack, slow decay
             {
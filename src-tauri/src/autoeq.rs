@@ -0,0 +1,746 @@
+//! Import of AutoEQ headphone correction results.
+//!
+//! [AutoEQ] publishes a `ParametricEQ.txt` and a `GraphicEQ.txt` file for
+//! each headphone, both in EqualizerAPO syntax:
+//!
+//! ```text
+//! Preamp: -6.2 dB
+//! Filter 1: ON LSC Fc 105 Hz Gain 5.5 dB Q 0.70
+//! Filter 2: ON PK Fc 150 Hz Gain -2.1 dB Q 0.50
+//! ```
+//!
+//! ```text
+//! GraphicEQ: 20 -2.5; 21 -2.6; 22 -2.6; ...
+//! ```
+//!
+//! Files can be imported one at a time, or in bulk from a local copy of the
+//! AutoEQ `results` directory, where each headphone has its own folder:
+//!
+//! ```text
+//! results/oratory1990/over-ear/Sennheiser HD 600/Sennheiser HD 600 ParametricEQ.txt
+//! ```
+//!
+//! Imported profiles record the file they came from in
+//! [`ProfileMetadata::source`], and batch imports tag each profile with the
+//! directories between the results root and the headphone folder (e.g.
+//! `oratory1990`, `over-ear`).
+//!
+//! [AutoEQ]: https://github.com/jaakkopasanen/AutoEq
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::profile::{list_profiles, sanitize_profile_name, write_profile};
+use crate::tray::update_tray_menu;
+use crate::types::{
+    EqProfile, FilterType, GraphicEqPoint, ParametricBand, ProfileMetadata, ProfileSource,
+};
+use crate::vst;
+
+/// Folder assigned to profiles from a batch import when none is given.
+pub const DEFAULT_IMPORT_FOLDER: &str = "AutoEQ";
+
+/// Q used for `LS`/`HS` shelves, which have no Q in EqualizerAPO syntax.
+const DEFAULT_SHELF_Q: f32 = 0.707;
+
+/// Which of AutoEQ's result files to import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoEqVariant {
+    /// `ParametricEQ.txt`: preamp and up to ~10 filters.
+    Parametric,
+    /// `GraphicEQ.txt`: a single `GraphicEQ:` line with ~127 points.
+    Graphic,
+}
+
+impl AutoEqVariant {
+    /// Returns the file name suffix AutoEQ uses for this variant.
+    pub fn file_suffix(self) -> &'static str {
+        match self {
+            AutoEqVariant::Parametric => "ParametricEQ.txt",
+            AutoEqVariant::Graphic => "GraphicEQ.txt",
+        }
+    }
+
+    /// Returns the format identifier stored in [`ProfileSource::format`].
+    pub fn format_id(self) -> &'static str {
+        match self {
+            AutoEqVariant::Parametric => "autoeq-parametric",
+            AutoEqVariant::Graphic => "autoeq-graphic",
+        }
+    }
+
+    /// Detects the variant from the file name, falling back to the content.
+    pub fn detect(path: &Path, content: &str) -> Self {
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if file_name.ends_with(AutoEqVariant::Graphic.file_suffix()) {
+            AutoEqVariant::Graphic
+        } else if file_name.ends_with(AutoEqVariant::Parametric.file_suffix()) {
+            AutoEqVariant::Parametric
+        } else if content
+            .lines()
+            .any(|line| line.trim_start().starts_with("GraphicEQ:"))
+        {
+            AutoEqVariant::Graphic
+        } else {
+            AutoEqVariant::Parametric
+        }
+    }
+}
+
+/// Summary of a batch import.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AutoEqImportReport {
+    /// Names of the profiles that were written.
+    pub imported: Vec<String>,
+    /// Names of profiles that already existed and were left unchanged.
+    pub skipped: Vec<String>,
+    /// Files that could not be imported.
+    pub failed: Vec<ImportFailure>,
+}
+
+/// A file that could not be imported, with the reason.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// A result file found in a directory tree, with the profile it will become.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedImport {
+    pub path: PathBuf,
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+/// The result files found in a directory tree.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportPlan {
+    /// Files to import, with unique profile names.
+    pub imports: Vec<PlannedImport>,
+    /// Files that cannot be imported, with the reason.
+    pub failed: Vec<ImportFailure>,
+}
+
+// =============================================================================
+// Parsing
+// =============================================================================
+
+/// Parses a number, rejecting NaN and infinity.
+fn parse_number(token: Option<&str>, field: &str) -> Result<f32, String> {
+    let token = token.ok_or_else(|| format!("Missing value for {}", field))?;
+    token
+        .parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("Invalid {} value '{}'", field, token))
+}
+
+/// Parses a `Preamp: -6.2 dB` line, returning the gain in dB.
+///
+/// # Errors
+///
+/// Returns an error if the line is not a preamp line or the value is invalid.
+pub fn parse_preamp_line(line: &str) -> Result<f32, String> {
+    let rest = line
+        .trim()
+        .strip_prefix("Preamp:")
+        .ok_or_else(|| format!("Not a preamp line: '{}'", line))?;
+    parse_number(rest.split_whitespace().next(), "preamp")
+}
+
+/// Parses an EqualizerAPO filter line such as
+/// `Filter 1: ON PK Fc 105 Hz Gain 5.5 dB Q 0.70`.
+///
+/// Supports peaking (`PK`, `PEQ`, `Modal`) and shelf (`LSC`, `HSC`, `LS`,
+/// `HS`) filters with a Q value. `OFF` filters are returned disabled.
+///
+/// # Errors
+///
+/// Returns an error for other filter types, bandwidth given in octaves
+/// instead of Q, or missing or invalid values.
+pub fn parse_filter_line(line: &str) -> Result<ParametricBand, String> {
+    let line = line.trim();
+    let (_, rest) = line
+        .split_once(':')
+        .filter(|(head, _)| head.starts_with("Filter"))
+        .ok_or_else(|| format!("Not a filter line: '{}'", line))?;
+
+    let mut tokens = rest.split_whitespace();
+    let enabled = match tokens.next() {
+        Some("ON") => true,
+        Some("OFF") => false,
+        other => {
+            return Err(format!(
+                "Expected ON or OFF, got '{}'",
+                other.unwrap_or_default()
+            ))
+        }
+    };
+
+    let type_code = tokens.next().ok_or("Missing filter type")?;
+    let filter_type = match type_code {
+        "PK" | "PEQ" | "Modal" => FilterType::Peaking,
+        "LSC" | "LS" => FilterType::LowShelf,
+        "HSC" | "HS" => FilterType::HighShelf,
+        other => return Err(format!("Unsupported filter type '{}'", other)),
+    };
+
+    let (mut frequency, mut gain, mut q_factor) = (None, 0.0, None);
+    while let Some(token) = tokens.next() {
+        match token {
+            "Fc" => frequency = Some(parse_number(tokens.next(), "Fc")?),
+            "Gain" => gain = parse_number(tokens.next(), "Gain")?,
+            "Q" => q_factor = Some(parse_number(tokens.next(), "Q")?),
+            "BW" => return Err("Bandwidth in octaves is not supported; use Q".to_string()),
+            // Units and the optional shelf slope in dB
+            _ => {}
+        }
+    }
+
+    let frequency = frequency
+        .filter(|f| *f > 0.0)
+        .ok_or("Missing or non-positive Fc")?;
+    let q_factor = match (q_factor, &filter_type) {
+        (Some(q), _) if q > 0.0 => q,
+        (Some(q), _) => return Err(format!("Q must be positive, got {}", q)),
+        (None, FilterType::LowShelf | FilterType::HighShelf) => DEFAULT_SHELF_Q,
+        (None, FilterType::Peaking) => return Err("Missing Q".to_string()),
+    };
+
+    Ok(ParametricBand {
+        filter_type,
+        frequency,
+        gain,
+        q_factor,
+        enabled,
+    })
+}
+
+/// Parses a `GraphicEQ: 20 -2.5; 21 -2.6; ...` line into points sorted by
+/// frequency.
+///
+/// # Errors
+///
+/// Returns an error if the line is not a GraphicEQ line, a point is
+/// malformed, or there are no points.
+pub fn parse_graphic_eq_line(line: &str) -> Result<Vec<GraphicEqPoint>, String> {
+    let rest = line
+        .trim()
+        .strip_prefix("GraphicEQ:")
+        .ok_or_else(|| format!("Not a GraphicEQ line: '{}'", line))?;
+
+    let mut points = Vec::new();
+    for pair in rest.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let mut values = pair.split_whitespace();
+        let frequency = parse_number(values.next(), "frequency")?;
+        let gain = parse_number(values.next(), "gain")?;
+        if frequency <= 0.0 || values.next().is_some() {
+            return Err(format!("Invalid GraphicEQ point '{}'", pair));
+        }
+        points.push(GraphicEqPoint { frequency, gain });
+    }
+
+    if points.is_empty() {
+        return Err("GraphicEQ line has no points".to_string());
+    }
+    points.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+    Ok(points)
+}
+
+/// Parses the content of an AutoEQ result file into a profile.
+///
+/// Blank lines and `#` comments are ignored. Multiple `Preamp:` lines are
+/// summed, as EqualizerAPO does. A `VSTPlugin:` line becomes the profile's
+/// VST plugin stage, so configs that host a plugin keep it.
+///
+/// # Errors
+///
+/// Returns an error naming the line number if a line cannot be parsed, or if
+/// the file contains no filters (parametric) or no `GraphicEQ:` line (graphic).
+pub fn parse_autoeq(
+    name: &str,
+    content: &str,
+    variant: AutoEqVariant,
+) -> Result<EqProfile, String> {
+    let mut profile = EqProfile {
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        let result = if line.is_empty() || line.starts_with('#') {
+            Ok(())
+        } else if line.starts_with("Preamp:") {
+            parse_preamp_line(line).map(|preamp| profile.preamp += preamp)
+        } else if variant == AutoEqVariant::Parametric && line.starts_with("Filter") {
+            parse_filter_line(line).map(|band| profile.bands.push(band))
+        } else if variant == AutoEqVariant::Graphic && line.starts_with("GraphicEQ:") {
+            parse_graphic_eq_line(line).map(|points| profile.graphic_eq = points)
+        } else if line.starts_with("VSTPlugin:") {
+            vst::parse_config_line(line).map(|plugin| profile.vst_plugin = Some(plugin))
+        } else {
+            Err(format!("Unexpected line '{}'", line))
+        };
+        result.map_err(|e| format!("Line {}: {}", index + 1, e))?;
+    }
+
+    match variant {
+        AutoEqVariant::Parametric if profile.bands.is_empty() => {
+            Err("No filters found".to_string())
+        }
+        AutoEqVariant::Graphic if profile.graphic_eq.is_empty() => {
+            Err("No GraphicEQ line found".to_string())
+        }
+        _ => Ok(profile),
+    }
+}
+
+// =============================================================================
+// Naming
+// =============================================================================
+
+/// Derives a profile name from an AutoEQ file name, e.g.
+/// `Sennheiser HD 600 ParametricEQ.txt` → `Sennheiser HD 600`.
+pub fn profile_name_from_path(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    let stem = [AutoEqVariant::Parametric, AutoEqVariant::Graphic]
+        .iter()
+        .find_map(|v| file_name.strip_suffix(v.file_suffix()))
+        .or_else(|| file_name.strip_suffix(".txt"))
+        .unwrap_or(file_name);
+    Some(sanitize_profile_name(stem)).filter(|name| !name.is_empty())
+}
+
+/// Reads and parses an AutoEQ result file.
+///
+/// The variant is detected from the file name or content, and the profile is
+/// named after the file unless `name` is given.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, or no name can be
+/// derived.
+pub fn read_autoeq_file(path: &Path, name: Option<&str>) -> Result<EqProfile, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let variant = AutoEqVariant::detect(path, &content);
+
+    let name = match name {
+        Some(name) => sanitize_profile_name(name),
+        None => profile_name_from_path(path).unwrap_or_default(),
+    };
+    if name.is_empty() {
+        return Err(format!("Cannot derive a profile name from {:?}", path));
+    }
+
+    let mut profile = parse_autoeq(&name, &content, variant)?;
+    profile.metadata.source = Some(ProfileSource {
+        format: variant.format_id().to_string(),
+        path: path.to_string_lossy().into_owned(),
+    });
+    Ok(profile)
+}
+
+// =============================================================================
+// Batch Import
+// =============================================================================
+
+/// Collects all files below `dir` whose name ends with `suffix`, sorted.
+fn collect_files(dir: &Path, suffix: &str, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_files(&path, suffix, files)?;
+        } else if file_type.is_file()
+            && path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with(suffix))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Finds the result files of one variant below `root` and works out the
+/// profile name and tags for each.
+///
+/// Tags are the directories between `root` and the headphone folder. When
+/// the same headphone appears more than once (e.g. measured by different
+/// sources or against different targets), later entries get the tag path
+/// appended, as in `Sennheiser HD 600 (crinacle, over-ear)`. If that name is
+/// taken too, a number is added (`Sennheiser HD 600 (crinacle, over-ear) 2`),
+/// so no file is dropped. Files no name can be derived from are listed in
+/// [`ImportPlan::failed`].
+///
+/// # Errors
+///
+/// Returns an error if `root` or a directory below it cannot be read.
+pub fn plan_directory_import(root: &Path, variant: AutoEqVariant) -> Result<ImportPlan, String> {
+    let mut files = Vec::new();
+    collect_files(root, variant.file_suffix(), &mut files)?;
+    files.sort();
+
+    let mut names = HashSet::new();
+    let mut plan = ImportPlan::default();
+    for path in files {
+        let Some(base_name) = profile_name_from_path(&path) else {
+            plan.failed.push(ImportFailure {
+                path: path.to_string_lossy().into_owned(),
+                error: "Cannot derive a profile name from the file name".to_string(),
+            });
+            continue;
+        };
+
+        // Skip the file itself and the headphone folder
+        let tags: Vec<String> = path
+            .strip_prefix(root)
+            .ok()
+            .and_then(|relative| relative.parent()?.parent())
+            .map(|dirs| {
+                dirs.components()
+                    .filter_map(|c| c.as_os_str().to_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut name = base_name.clone();
+        if names.contains(&name) && !tags.is_empty() {
+            name = format!(
+                "{} ({})",
+                base_name,
+                sanitize_profile_name(&tags.join(", "))
+            );
+        }
+        let unsuffixed = name.clone();
+        let mut counter = 2;
+        while names.contains(&name) {
+            name = format!("{} {}", unsuffixed, counter);
+            counter += 1;
+        }
+
+        names.insert(name.clone());
+        plan.imports.push(PlannedImport { path, name, tags });
+    }
+    Ok(plan)
+}
+
+/// Imports every result file of one variant below `root` as a profile.
+///
+/// Files that fail to parse are reported in
+/// [`AutoEqImportReport::failed`] and do not stop the import.
+///
+/// # Arguments
+///
+/// * `root` - AutoEQ `results` directory, or any directory below it
+/// * `variant` - Which result files to import
+/// * `folder` - Folder for the imported profiles (default [`DEFAULT_IMPORT_FOLDER`])
+/// * `overwrite` - Replace existing profiles with the same name
+///
+/// # Errors
+///
+/// Returns an error if the directory tree or the profile list cannot be read.
+pub fn import_directory(
+    root: &Path,
+    variant: AutoEqVariant,
+    folder: Option<&str>,
+    overwrite: bool,
+) -> Result<AutoEqImportReport, String> {
+    let existing: HashSet<String> = list_profiles()?.into_iter().collect();
+    let folder = folder
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .unwrap_or(DEFAULT_IMPORT_FOLDER);
+
+    let plan = plan_directory_import(root, variant)?;
+    let mut report = AutoEqImportReport {
+        failed: plan.failed,
+        ..Default::default()
+    };
+    for planned in plan.imports {
+        if !overwrite && existing.contains(&planned.name) {
+            report.skipped.push(planned.name);
+            continue;
+        }
+
+        let result = read_autoeq_file(&planned.path, Some(&planned.name)).and_then(|profile| {
+            write_profile(&EqProfile {
+                metadata: ProfileMetadata {
+                    folder: Some(folder.to_string()),
+                    tags: planned.tags,
+                    ..profile.metadata
+                },
+                ..profile
+            })
+        });
+        match result {
+            Ok(()) => report.imported.push(planned.name),
+            Err(error) => report.failed.push(ImportFailure {
+                path: planned.path.to_string_lossy().into_owned(),
+                error,
+            }),
+        }
+    }
+    Ok(report)
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Imports a single AutoEQ `ParametricEQ.txt` or `GraphicEQ.txt` file and
+/// saves it as a profile.
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to import
+/// * `name` - Profile name; derived from the file name if `None`
+/// * `overwrite` - Replace an existing profile with the same name
+/// * `app` - Tauri app handle for tray updates
+///
+/// # Returns
+///
+/// The saved profile.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be parsed, or a profile with the same
+/// name exists and `overwrite` is `false`.
+#[tauri::command]
+pub fn import_autoeq_file(
+    path: String,
+    name: Option<String>,
+    overwrite: bool,
+    app: AppHandle,
+) -> Result<EqProfile, String> {
+    let profile = read_autoeq_file(Path::new(&path), name.as_deref())?;
+    if !overwrite && list_profiles()?.contains(&profile.name) {
+        return Err(format!("Profile '{}' already exists", profile.name));
+    }
+    write_profile(&profile)?;
+
+    let _ = update_tray_menu(&app);
+    Ok(profile)
+}
+
+/// Imports all AutoEQ results of one variant found below a directory.
+///
+/// See [`import_directory`] for details.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read.
+#[tauri::command]
+pub fn import_autoeq_directory(
+    path: String,
+    variant: AutoEqVariant,
+    folder: Option<String>,
+    overwrite: bool,
+    app: AppHandle,
+) -> Result<AutoEqImportReport, String> {
+    let report = import_directory(Path::new(&path), variant, folder.as_deref(), overwrite)?;
+
+    let _ = update_tray_menu(&app);
+    Ok(report)
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMETRIC: &str = "Preamp: -6.2 dB\n\
+        Filter 1: ON LSC Fc 105 Hz Gain 5.5 dB Q 0.70\n\
+        Filter 2: ON PK Fc 150.5 Hz Gain -2.1 dB Q 0.50\n\
+        Filter 3: OFF HSC Fc 10000 Hz Gain -3.1 dB Q 0.70\n";
+
+    #[test]
+    fn parses_parametric_file() {
+        let profile = parse_autoeq("HD 600", PARAMETRIC, AutoEqVariant::Parametric).unwrap();
+        assert_eq!(profile.preamp, -6.2);
+        assert_eq!(profile.bands.len(), 3);
+
+        let band = &profile.bands[1];
+        assert_eq!(band.filter_type, FilterType::Peaking);
+        assert_eq!(band.frequency, 150.5);
+        assert_eq!(band.gain, -2.1);
+        assert_eq!(band.q_factor, 0.5);
+        assert!(band.enabled);
+
+        assert_eq!(profile.bands[0].filter_type, FilterType::LowShelf);
+        assert!(!profile.bands[2].enabled);
+    }
+
+    #[test]
+    fn filter_line_without_number() {
+        let band = parse_filter_line("Filter: ON PK Fc 1000 Hz Gain 3 dB Q 1.41").unwrap();
+        assert_eq!(band.frequency, 1000.0);
+        assert_eq!(band.q_factor, 1.41);
+    }
+
+    #[test]
+    fn shelf_without_q_uses_default() {
+        let band = parse_filter_line("Filter 1: ON LS Fc 100 Hz Gain 4 dB").unwrap();
+        assert_eq!(band.q_factor, DEFAULT_SHELF_Q);
+    }
+
+    #[test]
+    fn rejects_unsupported_filters() {
+        assert!(parse_filter_line("Filter 1: ON LP Fc 100 Hz")
+            .unwrap_err()
+            .contains("LP"));
+        assert!(parse_filter_line("Filter 1: ON PK Fc 100 Hz Gain 1 dB BW Oct 1").is_err());
+        assert!(parse_filter_line("Filter 1: ON PK Fc 100 Hz Gain 1 dB").is_err());
+        assert!(parse_filter_line("Filter 1: ON PK Fc abc Hz Gain 1 dB Q 1").is_err());
+    }
+
+    #[test]
+    fn parse_error_names_the_line() {
+        let content = "Preamp: -1 dB\nFilter 1: ON XX Fc 100 Hz Gain 1 dB Q 1\n";
+        let err = parse_autoeq("x", content, AutoEqVariant::Parametric).unwrap_err();
+        assert!(err.starts_with("Line 2:"), "{}", err);
+    }
+
+    #[test]
+    fn keeps_vst_plugin_line() {
+        let content = format!(
+            "{}VSTPlugin: Library \"limiter.dll\" ChunkData \"AAAA\"\n",
+            PARAMETRIC
+        );
+        let profile = parse_autoeq("HD 600", &content, AutoEqVariant::Parametric).unwrap();
+        let plugin = profile.vst_plugin.unwrap();
+        assert_eq!(plugin.library, "limiter.dll");
+        assert_eq!(plugin.chunk.as_deref(), Some("AAAA"));
+        assert_eq!(profile.bands.len(), 3);
+    }
+
+    #[test]
+    fn parametric_file_without_filters_is_an_error() {
+        assert!(parse_autoeq("x", "Preamp: -1 dB\n", AutoEqVariant::Parametric).is_err());
+    }
+
+    #[test]
+    fn parses_graphic_file() {
+        let content = "GraphicEQ: 20 -2.5; 10000 1.0; 21 -2.6;\n";
+        let profile = parse_autoeq("HD 600", content, AutoEqVariant::Graphic).unwrap();
+        assert!(profile.bands.is_empty());
+        assert_eq!(
+            profile.graphic_eq,
+            vec![
+                GraphicEqPoint {
+                    frequency: 20.0,
+                    gain: -2.5
+                },
+                GraphicEqPoint {
+                    frequency: 21.0,
+                    gain: -2.6
+                },
+                GraphicEqPoint {
+                    frequency: 10000.0,
+                    gain: 1.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_graphic_points() {
+        assert!(parse_graphic_eq_line("GraphicEQ: 20").is_err());
+        assert!(parse_graphic_eq_line("GraphicEQ: 20 1 2").is_err());
+        assert!(parse_graphic_eq_line("GraphicEQ: ").is_err());
+    }
+
+    #[test]
+    fn detects_variant_from_name_then_content() {
+        let graphic = Path::new("HD 600 GraphicEQ.txt");
+        let parametric = Path::new("HD 600 ParametricEQ.txt");
+        let other = Path::new("preset.txt");
+        assert_eq!(AutoEqVariant::detect(graphic, ""), AutoEqVariant::Graphic);
+        assert_eq!(
+            AutoEqVariant::detect(parametric, "GraphicEQ: 20 1"),
+            AutoEqVariant::Parametric
+        );
+        assert_eq!(
+            AutoEqVariant::detect(other, "GraphicEQ: 20 1"),
+            AutoEqVariant::Graphic
+        );
+        assert_eq!(
+            AutoEqVariant::detect(other, PARAMETRIC),
+            AutoEqVariant::Parametric
+        );
+    }
+
+    #[test]
+    fn profile_names_from_file_names() {
+        assert_eq!(
+            profile_name_from_path(Path::new("/r/Sennheiser HD 600 ParametricEQ.txt")),
+            Some("Sennheiser HD 600".to_string())
+        );
+        assert_eq!(
+            profile_name_from_path(Path::new("AKG K371: v2 GraphicEQ.txt")),
+            Some("AKG K371_ v2".to_string())
+        );
+        assert_eq!(profile_name_from_path(Path::new(" ParametricEQ.txt")), None);
+    }
+
+    #[test]
+    fn plans_directory_import_with_tags_and_unique_names() {
+        let root = std::env::temp_dir().join(format!("eqapo-autoeq-{}", std::process::id()));
+        let write = |relative: &str| {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, PARAMETRIC).unwrap();
+        };
+        write("oratory1990/over-ear/HD 600/HD 600 ParametricEQ.txt");
+        write("oratory1990/over-ear/HD 600/HD 600 GraphicEQ.txt");
+        write("oratory1990/in-ear/HD 600/HD 600 ParametricEQ.txt");
+        write("rtings/over-ear/HD 600/HD 600 ParametricEQ.txt");
+        write("rtings/over-ear/HD 600 v2/HD 600 ParametricEQ.txt");
+        write("crinacle/in-ear/Blessing 2/Blessing 2 ParametricEQ.txt");
+        write("crinacle/in-ear/Unnamed/ ParametricEQ.txt");
+
+        let plan = plan_directory_import(&root, AutoEqVariant::Parametric).unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        let summary: Vec<(&str, Vec<&str>)> = plan
+            .imports
+            .iter()
+            .map(|p| (p.name.as_str(), p.tags.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Blessing 2", vec!["crinacle", "in-ear"]),
+                ("HD 600", vec!["oratory1990", "in-ear"]),
+                (
+                    "HD 600 (oratory1990, over-ear)",
+                    vec!["oratory1990", "over-ear"]
+                ),
+                ("HD 600 (rtings, over-ear)", vec!["rtings", "over-ear"]),
+                ("HD 600 (rtings, over-ear) 2", vec!["rtings", "over-ear"]),
+            ]
+        );
+        assert_eq!(plan.failed.len(), 1);
+        assert!(plan.failed[0].path.ends_with(" ParametricEQ.txt"));
+    }
+}
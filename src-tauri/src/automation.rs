@@ -0,0 +1,786 @@
+//! Opt-in local automation API over loopback HTTP and WebSocket.
+//!
+//! Lets scripts on the same machine (Stream Deck actions, Home Assistant,
+//! test harnesses) drive the EQ. Every mutating request is turned into an
+//! [`Action`] and executed by [`crate::actions::dispatch`], so it behaves
+//! exactly like the same change made from the tray or the main window.
+//!
+//! # Security
+//!
+//! - The server is disabled by default and only binds to `127.0.0.1`
+//! - Every request must carry the token from [`AutomationSettings`], either as
+//!   `Authorization: Bearer <token>` or as a `?token=<token>` query parameter
+//!
+//! [`AutomationSettings`]: crate::types::AutomationSettings
+//!
+//! # Endpoints
+//!
+//! | Method  | Path                          | Body                    | Response            |
+//! |---------|-------------------------------|-------------------------|---------------------|
+//! | `GET`   | `/api/state`                  |                         | Settings            |
+//! | `GET`   | `/api/profiles`               |                         | Profile names       |
+//! | `POST`  | `/api/profiles/{name}/apply`  |                         | Settings            |
+//! | `GET`   | `/api/preamp`                 |                         | `{"preamp": -3.0}`  |
+//! | `PUT`   | `/api/preamp`                 | `{"preamp": -3.0}`      | Settings            |
+//! | `GET`   | `/api/bypass`                 |                         | `{"bypassed": false}` |
+//! | `PUT`   | `/api/bypass`                 | `{"bypassed": true}`    | Settings            |
+//! | `GET`   | `/api/bands`                  |                         | Bands               |
+//! | `PATCH` | `/api/bands/{index}`          | `{"gain": 2.5, ...}`    | Settings            |
+//! | `GET`   | `/api/events`                 | WebSocket upgrade       | Event stream        |
+//!
+//! Errors are returned as `{"error": "message"}` with a 4xx/5xx status code.
+//!
+//! # Event Stream
+//!
+//! `/api/events` upgrades to a WebSocket that receives one JSON text message
+//! per event, `{"event": "<name>", "payload": ...}`. The current settings are
+//! sent as a `settings-changed` event right after connecting. Forwarded events:
+//!
+//! - `settings-changed` - Any setting changed (payload: settings)
+//! - `profile-changed-from-tray` - A profile was applied (payload: name)
+//! - `peak_meter_update` - Peak meter reading, while the meter is running
+
+use std::io::{Read, Write};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use parking_lot::Mutex;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, EventId, Listener, Manager};
+use tiny_http::{Header, Request, Response, Server};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+use crate::actions::{dispatch, update_settings_for, Action, BandPatch};
+use crate::notifications::{report_error, ErrorSource};
+use crate::profile::{list_profiles, save_settings};
+use crate::types::{AppSettings, AppState};
+
+/// App events forwarded to WebSocket clients.
+const FORWARDED_EVENTS: [&str; 3] = [
+    "settings-changed",
+    "profile-changed-from-tray",
+    "peak_meter_update",
+];
+
+/// Channels of connected WebSocket clients.
+type Subscribers = Arc<Mutex<Vec<Sender<String>>>>;
+
+/// A running automation server.
+///
+/// The server is stopped when dropped: the listening socket is unblocked,
+/// event listeners are removed and WebSocket clients are disconnected.
+pub struct AutomationServer {
+    app: AppHandle,
+    server: Arc<Server>,
+    port: u16,
+    subscribers: Subscribers,
+    listeners: Vec<EventId>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl AutomationServer {
+    /// Starts the server on `127.0.0.1:{port}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port cannot be bound (e.g. already in use).
+    pub fn start(app: &AppHandle, port: u16, token: String) -> Result<Self, String> {
+        let server = Server::http(("127.0.0.1", port))
+            .map_err(|e| format!("Failed to start automation server on port {}: {}", port, e))?;
+        let server = Arc::new(server);
+        let subscribers: Subscribers = Arc::default();
+
+        let listeners = FORWARDED_EVENTS
+            .iter()
+            .map(|&name| {
+                let subscribers = Arc::clone(&subscribers);
+                app.listen_any(name, move |event| {
+                    broadcast(&subscribers, name, event.payload());
+                })
+            })
+            .collect();
+
+        let thread = {
+            let server = Arc::clone(&server);
+            let subscribers = Arc::clone(&subscribers);
+            let app = app.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle_request(&app, &token, &subscribers, request);
+                }
+            })
+        };
+
+        Ok(Self {
+            app: app.clone(),
+            server,
+            port,
+            subscribers,
+            listeners,
+            thread: Some(thread),
+        })
+    }
+
+    /// Returns the port the server is listening on.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for AutomationServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        for id in self.listeners.drain(..) {
+            self.app.unlisten(id);
+        }
+        // Dropping the senders ends each client's forwarding loop
+        self.subscribers.lock().clear();
+        if let Some(handle) = self.thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Sends an event to every connected WebSocket client, dropping closed ones.
+fn broadcast(subscribers: &Subscribers, event: &str, payload: &str) {
+    let message = format!(r#"{{"event":"{}","payload":{}}}"#, event, payload);
+    subscribers
+        .lock()
+        .retain(|tx| tx.send(message.clone()).is_ok());
+}
+
+// =============================================================================
+// Routing
+// =============================================================================
+
+/// An API endpoint.
+#[derive(Debug, PartialEq)]
+enum Route {
+    State,
+    ListProfiles,
+    ApplyProfile(String),
+    GetPreamp,
+    SetPreamp,
+    GetBypass,
+    SetBypass,
+    ListBands,
+    UpdateBand(usize),
+    Events,
+}
+
+/// An error response with its HTTP status code.
+#[derive(Debug, PartialEq)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Body of `PUT /api/preamp`.
+#[derive(Deserialize)]
+struct PreampBody {
+    preamp: f32,
+}
+
+/// Body of `PUT /api/bypass`.
+#[derive(Deserialize)]
+struct BypassBody {
+    bypassed: bool,
+}
+
+/// Maps a request method and path to a route.
+///
+/// # Errors
+///
+/// Returns 404 for unknown paths, 405 for known paths with the wrong method,
+/// and 400 for malformed path parameters.
+fn match_route(method: &str, path: &str) -> Result<Route, ApiError> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let route = match (method, segments.as_slice()) {
+        ("GET", ["api", "state"]) => Route::State,
+        ("GET", ["api", "profiles"]) => Route::ListProfiles,
+        ("POST", ["api", "profiles", name, "apply"]) => {
+            let name = percent_decode(name)
+                .ok_or_else(|| ApiError::new(400, "Invalid profile name encoding"))?;
+            Route::ApplyProfile(name)
+        }
+        ("GET", ["api", "preamp"]) => Route::GetPreamp,
+        ("PUT", ["api", "preamp"]) => Route::SetPreamp,
+        ("GET", ["api", "bypass"]) => Route::GetBypass,
+        ("PUT", ["api", "bypass"]) => Route::SetBypass,
+        ("GET", ["api", "bands"]) => Route::ListBands,
+        ("PATCH", ["api", "bands", index]) => {
+            let index = index
+                .parse()
+                .map_err(|_| ApiError::new(400, format!("Invalid band index: {}", index)))?;
+            Route::UpdateBand(index)
+        }
+        ("GET", ["api", "events"]) => Route::Events,
+        (
+            _,
+            ["api", "state"]
+            | ["api", "profiles"]
+            | ["api", "profiles", _, "apply"]
+            | ["api", "preamp"]
+            | ["api", "bypass"]
+            | ["api", "bands"]
+            | ["api", "bands", _]
+            | ["api", "events"],
+        ) => return Err(ApiError::new(405, "Method not allowed")),
+        _ => return Err(ApiError::new(404, format!("Not found: {}", path))),
+    };
+
+    Ok(route)
+}
+
+/// Converts a mutating route and its JSON body into an action.
+///
+/// # Returns
+///
+/// `None` for read-only routes.
+///
+/// # Errors
+///
+/// Returns 400 if the body is not valid JSON of the expected shape.
+fn route_action(route: &Route, body: &str) -> Result<Option<Action>, ApiError> {
+    let parse_error = |e: serde_json::Error| ApiError::new(400, format!("Invalid body: {}", e));
+
+    let action = match route {
+        Route::ApplyProfile(name) => Action::ApplyProfile { name: name.clone() },
+        Route::SetPreamp => {
+            let body: PreampBody = serde_json::from_str(body).map_err(parse_error)?;
+            Action::SetPreamp {
+                preamp: body.preamp,
+            }
+        }
+        Route::SetBypass => {
+            let body: BypassBody = serde_json::from_str(body).map_err(parse_error)?;
+            Action::SetEqEnabled {
+                enabled: !body.bypassed,
+            }
+        }
+        Route::UpdateBand(index) => {
+            let patch: BandPatch = serde_json::from_str(body).map_err(parse_error)?;
+            Action::UpdateBand {
+                index: *index,
+                patch,
+            }
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(action))
+}
+
+/// Checks that an action can succeed before running it, so that invalid
+/// requests get a 4xx status instead of a generic failure.
+fn check_action(settings: &AppSettings, action: &Action) -> Result<(), ApiError> {
+    match action {
+        Action::ApplyProfile { name } => {
+            let profiles = list_profiles().map_err(|e| ApiError::new(500, e))?;
+            if profiles.contains(name) {
+                Ok(())
+            } else {
+                Err(ApiError::new(404, format!("Profile not found: {}", name)))
+            }
+        }
+        Action::ShowWindow => Ok(()),
+        action => {
+            update_settings_for(&mut settings.clone(), action).map_err(|e| ApiError::new(400, e))
+        }
+    }
+}
+
+/// Executes a (non-WebSocket) route and returns the JSON response body.
+fn execute(app: &AppHandle, route: &Route, body: &str) -> Result<Value, ApiError> {
+    let settings = app.state::<AppState>().settings.lock().clone();
+
+    if let Some(action) = route_action(route, body)? {
+        check_action(&settings, &action)?;
+        let settings = dispatch(app, action).map_err(|e| ApiError::new(500, e))?;
+        return Ok(json!(settings));
+    }
+
+    let value = match route {
+        Route::State => json!(settings),
+        Route::ListProfiles => json!(list_profiles().map_err(|e| ApiError::new(500, e))?),
+        Route::GetPreamp => json!({ "preamp": settings.preamp }),
+        Route::GetBypass => json!({ "bypassed": !settings.eq_enabled }),
+        Route::ListBands => json!(settings.bands),
+        _ => return Err(ApiError::new(500, "Unhandled route")),
+    };
+    Ok(value)
+}
+
+// =============================================================================
+// Request Handling
+// =============================================================================
+
+/// Handles a single HTTP request on the server thread.
+fn handle_request(app: &AppHandle, token: &str, subscribers: &Subscribers, mut request: Request) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+    let provided = header_value(&request, "Authorization")
+        .and_then(|v| v.strip_prefix("Bearer ").map(|t| t.trim().to_string()))
+        .or_else(|| query_param(query, "token"));
+    if !is_authorized(provided.as_deref(), token) {
+        respond(request, Err(ApiError::new(401, "Missing or invalid token")));
+        return;
+    }
+
+    let route = match match_route(request.method().as_str(), path) {
+        Ok(route) => route,
+        Err(e) => {
+            respond(request, Err(e));
+            return;
+        }
+    };
+
+    if route == Route::Events {
+        let settings = app.state::<AppState>().settings.lock().clone();
+        let initial = format!(
+            r#"{{"event":"settings-changed","payload":{}}}"#,
+            json!(settings)
+        );
+        upgrade_websocket(request, subscribers, initial);
+        return;
+    }
+
+    let mut body = String::new();
+    if let Err(e) = request.as_reader().read_to_string(&mut body) {
+        respond(
+            request,
+            Err(ApiError::new(400, format!("Failed to read body: {}", e))),
+        );
+        return;
+    }
+
+    let result = execute(app, &route, &body);
+    respond(request, result);
+}
+
+/// Sends a JSON response.
+fn respond(request: Request, result: Result<Value, ApiError>) {
+    let (status, body) = match result {
+        Ok(value) => (200, value),
+        Err(e) => (e.status, json!({ "error": e.message })),
+    };
+
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(
+            Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                .expect("static header is valid"),
+        );
+    let _ = request.respond(response);
+}
+
+/// Completes a WebSocket handshake and starts forwarding events to the client.
+fn upgrade_websocket(request: Request, subscribers: &Subscribers, initial: String) {
+    let Some(key) = header_value(&request, "Sec-WebSocket-Key") else {
+        respond(
+            request,
+            Err(ApiError::new(400, "Expected a WebSocket upgrade request")),
+        );
+        return;
+    };
+
+    let accept = derive_accept_key(key.as_bytes());
+    let response = Response::empty(101).with_header(
+        Header::from_bytes(&b"Sec-WebSocket-Accept"[..], accept.as_bytes())
+            .expect("accept key is valid ASCII"),
+    );
+    let stream = request.upgrade("websocket", response);
+
+    let (tx, rx) = mpsc::channel();
+    subscribers.lock().push(tx);
+    thread::spawn(move || forward_events(stream, rx, initial));
+}
+
+/// Writes queued events to a WebSocket until the client or server goes away.
+fn forward_events<S: Read + Write>(stream: S, rx: Receiver<String>, initial: String) {
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+
+    for message in std::iter::once(initial).chain(rx) {
+        if socket.send(Message::Text(message)).is_err() {
+            return;
+        }
+    }
+
+    let _ = socket.close(None);
+    let _ = socket.flush();
+}
+
+/// Returns the value of a request header, if present.
+fn header_value(request: &Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(name))
+        .map(|h| h.value.as_str().to_string())
+}
+
+/// Compares the provided token with the expected one in constant time.
+fn is_authorized(provided: Option<&str>, token: &str) -> bool {
+    match provided {
+        Some(provided) if !token.is_empty() && provided.len() == token.len() => {
+            provided
+                .bytes()
+                .zip(token.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+        }
+        _ => false,
+    }
+}
+
+/// Extracts and decodes a query string parameter.
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| percent_decode(value))
+}
+
+/// Decodes `%XX` escapes in a URL component.
+///
+/// # Returns
+///
+/// `None` if an escape is malformed or the result is not valid UTF-8.
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = input.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// Generates a random 128-bit token encoded as hex.
+pub fn generate_token() -> String {
+    let bytes: [u8; 16] = rand::rng().random();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// =============================================================================
+// Server Lifecycle
+// =============================================================================
+
+/// Starts, restarts or stops the server to match the current settings.
+///
+/// Generates and persists a token if automation is enabled without one.
+///
+/// # Errors
+///
+/// Returns an error if settings cannot be saved or the server cannot start.
+pub fn sync_server(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+
+    let config = {
+        let mut settings = state.settings.lock();
+        if settings.automation.enabled && settings.automation.token.is_empty() {
+            settings.automation.token = generate_token();
+            save_settings(&settings)?;
+        }
+        settings.automation.clone()
+    };
+
+    let mut server = state.automation.lock();
+    // Stop the old server first so the port is free for a restart
+    *server = None;
+    if config.enabled {
+        *server = Some(AutomationServer::start(app, config.port, config.token)?);
+    }
+
+    Ok(())
+}
+
+/// Status of the automation server for the settings UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct AutomationStatus {
+    /// Whether automation is enabled in settings.
+    pub enabled: bool,
+    /// Whether the server is currently listening.
+    pub running: bool,
+    /// Configured port.
+    pub port: u16,
+    /// Token clients must send.
+    pub token: String,
+    /// Base URL of the API.
+    pub url: String,
+}
+
+fn automation_status(state: &AppState) -> AutomationStatus {
+    let config = state.settings.lock().automation.clone();
+    let running = state.automation.lock().as_ref().map(AutomationServer::port);
+
+    AutomationStatus {
+        enabled: config.enabled,
+        running: running.is_some(),
+        port: running.unwrap_or(config.port),
+        token: config.token,
+        url: format!("http://127.0.0.1:{}/api", running.unwrap_or(config.port)),
+    }
+}
+
+/// Returns the automation server status.
+#[tauri::command]
+pub fn get_automation_status(state: tauri::State<AppState>) -> AutomationStatus {
+    automation_status(&state)
+}
+
+/// Enables or disables the automation server and persists the choice.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the server should run
+/// * `port` - Optional new port; keeps the current port if `None`
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle
+///
+/// # Errors
+///
+/// Returns an error if settings cannot be saved or the server cannot start
+/// (e.g. the port is already in use). A server that fails to start leaves
+/// the previous settings and server in place.
+#[tauri::command]
+pub fn set_automation_enabled(
+    enabled: bool,
+    port: Option<u16>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<AutomationStatus, String> {
+    let previous = {
+        let mut settings = state.settings.lock();
+        let previous = settings.automation.clone();
+        settings.automation.enabled = enabled;
+        if let Some(port) = port {
+            settings.automation.port = port;
+        }
+        previous
+    };
+
+    if let Err(e) = sync_server(&app) {
+        // Don't persist a server that failed to start; bring back the old one
+        {
+            let mut settings = state.settings.lock();
+            settings.automation.enabled = previous.enabled;
+            settings.automation.port = previous.port;
+            save_settings(&settings)?;
+        }
+        if let Err(restart) = sync_server(&app) {
+            report_error(
+                &app,
+                ErrorSource::Automation,
+                "Restart automation server",
+                &restart,
+            );
+        }
+        return Err(e);
+    }
+
+    save_settings(&state.settings.lock())?;
+    Ok(automation_status(&state))
+}
+
+/// Generates a new token, invalidating the old one, and restarts the server.
+///
+/// # Errors
+///
+/// Returns an error if settings cannot be saved or the server cannot restart.
+#[tauri::command]
+pub fn regenerate_automation_token(
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<AutomationStatus, String> {
+    {
+        let mut settings = state.settings.lock();
+        settings.automation.token = generate_token();
+        save_settings(&settings)?;
+    }
+
+    sync_server(&app)?;
+    Ok(automation_status(&state))
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // Routing Tests
+    // =========================================================================
+
+    #[test]
+    fn match_route_known_paths() {
+        assert_eq!(match_route("GET", "/api/state"), Ok(Route::State));
+        assert_eq!(
+            match_route("GET", "/api/profiles/"),
+            Ok(Route::ListProfiles)
+        );
+        assert_eq!(match_route("PUT", "/api/preamp"), Ok(Route::SetPreamp));
+        assert_eq!(
+            match_route("PATCH", "/api/bands/2"),
+            Ok(Route::UpdateBand(2))
+        );
+        assert_eq!(match_route("GET", "/api/events"), Ok(Route::Events));
+    }
+
+    #[test]
+    fn match_route_decodes_profile_name() {
+        assert_eq!(
+            match_route("POST", "/api/profiles/Studio%20Monitors/apply"),
+            Ok(Route::ApplyProfile("Studio Monitors".to_string()))
+        );
+    }
+
+    #[test]
+    fn match_route_wrong_method() {
+        assert_eq!(
+            match_route("DELETE", "/api/preamp").unwrap_err().status,
+            405
+        );
+        assert_eq!(
+            match_route("GET", "/api/profiles/Studio/apply")
+                .unwrap_err()
+                .status,
+            405
+        );
+    }
+
+    #[test]
+    fn match_route_unknown_path() {
+        assert_eq!(match_route("GET", "/api/unknown").unwrap_err().status, 404);
+    }
+
+    #[test]
+    fn match_route_invalid_band_index() {
+        assert_eq!(
+            match_route("PATCH", "/api/bands/x").unwrap_err().status,
+            400
+        );
+    }
+
+    // =========================================================================
+    // Body Parsing Tests
+    // =========================================================================
+
+    #[test]
+    fn route_action_bypass_inverts_enabled() {
+        let action = route_action(&Route::SetBypass, r#"{"bypassed": true}"#).unwrap();
+        assert_eq!(action, Some(Action::SetEqEnabled { enabled: false }));
+    }
+
+    #[test]
+    fn route_action_preamp() {
+        let action = route_action(&Route::SetPreamp, r#"{"preamp": -4.5}"#).unwrap();
+        assert_eq!(action, Some(Action::SetPreamp { preamp: -4.5 }));
+    }
+
+    #[test]
+    fn route_action_band_patch() {
+        let action = route_action(&Route::UpdateBand(1), r#"{"gain": 3.0}"#).unwrap();
+        assert_eq!(
+            action,
+            Some(Action::UpdateBand {
+                index: 1,
+                patch: BandPatch {
+                    gain: Some(3.0),
+                    ..Default::default()
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn route_action_invalid_body() {
+        let err = route_action(&Route::SetPreamp, "not json").unwrap_err();
+        assert_eq!(err.status, 400);
+    }
+
+    #[test]
+    fn route_action_read_only_route() {
+        assert_eq!(route_action(&Route::State, "").unwrap(), None);
+    }
+
+    #[test]
+    fn check_action_rejects_missing_band() {
+        let action = Action::UpdateBand {
+            index: 10,
+            patch: BandPatch::default(),
+        };
+        let err = check_action(&AppSettings::default(), &action).unwrap_err();
+        assert_eq!(err.status, 400);
+    }
+
+    // =========================================================================
+    // Authentication Tests
+    // =========================================================================
+
+    #[test]
+    fn is_authorized_matches_token() {
+        assert!(is_authorized(Some("abc123"), "abc123"));
+        assert!(!is_authorized(Some("abc124"), "abc123"));
+        assert!(!is_authorized(Some("abc"), "abc123"));
+        assert!(!is_authorized(None, "abc123"));
+    }
+
+    #[test]
+    fn is_authorized_rejects_empty_token() {
+        assert!(!is_authorized(Some(""), ""));
+    }
+
+    #[test]
+    fn query_param_extracts_token() {
+        assert_eq!(
+            query_param("a=1&token=xyz", "token"),
+            Some("xyz".to_string())
+        );
+        assert_eq!(query_param("a=1", "token"), None);
+    }
+
+    #[test]
+    fn percent_decode_handles_utf8_and_errors() {
+        assert_eq!(percent_decode("Caf%C3%A9"), Some("Café".to_string()));
+        assert_eq!(percent_decode("bad%2"), None);
+        assert_eq!(percent_decode("bad%zz"), None);
+    }
+
+    #[test]
+    fn generate_token_is_hex() {
+        let token = generate_token();
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_token());
+    }
+}
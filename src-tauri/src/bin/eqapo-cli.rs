@@ -0,0 +1,7 @@
+//! Headless command-line interface for managing EQAPO GUI profiles.
+//!
+//! Run `eqapo-cli --help` for the list of commands.
+
+fn main() {
+    std::process::exit(eqapo_gui_lib::run_cli())
+}
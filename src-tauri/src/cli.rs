@@ -0,0 +1,780 @@
+//! Headless command-line interface for EQAPO GUI.
+//!
+//! The `eqapo-cli` binary reuses the profile and settings logic from
+//! [`crate::profile`] so presets can be managed from scripts without starting
+//! the Tauri runtime, the main window, or the system tray.
+//!
+//! # Usage
+//!
+//! ```text
+//! eqapo-cli [--json] <command> [arguments]
+//! ```
+//!
+//! | Command                        | Description                                   |
+//! |--------------------------------|-----------------------------------------------|
+//! | `list`                         | List saved profiles                           |
+//! | `show <name>`                  | Print a profile                               |
+//! | `apply <name>`                 | Apply a profile and make it current           |
+//! | `import <file> [--name <name>]`| Import a profile JSON file                    |
+//! | `export <name> <file>`         | Export a profile as `.json` or EAPO `.txt`    |
+//! | `validate <name or file>`      | Check a profile for invalid values            |
+//! | `delete <name>`                | Delete a profile                              |
+//! | `current`                      | Print the current settings                    |
+//!
+//! With `--json`, results are printed to stdout as JSON and errors are printed
+//! to stderr as `{"error": "..."}`.
+//!
+//! # Exit Codes
+//!
+//! | Code | Meaning                                        |
+//! |------|------------------------------------------------|
+//! | `0`  | Success                                        |
+//! | `1`  | The command failed (I/O or parse error)        |
+//! | `2`  | Invalid command-line usage                     |
+//! | `3`  | Validation found problems in the profile       |
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::actions::live_config_profile;
+use crate::convolution;
+use crate::dsp::DEFAULT_SAMPLE_RATE;
+use crate::profile::{
+    build_config_content, delete_profile, get_app_dir, list_profiles, load_profile, load_settings,
+    sanitize_profile_name, save_settings, stage_errors, write_config, write_profile,
+};
+use crate::types::{AppSettings, EqProfile};
+use crate::validation::{self, Severity};
+
+/// Exit code for a successful command.
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code for a command that failed while running.
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Exit code for invalid command-line usage.
+pub const EXIT_USAGE: i32 = 2;
+
+/// Exit code for a profile that failed validation.
+pub const EXIT_INVALID: i32 = 3;
+
+/// Help text printed for `--help` and usage errors.
+const USAGE: &str = "\
+Usage: eqapo-cli [--json] <command> [arguments]
+
+Commands:
+  list                          List saved profiles
+  show <name>                   Print a profile
+  apply <name>                  Apply a profile and make it current
+  import <file> [--name <name>] Import a profile JSON file
+  export <name> <file>          Export a profile (.json or EqualizerAPO .txt)
+  validate <name|file>          Check a profile for invalid values
+  delete <name>                 Delete a profile
+  current                       Print the current settings
+
+Options:
+  --json                        Print machine-readable JSON output
+  -h, --help                    Show this help";
+
+/// A parsed CLI command.
+#[derive(Debug, PartialEq)]
+enum Command {
+    List,
+    Show { name: String },
+    Apply { name: String },
+    Import { file: PathBuf, name: Option<String> },
+    Export { name: String, file: PathBuf },
+    Validate { target: String },
+    Delete { name: String },
+    Current,
+    Help,
+}
+
+/// Parsed command-line options.
+#[derive(Debug, PartialEq)]
+struct CliOptions {
+    json: bool,
+    command: Command,
+}
+
+/// Result of a successful command, rendered as text or JSON.
+struct Output {
+    text: String,
+    json: serde_json::Value,
+    code: i32,
+}
+
+impl Output {
+    fn new(text: String, json: serde_json::Value) -> Self {
+        Self {
+            text,
+            json,
+            code: EXIT_SUCCESS,
+        }
+    }
+}
+
+/// Errors that map to a non-zero exit code.
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    Failed(String),
+    Invalid(String),
+}
+
+impl CliError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => EXIT_USAGE,
+            CliError::Failed(_) => EXIT_FAILURE,
+            CliError::Invalid(_) => EXIT_INVALID,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            CliError::Usage(msg) | CliError::Failed(msg) | CliError::Invalid(msg) => msg,
+        }
+    }
+}
+
+impl From<String> for CliError {
+    fn from(msg: String) -> Self {
+        CliError::Failed(msg)
+    }
+}
+
+/// Runs the CLI with the process arguments and returns the exit code.
+///
+/// This is the entry point used by the `eqapo-cli` binary:
+///
+/// ```ignore
+/// fn main() {
+///     std::process::exit(eqapo_gui_lib::run_cli());
+/// }
+/// ```
+pub fn run_cli() -> i32 {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    run(&args, &mut std::io::stdout(), &mut std::io::stderr())
+}
+
+/// Runs the CLI with explicit arguments and output streams.
+///
+/// # Returns
+///
+/// The process exit code (see the module documentation).
+fn run(args: &[String], out: &mut dyn Write, err: &mut dyn Write) -> i32 {
+    let json_requested = args.iter().any(|a| a == "--json");
+
+    let result = parse_args(args)
+        .map_err(CliError::Usage)
+        .and_then(|options| execute(&options.command).map(|output| (options.json, output)));
+
+    match result {
+        Ok((json, output)) => {
+            let _ = if json {
+                writeln!(
+                    out,
+                    "{}",
+                    serde_json::to_string_pretty(&output.json).unwrap_or_default()
+                )
+            } else {
+                writeln!(out, "{}", output.text)
+            };
+            output.code
+        }
+        Err(e) => {
+            let _ = if json_requested {
+                writeln!(err, "{}", json!({ "error": e.message() }))
+            } else if matches!(e, CliError::Usage(_)) {
+                writeln!(err, "error: {}\n\n{}", e.message(), USAGE)
+            } else {
+                writeln!(err, "error: {}", e.message())
+            };
+            e.exit_code()
+        }
+    }
+}
+
+/// Parses command-line arguments (without the program name).
+///
+/// # Errors
+///
+/// Returns a usage message if the command is unknown or arguments are missing.
+fn parse_args(args: &[String]) -> Result<CliOptions, String> {
+    let mut json = false;
+    let mut name_override = None;
+    let mut positional: Vec<&str> = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                return Ok(CliOptions {
+                    json,
+                    command: Command::Help,
+                })
+            }
+            "--name" => {
+                let value = iter.next().ok_or("--name requires a value")?;
+                name_override = Some(value.clone());
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            value => positional.push(value),
+        }
+    }
+
+    let (command_name, rest) = positional.split_first().ok_or("No command given")?;
+
+    let expect_args = |count: usize| -> Result<(), String> {
+        if rest.len() == count {
+            Ok(())
+        } else {
+            Err(format!(
+                "'{}' expects {} argument(s), got {}",
+                command_name,
+                count,
+                rest.len()
+            ))
+        }
+    };
+
+    let command = match *command_name {
+        "list" => {
+            expect_args(0)?;
+            Command::List
+        }
+        "show" => {
+            expect_args(1)?;
+            Command::Show {
+                name: rest[0].to_string(),
+            }
+        }
+        "apply" => {
+            expect_args(1)?;
+            Command::Apply {
+                name: rest[0].to_string(),
+            }
+        }
+        "import" => {
+            expect_args(1)?;
+            Command::Import {
+                file: PathBuf::from(rest[0]),
+                name: name_override.take(),
+            }
+        }
+        "export" => {
+            expect_args(2)?;
+            Command::Export {
+                name: rest[0].to_string(),
+                file: PathBuf::from(rest[1]),
+            }
+        }
+        "validate" => {
+            expect_args(1)?;
+            Command::Validate {
+                target: rest[0].to_string(),
+            }
+        }
+        "delete" => {
+            expect_args(1)?;
+            Command::Delete {
+                name: rest[0].to_string(),
+            }
+        }
+        "current" => {
+            expect_args(0)?;
+            Command::Current
+        }
+        "help" => Command::Help,
+        other => return Err(format!("Unknown command: {}", other)),
+    };
+
+    if name_override.is_some() {
+        return Err("--name is only valid with 'import'".to_string());
+    }
+
+    Ok(CliOptions { json, command })
+}
+
+/// Executes a parsed command.
+fn execute(command: &Command) -> Result<Output, CliError> {
+    match command {
+        Command::List => cmd_list(),
+        Command::Show { name } => cmd_show(name),
+        Command::Apply { name } => cmd_apply(name),
+        Command::Import { file, name } => cmd_import(file, name.as_deref()),
+        Command::Export { name, file } => cmd_export(name, file),
+        Command::Validate { target } => cmd_validate(target),
+        Command::Delete { name } => cmd_delete(name),
+        Command::Current => cmd_current(),
+        Command::Help => Ok(Output::new(USAGE.to_string(), json!({ "usage": USAGE }))),
+    }
+}
+
+fn cmd_list() -> Result<Output, CliError> {
+    let profiles = list_profiles()?;
+    Ok(Output::new(profiles.join("\n"), json!(profiles)))
+}
+
+fn cmd_show(name: &str) -> Result<Output, CliError> {
+    let profile = load_profile(name.to_string())?;
+    Ok(Output::new(format_profile(&profile), json!(profile)))
+}
+
+fn cmd_apply(name: &str) -> Result<Output, CliError> {
+    let profile = load_profile(name.to_string())?;
+    let mut settings = load_settings();
+    let sample_rate = sample_rate(&settings);
+
+    settings.current_profile = Some(name.to_string());
+    settings.load_live_profile(profile);
+
+    let live = live_config_profile(&mut settings, sample_rate)?;
+    write_config(
+        &live,
+        settings.config_path.clone(),
+        settings.eq_enabled,
+        &settings.config_precision,
+        sample_rate,
+    )?;
+    save_settings(&settings)?;
+
+    Ok(Output::new(
+        format!("Applied profile '{}'", name),
+        json!({ "applied": name, "eq_enabled": settings.eq_enabled }),
+    ))
+}
+
+fn cmd_import(file: &Path, name: Option<&str>) -> Result<Output, CliError> {
+    let profile = read_profile_file(file)?;
+    let name = import_name(file, name, &profile)?;
+
+    write_profile(&EqProfile {
+        name: name.clone(),
+        ..profile
+    })?;
+
+    Ok(Output::new(
+        format!("Imported profile '{}'", name),
+        json!({ "imported": name }),
+    ))
+}
+
+/// Picks the name of an imported profile: `--name`, the name stored in the
+/// file, or the file name, in that order.
+///
+/// The name becomes the file name in the profiles directory, so characters
+/// such as path separators are replaced (see [`sanitize_profile_name`]).
+///
+/// # Errors
+///
+/// Returns an error if the name is empty after sanitizing.
+fn import_name(file: &Path, name: Option<&str>, profile: &EqProfile) -> Result<String, String> {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None if !profile.name.trim().is_empty() => profile.name.clone(),
+        None => file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(String::from)
+            .ok_or_else(|| format!("Cannot derive a profile name from {:?}", file))?,
+    };
+
+    let sanitized = sanitize_profile_name(&name);
+    if sanitized.is_empty() {
+        return Err(format!("Invalid profile name '{}'", name));
+    }
+    Ok(sanitized)
+}
+
+fn cmd_export(name: &str, file: &Path) -> Result<Output, CliError> {
+    let profile = load_profile(name.to_string())?;
+
+    let is_txt = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("txt"));
+
+    let content = if is_txt {
+        // Refuse what the app would refuse to write as its config
+        let settings = load_settings();
+        let issues = validate_profile(&profile, sample_rate(&settings));
+        if !issues.is_empty() {
+            return Err(CliError::Invalid(format!(
+                "Profile '{}' cannot be exported as a config: {}",
+                name,
+                issues.join("; ")
+            )));
+        }
+        build_config_content(&profile, true, &settings.config_precision)
+    } else {
+        serde_json::to_string_pretty(&profile)
+            .map_err(|e| format!("Failed to serialize profile: {}", e))?
+    };
+
+    fs::write(file, content).map_err(|e| format!("Failed to write {:?}: {}", file, e))?;
+
+    Ok(Output::new(
+        format!("Exported profile '{}' to {}", name, file.display()),
+        json!({ "exported": name, "path": file }),
+    ))
+}
+
+fn cmd_validate(target: &str) -> Result<Output, CliError> {
+    let path = Path::new(target);
+    let profile = if path.is_file() {
+        read_profile_file(path)?
+    } else {
+        load_profile(target.to_string())?
+    };
+
+    let issues = validate_profile(&profile, sample_rate(&load_settings()));
+    let text = if issues.is_empty() {
+        format!("Profile '{}' is valid", profile.name)
+    } else {
+        let mut text = format!("Profile '{}' has {} issue(s):", profile.name, issues.len());
+        for issue in &issues {
+            text.push_str("\n  - ");
+            text.push_str(issue);
+        }
+        text
+    };
+
+    let mut output = Output::new(
+        text,
+        json!({ "name": profile.name, "valid": issues.is_empty(), "issues": issues }),
+    );
+    if !issues.is_empty() {
+        output.code = EXIT_INVALID;
+    }
+    Ok(output)
+}
+
+fn cmd_delete(name: &str) -> Result<Output, CliError> {
+    delete_profile(name.to_string())?;
+
+    // Don't leave settings pointing at a profile that no longer exists
+    let mut settings = load_settings();
+    if settings.current_profile.as_deref() == Some(name) {
+        settings.current_profile = None;
+        save_settings(&settings)?;
+    }
+
+    Ok(Output::new(
+        format!("Deleted profile '{}'", name),
+        json!({ "deleted": name }),
+    ))
+}
+
+fn cmd_current() -> Result<Output, CliError> {
+    let settings = load_settings();
+
+    let mut text = format!(
+        "Profile: {}\nEQ: {}\nPreamp: {:.1} dB\nConfig: {}\nBands:",
+        settings.current_profile.as_deref().unwrap_or("(none)"),
+        if settings.eq_enabled {
+            "enabled"
+        } else {
+            "bypassed"
+        },
+        settings.preamp,
+        settings
+            .config_path
+            .as_deref()
+            .unwrap_or("(default live_config.txt)"),
+    );
+    for band in &settings.bands {
+        text.push_str("\n  ");
+        text.push_str(&format_band(band));
+    }
+
+    Ok(Output::new(text, json!(settings)))
+}
+
+/// Reads and parses a profile JSON file from an arbitrary path.
+fn read_profile_file(path: &Path) -> Result<EqProfile, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse profile: {}", e))
+}
+
+/// Formats a profile for human-readable output.
+fn format_profile(profile: &EqProfile) -> String {
+    let mut text = format!(
+        "Name: {}\nPreamp: {:.1} dB\nBands:",
+        profile.name, profile.preamp
+    );
+    for band in &profile.bands {
+        text.push_str("\n  ");
+        text.push_str(&format_band(band));
+    }
+    if !profile.graphic_eq.is_empty() {
+        text.push_str(&format!("\nGraphicEQ: {} points", profile.graphic_eq.len()));
+    }
+    if let Some(plugin) = &profile.vst_plugin {
+        text.push_str(&format!("\nVST plugin: {}", plugin.library));
+    }
+    text
+}
+
+/// Formats a band as its EqualizerAPO line, marking disabled bands.
+fn format_band(band: &crate::types::ParametricBand) -> String {
+    if band.enabled {
+        band.to_eapo_line()
+    } else {
+        format!("{} (disabled)", band.to_eapo_line())
+    }
+}
+
+/// Returns the sample rate bands are validated against.
+///
+/// The CLI does not query the output device, so this is the rate configured
+/// in the settings, falling back to 48 kHz.
+fn sample_rate(settings: &AppSettings) -> u32 {
+    settings
+        .validation
+        .sample_rate
+        .unwrap_or(DEFAULT_SAMPLE_RATE as u32)
+}
+
+/// Checks a profile for values EqualizerAPO cannot use.
+///
+/// Bands are checked with [`validation::check_bands`] against `sample_rate`
+/// and the stages with [`stage_errors`] and the convolution impulse check,
+/// so a profile passes here exactly when the app would apply it.
+///
+/// # Returns
+///
+/// A list of human-readable issues; empty if the profile is valid.
+fn validate_profile(profile: &EqProfile, sample_rate: u32) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    if profile.name.trim().is_empty() {
+        issues.push("Profile name is empty".to_string());
+    }
+    if !profile.preamp.is_finite() {
+        issues.push("Preamp is not a finite number".to_string());
+    }
+
+    // Same band checks as saving or applying in the app, without clamping
+    let mut bands = profile.bands.clone();
+    let report = validation::check_bands(&mut bands, sample_rate, false);
+    issues.extend(
+        report
+            .issues
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| issue.message),
+    );
+
+    issues.extend(stage_errors(profile, sample_rate));
+    if let Some(stage) = &profile.convolution {
+        if let Err(e) = get_app_dir().and_then(|dir| convolution::check_stage(&dir, stage)) {
+            issues.push(e);
+        }
+    }
+
+    issues
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_band, FilterType};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    // =========================================================================
+    // Argument Parsing Tests
+    // =========================================================================
+
+    #[test]
+    fn parse_args_list() {
+        let options = parse_args(&args(&["list"])).unwrap();
+        assert_eq!(options.command, Command::List);
+        assert!(!options.json);
+    }
+
+    #[test]
+    fn parse_args_json_flag_anywhere() {
+        let options = parse_args(&args(&["show", "Studio", "--json"])).unwrap();
+        assert!(options.json);
+        assert_eq!(
+            options.command,
+            Command::Show {
+                name: "Studio".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_args_import_with_name() {
+        let options = parse_args(&args(&["import", "preset.json", "--name", "HD600"])).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Import {
+                file: PathBuf::from("preset.json"),
+                name: Some("HD600".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_args_export_requires_two_arguments() {
+        assert!(parse_args(&args(&["export", "Studio"])).is_err());
+    }
+
+    #[test]
+    fn parse_args_name_only_valid_for_import() {
+        assert!(parse_args(&args(&["show", "Studio", "--name", "Other"])).is_err());
+    }
+
+    #[test]
+    fn parse_args_unknown_command() {
+        assert!(parse_args(&args(&["frobnicate"])).is_err());
+    }
+
+    #[test]
+    fn parse_args_no_command() {
+        assert!(parse_args(&args(&["--json"])).is_err());
+    }
+
+    #[test]
+    fn parse_args_help() {
+        let options = parse_args(&args(&["--help"])).unwrap();
+        assert_eq!(options.command, Command::Help);
+    }
+
+    // =========================================================================
+    // Exit Code Tests
+    // =========================================================================
+
+    #[test]
+    fn run_usage_error_exit_code() {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        let code = run(&args(&["bogus"]), &mut out, &mut err);
+        assert_eq!(code, EXIT_USAGE);
+        assert!(String::from_utf8(err).unwrap().contains("Unknown command"));
+    }
+
+    #[test]
+    fn run_usage_error_json() {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        let code = run(&args(&["--json", "bogus"]), &mut out, &mut err);
+        assert_eq!(code, EXIT_USAGE);
+
+        let parsed: serde_json::Value = serde_json::from_slice(&err).unwrap();
+        assert!(parsed["error"].as_str().unwrap().contains("bogus"));
+    }
+
+    #[test]
+    fn run_help_succeeds() {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        assert_eq!(run(&args(&["help"]), &mut out, &mut err), EXIT_SUCCESS);
+        assert!(String::from_utf8(out).unwrap().contains("Usage:"));
+    }
+
+    // =========================================================================
+    // Validation Tests
+    // =========================================================================
+
+    #[test]
+    fn import_name_stays_inside_profiles_directory() {
+        let file = Path::new("shared.json");
+        let profile = EqProfile {
+            name: "../../x".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(import_name(file, None, &profile).unwrap(), ".._.._x");
+        assert_eq!(import_name(file, Some("a\\b"), &profile).unwrap(), "a_b");
+        assert!(import_name(file, Some(" "), &profile).is_err());
+
+        // Falls back to the file name
+        let unnamed = EqProfile::default();
+        assert_eq!(import_name(file, None, &unnamed).unwrap(), "shared");
+    }
+
+    #[test]
+    fn validate_profile_accepts_sane_values() {
+        let profile = EqProfile {
+            name: "Ok".to_string(),
+            preamp: -3.0,
+            bands: vec![test_band(FilterType::Peaking, 1000.0, 3.0, 1.41)],
+            ..Default::default()
+        };
+        assert!(validate_profile(&profile, 48000).is_empty());
+    }
+
+    #[test]
+    fn validate_profile_reports_each_issue() {
+        let profile = EqProfile {
+            name: " ".to_string(),
+            preamp: f32::NAN,
+            bands: vec![test_band(FilterType::Peaking, 0.0, f32::INFINITY, 0.0)],
+            ..Default::default()
+        };
+        let issues = validate_profile(&profile, 48000);
+        assert_eq!(issues.len(), 5);
+        assert!(issues.iter().any(|i| i.starts_with("Band 1: Q")));
+    }
+
+    #[test]
+    fn validate_profile_checks_bands_against_sample_rate() {
+        let profile = EqProfile {
+            name: "Air".to_string(),
+            bands: vec![test_band(FilterType::Peaking, 22000.0, 3.0, 1.41)],
+            ..Default::default()
+        };
+        assert!(validate_profile(&profile, 48000).is_empty());
+        let issues = validate_profile(&profile, 44100);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].contains("Nyquist"));
+    }
+
+    #[test]
+    fn validate_profile_checks_stages() {
+        let profile = EqProfile {
+            name: "Stages".to_string(),
+            crossfeed: Some(crate::crossfeed::Crossfeed {
+                feed_level: 0.0,
+                ..Default::default()
+            }),
+            mid_side: Some(crate::mid_side::MidSide {
+                width: 2.5,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let issues = validate_profile(&profile, 48000);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[1].starts_with("Stereo width"));
+    }
+
+    #[test]
+    fn validate_profile_reports_missing_vst_plugin() {
+        let profile = EqProfile {
+            name: "Plugin".to_string(),
+            vst_plugin: Some(crate::vst::VstPlugin {
+                library: "missing-plugin.dll".to_string(),
+                chunk: None,
+            }),
+            ..Default::default()
+        };
+        let issues = validate_profile(&profile, 48000);
+        assert_eq!(issues, vec!["VST plugin 'missing-plugin.dll' not found"]);
+    }
+}
@@ -9,8 +9,9 @@ use crate::ab_test::{
     export_results_csv, export_results_json, ABSession, ABSessionResults, ABStateForUI, ABTestMode,
     ActiveOption,
 };
-use crate::profile::{apply_profile, get_app_dir, load_profile};
+use crate::profile::{get_app_dir, load_profile, write_config};
 use crate::types::AppState;
+use crate::validation;
 
 /// Starts a new A/B test session.
 ///
@@ -74,14 +75,20 @@ pub fn start_ab_session(
 /// Returns an error if:
 /// - No active A/B session exists
 /// - The option is invalid
-/// - The preset cannot be loaded or applied
+/// - The preset cannot be loaded, has invalid bands, or cannot be applied
 #[tauri::command]
 pub fn apply_ab_option(option: String, state: tauri::State<AppState>) -> Result<(), String> {
-    // First, get config_path from settings (short lock scope)
-    let config_path = {
+    // First, get config_path, precision and validation options from settings
+    // (short lock scope; resolve_sample_rate takes the lock itself)
+    let (config_path, precision, clamp) = {
         let settings = state.settings.lock();
-        settings.config_path.clone()
+        (
+            settings.config_path.clone(),
+            settings.config_precision,
+            settings.validation.auto_clamp,
+        )
     };
+    let sample_rate = validation::resolve_sample_rate(&state);
 
     // Then work with the A/B session
     let (preset_name, trim) = {
@@ -116,9 +123,10 @@ pub fn apply_ab_option(option: String, state: tauri::State<AppState>) -> Result<
     };
 
     // Now load and apply the profile (no locks held)
-    let profile = load_profile(preset_name)?;
-    let adjusted_preamp = profile.preamp + trim;
-    apply_profile(profile.bands, adjusted_preamp, config_path, Some(true))?;
+    let mut profile = load_profile(preset_name)?;
+    profile.preamp += trim;
+    validation::enforce_bands(&mut profile.bands, sample_rate, clamp)?;
+    write_config(&profile, config_path, true, &precision, sample_rate)?;
 
     Ok(())
 }
@@ -0,0 +1,326 @@
+//! Convolution stages backed by impulse response WAV files.
+//!
+//! Impulses are imported into `Documents/EQAPO GUI/impulses/` and referenced
+//! from profiles by file name. When a profile is applied, the impulse is
+//! copied into an `impulses/` directory next to the config file and emitted
+//! as a relative `Convolution:` line, so EqualizerAPO never has to read
+//! files outside the allowed config directories.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::actions::notify_settings_changed;
+use crate::profile::{
+    ensure_dirs, load_profile, sanitize_profile_name, save_settings, write_profile,
+};
+use crate::types::{AppState, ConvolutionStage};
+use crate::wav::{self, WavInfo};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Directory holding impulses, relative to the app and config directories.
+pub const IMPULSES_DIR: &str = "impulses";
+
+/// Maximum number of impulse channels.
+///
+/// EqualizerAPO expects either a mono impulse, applied to every channel, or
+/// one channel per device channel.
+pub const MAX_IMPULSE_CHANNELS: u16 = 8;
+
+/// Sample rates accepted for impulses.
+pub const SUPPORTED_SAMPLE_RATES: [u32; 8] =
+    [44100, 48000, 88200, 96000, 176400, 192000, 352800, 384000];
+
+/// Maximum impulse length in seconds.
+pub const MAX_IMPULSE_SECONDS: f64 = 10.0;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// An impulse file in the `impulses/` directory with its header.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImpulseInfo {
+    /// File name inside the `impulses/` directory.
+    pub file: String,
+
+    #[serde(flatten)]
+    pub info: WavInfo,
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// Checks that an impulse file name is a plain `.wav` file name.
+///
+/// # Errors
+///
+/// Returns an error if the name is empty, contains path components, or does
+/// not end in `.wav`.
+pub fn validate_file_name(file: &str) -> Result<(), String> {
+    if file.is_empty()
+        || file != sanitize_profile_name(file)
+        || file.starts_with('.')
+        || Path::new(file).file_name().and_then(|f| f.to_str()) != Some(file)
+    {
+        return Err(format!("Invalid impulse file name '{}'", file));
+    }
+    if !file.to_ascii_lowercase().ends_with(".wav") {
+        return Err(format!("Impulse '{}' is not a .wav file", file));
+    }
+    Ok(())
+}
+
+/// Checks that an impulse can be used by EqualizerAPO's convolution filter.
+///
+/// # Errors
+///
+/// Returns an error if the channel count, sample rate or length is out of
+/// range.
+pub fn validate_impulse(info: &WavInfo) -> Result<(), String> {
+    if info.channels == 0 || info.channels > MAX_IMPULSE_CHANNELS {
+        return Err(format!(
+            "Impulse has {} channels; expected 1 to {}",
+            info.channels, MAX_IMPULSE_CHANNELS
+        ));
+    }
+    if !SUPPORTED_SAMPLE_RATES.contains(&info.sample_rate) {
+        return Err(format!(
+            "Unsupported impulse sample rate {} Hz",
+            info.sample_rate
+        ));
+    }
+    if info.frames == 0 {
+        return Err("Impulse is empty".to_string());
+    }
+    if info.duration() > MAX_IMPULSE_SECONDS {
+        return Err(format!(
+            "Impulse is {:.1} s long; the maximum is {} s",
+            info.duration(),
+            MAX_IMPULSE_SECONDS
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the path of a stage's impulse in the app's `impulses/` directory.
+///
+/// # Errors
+///
+/// Returns an error if the file name is invalid.
+pub fn impulse_path(app_dir: &Path, stage: &ConvolutionStage) -> Result<PathBuf, String> {
+    validate_file_name(&stage.file)?;
+    Ok(app_dir.join(IMPULSES_DIR).join(&stage.file))
+}
+
+/// Reads and validates the impulse referenced by a stage.
+///
+/// # Errors
+///
+/// Returns an error if the file is missing, not a supported WAV file, or
+/// fails [`validate_impulse`].
+pub fn check_stage(app_dir: &Path, stage: &ConvolutionStage) -> Result<WavInfo, String> {
+    let path = impulse_path(app_dir, stage)?;
+    if !path.is_file() {
+        return Err(format!("Impulse '{}' not found", stage.file));
+    }
+    let info = wav::read_info(&path)?;
+    validate_impulse(&info).map_err(|e| format!("Impulse '{}': {}", stage.file, e))?;
+    Ok(info)
+}
+
+/// Returns the EqualizerAPO line for a stage.
+///
+/// The path is relative to the config file, which EqualizerAPO resolves
+/// against the directory of the config that contains the line. It always
+/// uses `\`, since EqualizerAPO configs are Windows-only and the config must
+/// not depend on the host that wrote it.
+#[must_use]
+pub fn config_line(stage: &ConvolutionStage) -> String {
+    format!("Convolution: {}\\{}", IMPULSES_DIR, stage.file)
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Copies a WAV file into the `impulses/` directory.
+///
+/// # Arguments
+///
+/// * `path` - Path of the WAV file to import
+/// * `overwrite` - Whether to replace an impulse with the same file name
+///
+/// # Returns
+///
+/// The stored file name and header.
+///
+/// # Errors
+///
+/// Returns an error if the file is not a valid impulse, an impulse with the
+/// same name exists and `overwrite` is `false`, or copying fails.
+#[tauri::command]
+pub fn import_impulse(path: String, overwrite: bool) -> Result<ImpulseInfo, String> {
+    let source = Path::new(&path);
+    let info = wav::read_info(source)?;
+    validate_impulse(&info)?;
+
+    let stem = source
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(sanitize_profile_name)
+        .filter(|s| !s.is_empty() && !s.starts_with('.'))
+        .ok_or("Impulse file has no usable name")?;
+    let file = format!("{}.wav", stem);
+    validate_file_name(&file)?;
+
+    let dir = ensure_dirs()?.join(IMPULSES_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create impulses folder: {}", e))?;
+    let dest = dir.join(&file);
+    if dest.exists() && !overwrite {
+        return Err(format!("Impulse '{}' already exists", file));
+    }
+    fs::copy(source, &dest).map_err(|e| format!("Failed to copy impulse: {}", e))?;
+
+    Ok(ImpulseInfo { file, info })
+}
+
+/// Lists the impulses in the `impulses/` directory.
+///
+/// Files that are not readable WAV files are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read.
+#[tauri::command]
+pub fn list_impulses() -> Result<Vec<ImpulseInfo>, String> {
+    let dir = ensure_dirs()?.join(IMPULSES_DIR);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut impulses: Vec<ImpulseInfo> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read impulses folder: {}", e))?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let file = path.file_name()?.to_str()?.to_string();
+            validate_file_name(&file).ok()?;
+            let info = wav::read_info(&path).ok()?;
+            Some(ImpulseInfo { file, info })
+        })
+        .collect();
+    impulses.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(impulses)
+}
+
+/// Sets or removes the convolution stage of a saved profile.
+///
+/// # Arguments
+///
+/// * `name` - The profile name
+/// * `convolution` - The new stage, or `None` to remove it
+///
+/// # Errors
+///
+/// Returns an error if the impulse is invalid or the profile cannot be
+/// loaded or written.
+#[tauri::command]
+pub fn set_profile_convolution(
+    name: String,
+    convolution: Option<ConvolutionStage>,
+) -> Result<(), String> {
+    if let Some(stage) = &convolution {
+        check_stage(&ensure_dirs()?, stage)?;
+    }
+    let mut profile = load_profile(name)?;
+    profile.convolution = convolution;
+    write_profile(&profile)
+}
+
+/// Sets or removes the convolution stage of the current EQ.
+///
+/// The stage is written to the config on the next apply.
+///
+/// # Arguments
+///
+/// * `convolution` - The new stage, or `None` to remove it
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for notifications
+///
+/// # Errors
+///
+/// Returns an error if the impulse is invalid or settings cannot be saved.
+#[tauri::command]
+pub fn set_convolution(
+    convolution: Option<ConvolutionStage>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if let Some(stage) = &convolution {
+        check_stage(&ensure_dirs()?, stage)?;
+    }
+    {
+        let mut settings = state.settings.lock();
+        settings.convolution = convolution;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wav::SampleFormat;
+
+    fn info(channels: u16, sample_rate: u32, frames: u64) -> WavInfo {
+        WavInfo {
+            format: SampleFormat::Float,
+            channels,
+            sample_rate,
+            bits_per_sample: 32,
+            frames,
+        }
+    }
+
+    #[test]
+    fn accepts_plain_wav_file_names() {
+        assert!(validate_file_name("Room correction.wav").is_ok());
+        assert!(validate_file_name("ir.WAV").is_ok());
+    }
+
+    #[test]
+    fn rejects_paths_and_other_extensions() {
+        for name in ["", "../ir.wav", "sub/ir.wav", "sub\\ir.wav", "..", "ir.txt"] {
+            assert!(validate_file_name(name).is_err(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn validates_channels_rate_and_length() {
+        assert!(validate_impulse(&info(1, 48000, 4096)).is_ok());
+        assert!(validate_impulse(&info(2, 44100, 65536)).is_ok());
+        assert!(validate_impulse(&info(9, 48000, 4096)).is_err());
+        assert!(validate_impulse(&info(2, 22050, 4096)).is_err());
+        assert!(validate_impulse(&info(1, 48000, 0)).is_err());
+        assert!(validate_impulse(&info(1, 48000, 48000 * 11)).is_err());
+    }
+
+    #[test]
+    fn config_line_is_relative_to_config() {
+        let stage = ConvolutionStage {
+            file: "room.wav".to_string(),
+        };
+        assert_eq!(config_line(&stage), "Convolution: impulses\\room.wav");
+    }
+}
@@ -0,0 +1,417 @@
+//! Bauer-style crossfeed for headphone listening.
+//!
+//! Part of each channel is low-passed and mixed into the opposite channel,
+//! imitating how sound from a loudspeaker reaches both ears. EqualizerAPO
+//! has no crossfeed filter, so the stage is expanded into directives:
+//!
+//! ```text
+//! Copy: XFL=L XFR=R                      ; copies for the crossfed paths
+//! Channel: XFL XFR
+//! Filter: ON LPQ Fc 700 Hz Q 0.707       ; head shadow low-pass
+//! Delay: 0.1 ms                          ; optional extra delay
+//! Channel: L R
+//! Filter: ON HSC Fc 700 Hz Gain … dB Q 0.707
+//! Copy: L=…*L+…*XFR R=…*R+…*XFL
+//! Channel: all
+//! ```
+//!
+//! The direct paths get a high shelf so that, after scaling, a centered
+//! (mono) signal keeps a flat response: bass adds up from both paths while
+//! treble only comes from the boosted direct path. The Butterworth low-pass
+//! already delays the crossfed signal by about 0.3 ms at 700 Hz, close to
+//! the interaural delay of loudspeakers at ±30°, so the presets add none.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::actions::notify_settings_changed;
+use crate::dsp::Biquad;
+use crate::profile::{load_profile, save_settings, write_profile};
+use crate::types::{format_config_number, AppState, ConfigPrecision, FilterType, ParametricBand};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Q of the low-pass on the crossfed path and the shelf on the direct path.
+const FILTER_Q: f32 = 0.707;
+
+/// Virtual channels holding the crossfed copies of left and right.
+const CROSS_LEFT: &str = "XFL";
+const CROSS_RIGHT: &str = "XFR";
+
+/// Allowed cutoff range in Hz.
+pub const CUTOFF_RANGE: (f32, f32) = (300.0, 2000.0);
+
+/// Allowed feed level range in dB below the direct signal.
+pub const FEED_LEVEL_RANGE: (f32, f32) = (1.0, 15.0);
+
+/// Longest extra delay in milliseconds.
+pub const MAX_DELAY_MS: f32 = 1.0;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Crossfeed presets, following the levels popularized by bs2b.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossfeedPreset {
+    /// 700 Hz, 4.5 dB: subtle, close to Bauer's original circuit
+    Default,
+    /// 700 Hz, 6 dB: Chu Moy's headphone amplifier
+    ChuMoy,
+    /// 650 Hz, 9.5 dB: Jan Meier's stronger setting
+    JanMeier,
+}
+
+impl CrossfeedPreset {
+    /// All presets, mildest first.
+    pub const ALL: [CrossfeedPreset; 3] = [Self::Default, Self::ChuMoy, Self::JanMeier];
+
+    /// Display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Default => "Default (Bauer)",
+            Self::ChuMoy => "Chu Moy",
+            Self::JanMeier => "Jan Meier",
+        }
+    }
+
+    /// Crossfeed parameters of the preset.
+    pub fn crossfeed(self) -> Crossfeed {
+        let (cutoff, feed_level) = match self {
+            Self::Default => (700.0, 4.5),
+            Self::ChuMoy => (700.0, 6.0),
+            Self::JanMeier => (650.0, 9.5),
+        };
+        Crossfeed {
+            cutoff,
+            feed_level,
+            delay_ms: 0.0,
+        }
+    }
+}
+
+/// Crossfeed stage of a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Crossfeed {
+    /// Cutoff of the crossfed low-pass in Hz.
+    pub cutoff: f32,
+
+    /// Level of the crossfed signal below the direct signal at low
+    /// frequencies, in dB. Lower values mean stronger crossfeed.
+    pub feed_level: f32,
+
+    /// Extra delay of the crossfed signal in milliseconds.
+    #[serde(default)]
+    pub delay_ms: f32,
+}
+
+impl Default for Crossfeed {
+    fn default() -> Self {
+        CrossfeedPreset::Default.crossfeed()
+    }
+}
+
+/// A preset with its display name and parameters.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossfeedPresetInfo {
+    pub preset: CrossfeedPreset,
+    pub label: &'static str,
+    pub crossfeed: Crossfeed,
+}
+
+impl Crossfeed {
+    /// Checks that the parameters are within range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first parameter out of range.
+    pub fn validate(&self) -> Result<(), String> {
+        let (min_cutoff, max_cutoff) = CUTOFF_RANGE;
+        if !(min_cutoff..=max_cutoff).contains(&self.cutoff) {
+            return Err(format!(
+                "Crossfeed cutoff must be between {} and {} Hz, got {}",
+                min_cutoff, max_cutoff, self.cutoff
+            ));
+        }
+        let (min_level, max_level) = FEED_LEVEL_RANGE;
+        if !(min_level..=max_level).contains(&self.feed_level) {
+            return Err(format!(
+                "Crossfeed level must be between {} and {} dB, got {}",
+                min_level, max_level, self.feed_level
+            ));
+        }
+        if !(0.0..=MAX_DELAY_MS).contains(&self.delay_ms) {
+            return Err(format!(
+                "Crossfeed delay must be between 0 and {} ms, got {}",
+                MAX_DELAY_MS, self.delay_ms
+            ));
+        }
+        Ok(())
+    }
+
+    /// Linear gain of the crossfed path relative to the direct path.
+    fn cross_ratio(&self) -> f64 {
+        10f64.powf(-f64::from(self.feed_level) / 20.0)
+    }
+
+    /// Returns the `(direct, cross)` mixing gains.
+    ///
+    /// Both are scaled so that bass in a centered signal stays at unity.
+    fn mix_gains(&self) -> (f64, f64) {
+        let ratio = self.cross_ratio();
+        let direct = 1.0 / (1.0 + ratio);
+        (direct, ratio * direct)
+    }
+
+    /// High shelf on the direct path that restores treble lost to scaling.
+    fn direct_shelf(&self) -> ParametricBand {
+        ParametricBand {
+            filter_type: FilterType::HighShelf,
+            frequency: self.cutoff,
+            gain: (20.0 * (1.0 + self.cross_ratio()).log10()) as f32,
+            q_factor: FILTER_Q,
+            enabled: true,
+        }
+    }
+
+    /// Returns the EqualizerAPO directives for the stage.
+    ///
+    /// The filters are written with `precision`, like the profile's bands;
+    /// the mix gains are always lossless. The last line resets the channel
+    /// selection to `all`, so filters that follow apply to every channel
+    /// again.
+    #[must_use]
+    pub fn config_lines(&self, precision: &ConfigPrecision) -> Vec<String> {
+        let number = |value: f64| format_config_number(value as f32, None, 0);
+        let (direct, cross) = self.mix_gains();
+
+        let mut lines = vec![
+            format!(
+                "; Crossfeed: {} Hz, {} dB",
+                number(self.cutoff.into()),
+                number(self.feed_level.into())
+            ),
+            format!("Copy: {}=L {}=R", CROSS_LEFT, CROSS_RIGHT),
+            format!("Channel: {} {}", CROSS_LEFT, CROSS_RIGHT),
+            format!(
+                "Filter: ON LPQ Fc {} Hz Q {}",
+                format_config_number(self.cutoff, precision.frequency, 0),
+                format_config_number(FILTER_Q, precision.q_factor, 2)
+            ),
+        ];
+        if self.delay_ms > 0.0 {
+            lines.push(format!("Delay: {} ms", number(self.delay_ms.into())));
+        }
+        lines.extend([
+            String::from("Channel: L R"),
+            self.direct_shelf().to_eapo_line_with(precision),
+            format!(
+                "Copy: L={d}*L+{c}*{xr} R={d}*R+{c}*{xl}",
+                d = number(direct),
+                c = number(cross),
+                xl = CROSS_LEFT,
+                xr = CROSS_RIGHT
+            ),
+            String::from("Channel: all"),
+        ]);
+        lines
+    }
+
+    /// Returns the magnitude responses in dB of the direct path (same ear)
+    /// and the crossfed path (opposite ear) at each frequency.
+    pub fn response_db(&self, freqs: &[f64], sample_rate: f64) -> (Vec<f64>, Vec<f64>) {
+        let (direct, cross) = self.mix_gains();
+        let shelf = Biquad::from_band(&self.direct_shelf(), sample_rate);
+        let lowpass = Biquad::lowpass(self.cutoff.into(), FILTER_Q.into(), sample_rate);
+
+        freqs
+            .iter()
+            .map(|&freq| {
+                (
+                    20.0 * direct.log10() + shelf.magnitude_db(freq, sample_rate),
+                    20.0 * cross.log10() + lowpass.magnitude_db(freq, sample_rate),
+                )
+            })
+            .unzip()
+    }
+
+    /// Total delay of the crossfed path at low frequencies, in seconds.
+    ///
+    /// Includes the low-pass group delay at DC, `1 / (Q * w0)`.
+    pub fn delay_seconds(&self) -> f64 {
+        let w0 = 2.0 * std::f64::consts::PI * f64::from(self.cutoff);
+        1.0 / (f64::from(FILTER_Q) * w0) + f64::from(self.delay_ms) / 1000.0
+    }
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Lists the crossfeed presets.
+#[tauri::command]
+pub fn list_crossfeed_presets() -> Vec<CrossfeedPresetInfo> {
+    CrossfeedPreset::ALL
+        .iter()
+        .map(|&preset| CrossfeedPresetInfo {
+            preset,
+            label: preset.label(),
+            crossfeed: preset.crossfeed(),
+        })
+        .collect()
+}
+
+/// Sets or removes the crossfeed stage of a saved profile.
+///
+/// # Arguments
+///
+/// * `name` - The profile name
+/// * `crossfeed` - The new stage, or `None` to remove it
+///
+/// # Errors
+///
+/// Returns an error if the parameters are out of range or the profile
+/// cannot be loaded or written.
+#[tauri::command]
+pub fn set_profile_crossfeed(name: String, crossfeed: Option<Crossfeed>) -> Result<(), String> {
+    if let Some(crossfeed) = &crossfeed {
+        crossfeed.validate()?;
+    }
+    let mut profile = load_profile(name)?;
+    profile.crossfeed = crossfeed;
+    write_profile(&profile)
+}
+
+/// Sets or removes the crossfeed stage of the current EQ.
+///
+/// The stage is written to the config on the next apply.
+///
+/// # Arguments
+///
+/// * `crossfeed` - The new stage, or `None` to remove it
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for notifications
+///
+/// # Errors
+///
+/// Returns an error if the parameters are out of range or settings cannot
+/// be saved.
+#[tauri::command]
+pub fn set_crossfeed(
+    crossfeed: Option<Crossfeed>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if let Some(crossfeed) = &crossfeed {
+        crossfeed.validate()?;
+    }
+    {
+        let mut settings = state.settings.lock();
+        settings.crossfeed = crossfeed;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid() {
+        for preset in CrossfeedPreset::ALL {
+            assert!(preset.crossfeed().validate().is_ok(), "{:?}", preset);
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_parameters() {
+        let base = Crossfeed::default();
+        for crossfeed in [
+            Crossfeed {
+                cutoff: 100.0,
+                ..base
+            },
+            Crossfeed {
+                feed_level: 0.0,
+                ..base
+            },
+            Crossfeed {
+                delay_ms: -0.1,
+                ..base
+            },
+        ] {
+            assert!(crossfeed.validate().is_err(), "{:?}", crossfeed);
+        }
+    }
+
+    #[test]
+    fn expands_into_copy_filter_and_channel_directives() {
+        let lines = Crossfeed {
+            delay_ms: 0.1,
+            ..CrossfeedPreset::ChuMoy.crossfeed()
+        }
+        .config_lines(&ConfigPrecision::default());
+
+        assert_eq!(lines[1], "Copy: XFL=L XFR=R");
+        assert_eq!(lines[2], "Channel: XFL XFR");
+        assert_eq!(lines[3], "Filter: ON LPQ Fc 700 Hz Q 0.707");
+        assert_eq!(lines[4], "Delay: 0.1 ms");
+        assert_eq!(lines[5], "Channel: L R");
+        assert!(lines[6].starts_with("Filter: ON HSC Fc 700 Hz Gain 3.5"));
+        assert!(lines[7].starts_with("Copy: L=0.66"));
+        assert!(lines[7].contains("*XFR R=0.66"));
+        assert_eq!(lines.last().unwrap(), "Channel: all");
+    }
+
+    #[test]
+    fn preset_without_delay_omits_delay_line() {
+        let lines = Crossfeed::default().config_lines(&ConfigPrecision::default());
+        assert!(!lines.iter().any(|l| l.starts_with("Delay:")));
+    }
+
+    #[test]
+    fn filters_follow_config_precision() {
+        let precision = ConfigPrecision {
+            frequency: Some(1),
+            gain: Some(0),
+            q_factor: Some(1),
+            preamp: None,
+        };
+        let lines = CrossfeedPreset::ChuMoy.crossfeed().config_lines(&precision);
+
+        assert_eq!(lines[3], "Filter: ON LPQ Fc 700.0 Hz Q 0.7");
+        assert!(lines[5].starts_with("Filter: ON HSC Fc 700.0 Hz Gain 4 dB Q 0.7"));
+        // Mix gains stay lossless
+        assert!(lines[6].starts_with("Copy: L=0.66"));
+    }
+
+    #[test]
+    fn centered_signal_stays_flat() {
+        let crossfeed = CrossfeedPreset::JanMeier.crossfeed();
+        let freqs = [20.0, 10000.0];
+        let (direct, cross) = crossfeed.response_db(&freqs, 48000.0);
+
+        // Bass: both paths add up (in phase) to unity
+        let bass = 10f64.powf(direct[0] / 20.0) + 10f64.powf(cross[0] / 20.0);
+        assert!((bass - 1.0).abs() < 0.01, "{}", bass);
+
+        // Treble: the boosted direct path alone is at unity
+        assert!(direct[1].abs() < 0.3, "{}", direct[1]);
+        assert!(cross[1] < -30.0);
+    }
+
+    #[test]
+    fn crossfed_path_is_delayed_like_a_loudspeaker() {
+        let delay = CrossfeedPreset::Default.crossfeed().delay_seconds();
+        assert!((0.2e-3..0.4e-3).contains(&delay), "{}", delay);
+    }
+}
@@ -0,0 +1,395 @@
+//! Filter math shared by backend features that need the EQ's frequency response.
+//!
+//! Mirrors `lib/audio-math.ts` in the frontend (RBJ Audio EQ Cookbook biquads,
+//! as implemented by EqualizerAPO) so that values computed here, such as the
+//! predicted peak gain shown in the tray, match what the UI displays.
+
+use std::f64::consts::PI;
+
+use serde::Serialize;
+
+use crate::crossfeed::Crossfeed;
+use crate::graphic_eq;
+use crate::types::{EqProfile, FilterType, GraphicEqPoint, ParametricBand};
+
+/// Sample rate used when the actual device rate is unknown.
+pub const DEFAULT_SAMPLE_RATE: f64 = 48000.0;
+
+/// Number of log-spaced points used for response calculations.
+pub const NUM_POINTS: usize = 200;
+
+/// Lowest frequency of the response grid, in Hz.
+pub const FREQ_MIN: f64 = 20.0;
+
+/// Highest frequency of the response grid, in Hz.
+pub const FREQ_MAX: f64 = 20000.0;
+
+/// Returns [`NUM_POINTS`] log-spaced frequencies from 20 Hz to 20 kHz.
+pub fn frequency_points() -> Vec<f64> {
+    log_spaced(NUM_POINTS, FREQ_MIN, FREQ_MAX)
+}
+
+/// Returns `count` log-spaced frequencies from `min` to `max` inclusive.
+pub fn log_spaced(count: usize, min: f64, max: f64) -> Vec<f64> {
+    if count < 2 {
+        return vec![min; count];
+    }
+    let (log_min, log_max) = (min.log10(), max.log10());
+    (0..count)
+        .map(|i| {
+            let t = i as f64 / (count - 1) as f64;
+            10f64.powf(log_min + t * (log_max - log_min))
+        })
+        .collect()
+}
+
+/// Normalized biquad coefficients (`a0 == 1`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Biquad {
+    pub b0: f64,
+    pub b1: f64,
+    pub b2: f64,
+    pub a1: f64,
+    pub a2: f64,
+}
+
+impl Biquad {
+    /// Computes the RBJ coefficients for a band.
+    ///
+    /// Shelf filters map Q to the cookbook's slope as `S = 1 / (2 * Q²)`, the
+    /// same mapping the frontend uses. The center frequency is clamped below
+    /// Nyquist.
+    pub fn from_band(band: &ParametricBand, sample_rate: f64) -> Self {
+        let fc = (band.frequency as f64).clamp(1.0, sample_rate / 2.0 - 1.0);
+        let q = band.q_factor as f64;
+        let a = 10f64.powf(band.gain as f64 / 40.0);
+        let w0 = 2.0 * PI * fc / sample_rate;
+        let (sin_w0, cos_w0) = w0.sin_cos();
+
+        let (b0, b1, b2, a0, a1, a2) = match band.filter_type {
+            FilterType::Peaking => {
+                let alpha = sin_w0 / (2.0 * q);
+                (
+                    1.0 + alpha * a,
+                    -2.0 * cos_w0,
+                    1.0 - alpha * a,
+                    1.0 + alpha / a,
+                    -2.0 * cos_w0,
+                    1.0 - alpha / a,
+                )
+            }
+            FilterType::LowShelf | FilterType::HighShelf => {
+                // Guard against Q = 0 (division by zero)
+                let safe_q = q.max(0.0001);
+                let s = 1.0 / (2.0 * safe_q * safe_q);
+                let alpha = (sin_w0 / 2.0) * ((a + 1.0 / a) * (1.0 / s - 1.0) + 2.0).sqrt();
+                let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+                if band.filter_type == FilterType::LowShelf {
+                    (
+                        a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+                        2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0),
+                        a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+                        (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+                        -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0),
+                        (a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+                    )
+                } else {
+                    (
+                        a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha),
+                        -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0),
+                        a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha),
+                        (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha,
+                        2.0 * ((a - 1.0) - (a + 1.0) * cos_w0),
+                        (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha,
+                    )
+                }
+            }
+        };
+
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    /// Computes the RBJ coefficients of a second-order low-pass filter
+    /// (EqualizerAPO's `LPQ`).
+    pub fn lowpass(frequency: f64, q: f64, sample_rate: f64) -> Self {
+        let fc = frequency.clamp(1.0, sample_rate / 2.0 - 1.0);
+        let w0 = 2.0 * PI * fc / sample_rate;
+        let (sin_w0, cos_w0) = w0.sin_cos();
+        let alpha = sin_w0 / (2.0 * q.max(0.0001));
+        let a0 = 1.0 + alpha;
+
+        Self {
+            b0: (1.0 - cos_w0) / 2.0 / a0,
+            b1: (1.0 - cos_w0) / a0,
+            b2: (1.0 - cos_w0) / 2.0 / a0,
+            a1: -2.0 * cos_w0 / a0,
+            a2: (1.0 - alpha) / a0,
+        }
+    }
+
+    /// Returns the magnitude response in dB at `freq` Hz.
+    ///
+    /// Returns -100 dB for degenerate responses (zero or non-finite magnitude).
+    pub fn magnitude_db(&self, freq: f64, sample_rate: f64) -> f64 {
+        let w = 2.0 * PI * freq.clamp(0.0, sample_rate / 2.0) / sample_rate;
+        let (sin_w, cos_w) = w.sin_cos();
+        let (sin_2w, cos_2w) = (2.0 * w).sin_cos();
+
+        let num_re = self.b0 + self.b1 * cos_w + self.b2 * cos_2w;
+        let num_im = -(self.b1 * sin_w + self.b2 * sin_2w);
+        let den_re = 1.0 + self.a1 * cos_w + self.a2 * cos_2w;
+        let den_im = -(self.a1 * sin_w + self.a2 * sin_2w);
+
+        let mag_squared = (num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im);
+        if mag_squared <= 0.0 || !mag_squared.is_finite() {
+            return -100.0;
+        }
+        10.0 * mag_squared.log10()
+    }
+}
+
+/// Returns the combined response in dB (including preamp and GraphicEQ
+/// curve) at each frequency.
+///
+/// Disabled bands are skipped, matching what is written to EqualizerAPO.
+pub fn response_db(
+    bands: &[ParametricBand],
+    graphic: &[GraphicEqPoint],
+    preamp: f32,
+    freqs: &[f64],
+    sample_rate: f64,
+) -> Vec<f64> {
+    let filters: Vec<Biquad> = bands
+        .iter()
+        .filter(|band| band.enabled)
+        .map(|band| Biquad::from_band(band, sample_rate))
+        .collect();
+
+    freqs
+        .iter()
+        .map(|&freq| {
+            preamp as f64
+                + graphic_eq::gain_at(graphic, freq)
+                + filters
+                    .iter()
+                    .map(|f| f.magnitude_db(freq, sample_rate))
+                    .sum::<f64>()
+        })
+        .collect()
+}
+
+/// Returns the maximum gain of the EQ across 20 Hz–20 kHz, in dB, rounded to
+/// one decimal place.
+///
+/// A positive value means full-scale input would clip.
+pub fn peak_gain_db(
+    bands: &[ParametricBand],
+    graphic: &[GraphicEqPoint],
+    preamp: f32,
+    sample_rate: f64,
+) -> f32 {
+    rounded_peak(&response_db(
+        bands,
+        graphic,
+        preamp,
+        &frequency_points(),
+        sample_rate,
+    ))
+}
+
+/// Returns the maximum gain a profile can apply across 20 Hz–20 kHz, in dB,
+/// rounded to one decimal place.
+///
+/// Unlike [`peak_gain_db`], this includes the mid/side and crossfeed stages,
+/// assuming the worst case where both channels add up in phase.
+pub fn profile_peak_gain_db(profile: &EqProfile, sample_rate: f64) -> f32 {
+    let freqs = frequency_points();
+    let mut gains = response_db(
+        &profile.bands,
+        &profile.graphic_eq,
+        profile.preamp,
+        &freqs,
+        sample_rate,
+    );
+
+    if let Some(mid_side) = &profile.mid_side {
+        for (gain, stage) in gains
+            .iter_mut()
+            .zip(mid_side.response_db(&freqs, sample_rate))
+        {
+            *gain += stage;
+        }
+    }
+    if let Some(crossfeed) = &profile.crossfeed {
+        let (direct, cross) = crossfeed.response_db(&freqs, sample_rate);
+        for ((gain, d), c) in gains.iter_mut().zip(&direct).zip(&cross) {
+            let linear = 10f64.powf(d / 20.0) + 10f64.powf(c / 20.0);
+            *gain += 20.0 * linear.log10();
+        }
+    }
+
+    rounded_peak(&gains)
+}
+
+/// Returns the largest gain, rounded to one decimal place.
+fn rounded_peak(gains: &[f64]) -> f32 {
+    let max = gains.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    ((max * 10.0).round() / 10.0) as f32
+}
+
+/// A frequency response on a log-spaced grid.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FrequencyResponse {
+    /// Frequencies in Hz.
+    pub frequencies: Vec<f64>,
+    /// Gain in dB at each frequency.
+    pub gains: Vec<f64>,
+    /// Maximum gain in dB, rounded to 0.1 dB.
+    pub peak_gain: f32,
+    /// Gain in dB reaching the opposite ear through crossfeed, if enabled.
+    /// `gains` is then the response at the same ear.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crossfeed_gains: Option<Vec<f64>>,
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Calculates the frequency response of bands, GraphicEQ curve and preamp.
+///
+/// # Arguments
+///
+/// * `bands` - Parametric bands
+/// * `graphic_eq` - GraphicEQ curve; `None` for parametric-only
+/// * `preamp` - Preamp gain in dB
+/// * `sample_rate` - Sample rate in Hz (default [`DEFAULT_SAMPLE_RATE`])
+/// * `crossfeed` - Crossfeed stage; `None` if disabled
+#[tauri::command]
+pub fn get_frequency_response(
+    bands: Vec<ParametricBand>,
+    graphic_eq: Option<Vec<GraphicEqPoint>>,
+    preamp: f32,
+    sample_rate: Option<f64>,
+    crossfeed: Option<Crossfeed>,
+) -> FrequencyResponse {
+    let graphic = graphic_eq.unwrap_or_default();
+    let sample_rate = sample_rate.unwrap_or(DEFAULT_SAMPLE_RATE);
+    let frequencies = frequency_points();
+    let mut gains = response_db(&bands, &graphic, preamp, &frequencies, sample_rate);
+    let peak_gain = peak_gain_db(&bands, &graphic, preamp, sample_rate);
+
+    let crossfeed_gains = crossfeed.map(|crossfeed| {
+        let (direct, cross) = crossfeed.response_db(&frequencies, sample_rate);
+        let cross_gains = gains.iter().zip(&cross).map(|(g, c)| g + c).collect();
+        for (gain, d) in gains.iter_mut().zip(&direct) {
+            *gain += d;
+        }
+        cross_gains
+    });
+
+    FrequencyResponse {
+        frequencies,
+        gains,
+        peak_gain,
+        crossfeed_gains,
+    }
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_band;
+
+    #[test]
+    fn frequency_points_span_audible_range() {
+        let freqs = frequency_points();
+        assert_eq!(freqs.len(), NUM_POINTS);
+        assert!((freqs[0] - FREQ_MIN).abs() < 1e-9);
+        assert!((freqs[NUM_POINTS - 1] - FREQ_MAX).abs() < 1e-6);
+    }
+
+    #[test]
+    fn peaking_gain_at_center_frequency() {
+        let filter = Biquad::from_band(&test_band(FilterType::Peaking, 1000.0, 6.0, 1.41), 48000.0);
+        assert!((filter.magnitude_db(1000.0, 48000.0) - 6.0).abs() < 0.01);
+        assert!(filter.magnitude_db(50.0, 48000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn shelves_reach_full_gain_away_from_corner() {
+        let low = Biquad::from_band(
+            &test_band(FilterType::LowShelf, 200.0, -4.0, 0.707),
+            48000.0,
+        );
+        assert!((low.magnitude_db(20.0, 48000.0) + 4.0).abs() < 0.1);
+        assert!(low.magnitude_db(10000.0, 48000.0).abs() < 0.1);
+
+        let high = Biquad::from_band(
+            &test_band(FilterType::HighShelf, 5000.0, 3.0, 0.707),
+            48000.0,
+        );
+        assert!((high.magnitude_db(20000.0, 48000.0) - 3.0).abs() < 0.2);
+        assert!(high.magnitude_db(100.0, 48000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn peak_gain_includes_preamp_and_skips_disabled() {
+        let mut boost = test_band(FilterType::Peaking, 1000.0, 6.0, 1.0);
+        let peak = peak_gain_db(&[boost.clone()], &[], -2.0, DEFAULT_SAMPLE_RATE);
+        assert!((peak - 4.0).abs() <= 0.1);
+
+        boost.enabled = false;
+        assert_eq!(peak_gain_db(&[boost], &[], -2.0, DEFAULT_SAMPLE_RATE), -2.0);
+    }
+
+    #[test]
+    fn flat_response_is_preamp() {
+        let response = response_db(&[], &[], 1.5, &[100.0, 1000.0], DEFAULT_SAMPLE_RATE);
+        assert_eq!(response, vec![1.5, 1.5]);
+    }
+
+    #[test]
+    fn graphic_curve_adds_to_response() {
+        let graphic = [
+            GraphicEqPoint {
+                frequency: 100.0,
+                gain: 3.0,
+            },
+            GraphicEqPoint {
+                frequency: 1000.0,
+                gain: -3.0,
+            },
+        ];
+        let response = response_db(&[], &graphic, -1.0, &[50.0, 5000.0], DEFAULT_SAMPLE_RATE);
+        assert_eq!(response, vec![2.0, -4.0]);
+        assert_eq!(peak_gain_db(&[], &graphic, -1.0, DEFAULT_SAMPLE_RATE), 2.0);
+    }
+
+    #[test]
+    fn profile_peak_includes_mid_side_width() {
+        let mut profile = EqProfile {
+            preamp: -3.0,
+            bands: vec![],
+            ..Default::default()
+        };
+        assert_eq!(profile_peak_gain_db(&profile, DEFAULT_SAMPLE_RATE), -3.0);
+
+        profile.mid_side = Some(crate::mid_side::MidSide {
+            width: 2.0,
+            ..Default::default()
+        });
+        assert_eq!(profile_peak_gain_db(&profile, DEFAULT_SAMPLE_RATE), 3.0);
+    }
+}
@@ -0,0 +1,717 @@
+//! Export of profiles to other equalizer software.
+//!
+//! [`ExportFormat`] is the registry of supported targets; each variant knows
+//! its display name, file extension and how to write an [`EqProfile`].
+//!
+//! | Format       | Output                                                  |
+//! |--------------|---------------------------------------------------------|
+//! | CamillaDSP   | YAML `filters` and `pipeline` (v3 syntax)               |
+//! | PipeWire     | `libpipewire-module-filter-chain` config with `bq_*`    |
+//! | EasyEffects  | Output preset JSON with one equalizer (APO mode)        |
+//! | Peace        | `.peace` preset (INI)                                   |
+//! | JamesDSP     | `audio.conf` lines enabling the arbitrary-response EQ   |
+//! | Wavelet      | 127-point `GraphicEQ:` line                             |
+//! | miniDSP      | Biquad coefficients for the advanced biquad editor      |
+//! | REW          | Filter settings text, disabled bands as `OFF` slots     |
+//!
+//! Disabled bands are left out of every format except REW, whose filter
+//! settings files can mark a filter `OFF` (see [`crate::rew`]). Parametric
+//! formats cannot carry a GraphicEQ curve; when the profile has one, it is
+//! left out with a warning. The GraphicEQ formats (JamesDSP, Wavelet) instead
+//! sample the response of the bands, curve and preamp.
+//!
+//! No format can carry the other stages of a profile (mid/side, crossfeed,
+//! convolution, VST plugins, channel routing and bass management); each one
+//! present is left out with a warning.
+
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::dsp::{self, Biquad};
+use crate::fitting::rasterize_grid;
+use crate::graphic_eq::to_eapo_line;
+use crate::profile::load_profile;
+use crate::rew::to_rew_filters;
+use crate::types::{EqProfile, FilterType, GraphicEqPoint, ParametricBand};
+
+/// Number of biquads per channel most miniDSP devices accept.
+const MINIDSP_MAX_BIQUADS: usize = 10;
+
+/// Number of bands in an EasyEffects equalizer.
+const EASYEFFECTS_MAX_BANDS: usize = 32;
+
+/// A supported export format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    CamillaDsp,
+    PipeWire,
+    EasyEffects,
+    Peace,
+    JamesDsp,
+    Wavelet,
+    MiniDsp,
+    Rew,
+}
+
+/// Output of an export.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Export {
+    /// File content.
+    pub content: String,
+    /// Parts of the profile that could not be exported exactly.
+    pub warnings: Vec<String>,
+}
+
+/// Describes an export format for selection in the UI.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportFormatInfo {
+    /// Value to pass to [`export_profile`].
+    pub id: ExportFormat,
+    /// Display name.
+    pub label: &'static str,
+    /// Suggested file extension, without the dot.
+    pub extension: &'static str,
+}
+
+impl ExportFormat {
+    /// All formats, in display order.
+    pub const ALL: [ExportFormat; 8] = [
+        ExportFormat::CamillaDsp,
+        ExportFormat::PipeWire,
+        ExportFormat::EasyEffects,
+        ExportFormat::Peace,
+        ExportFormat::JamesDsp,
+        ExportFormat::Wavelet,
+        ExportFormat::MiniDsp,
+        ExportFormat::Rew,
+    ];
+
+    /// Returns the display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::CamillaDsp => "CamillaDSP",
+            ExportFormat::PipeWire => "PipeWire filter-chain",
+            ExportFormat::EasyEffects => "EasyEffects",
+            ExportFormat::Peace => "Peace",
+            ExportFormat::JamesDsp => "JamesDSP",
+            ExportFormat::Wavelet => "Wavelet",
+            ExportFormat::MiniDsp => "miniDSP biquads",
+            ExportFormat::Rew => "REW filter settings",
+        }
+    }
+
+    /// Returns the suggested file extension.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::CamillaDsp => "yml",
+            ExportFormat::PipeWire | ExportFormat::JamesDsp => "conf",
+            ExportFormat::EasyEffects => "json",
+            ExportFormat::Peace => "peace",
+            ExportFormat::Wavelet | ExportFormat::MiniDsp | ExportFormat::Rew => "txt",
+        }
+    }
+
+    /// Returns whether the format stores a sampled curve rather than bands.
+    fn is_graphic(self) -> bool {
+        matches!(self, ExportFormat::JamesDsp | ExportFormat::Wavelet)
+    }
+
+    /// Writes a profile in this format.
+    ///
+    /// `sample_rate` is used for miniDSP coefficients and when sampling the
+    /// response for GraphicEQ formats.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile exceeds a hard limit of the format.
+    pub fn export(self, profile: &EqProfile, sample_rate: f64) -> Result<Export, String> {
+        let mut warnings = Vec::new();
+        if !self.is_graphic() && !profile.graphic_eq.is_empty() {
+            warnings.push(format!(
+                "{} does not support GraphicEQ curves; the curve was not exported",
+                self.label()
+            ));
+        }
+        for (present, feature, left_out) in unexported_stages(profile) {
+            if present {
+                warnings.push(format!(
+                    "{} does not support {}; {}",
+                    self.label(),
+                    feature,
+                    left_out
+                ));
+            }
+        }
+
+        let content = match self {
+            ExportFormat::CamillaDsp => to_camilladsp(profile),
+            ExportFormat::PipeWire => to_pipewire(profile),
+            ExportFormat::EasyEffects => to_easyeffects(profile)?,
+            ExportFormat::Peace => to_peace(profile),
+            ExportFormat::JamesDsp => to_jamesdsp(profile, sample_rate),
+            ExportFormat::Wavelet => to_wavelet(profile, sample_rate),
+            ExportFormat::MiniDsp => {
+                let (content, count) = to_minidsp(profile, sample_rate);
+                if count > MINIDSP_MAX_BIQUADS {
+                    warnings.push(format!(
+                        "{} biquads exported; many miniDSP devices accept at most {} per channel",
+                        count, MINIDSP_MAX_BIQUADS
+                    ));
+                }
+                content
+            }
+            ExportFormat::Rew => to_rew_filters(profile),
+        };
+        Ok(Export { content, warnings })
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Returns the stages no format can carry: whether the profile has each one,
+/// the feature the format lacks, and what was left out.
+fn unexported_stages(profile: &EqProfile) -> [(bool, &'static str, &'static str); 5] {
+    [
+        (
+            profile.mid_side.is_some(),
+            "mid/side processing",
+            "the mid/side stage was not exported",
+        ),
+        (
+            profile.crossfeed.is_some(),
+            "crossfeed",
+            "the crossfeed stage was not exported",
+        ),
+        (
+            profile.convolution.is_some(),
+            "convolution",
+            "the impulse response was not exported",
+        ),
+        (
+            profile.vst_plugin.is_some(),
+            "VST plugins",
+            "the plugin was not exported",
+        ),
+        (
+            profile.routing.is_some(),
+            "channel routing",
+            "the routing and bass management were not exported",
+        ),
+    ]
+}
+
+/// Returns the enabled bands of a profile.
+fn enabled_bands(profile: &EqProfile) -> impl Iterator<Item = &ParametricBand> {
+    profile.bands.iter().filter(|band| band.enabled)
+}
+
+/// Converts to `f64` through the shortest decimal representation, so that
+/// JSON shows `0.7` rather than `0.699999988079071`.
+fn json_number(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or(value as f64)
+}
+
+/// Quotes a string for YAML, JSON-style, and PipeWire's SPA-JSON.
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Samples the complete response of a profile (bands, curve and preamp) on
+/// the default 127-point grid, rounded to 0.1 dB.
+fn sampled_response(profile: &EqProfile, sample_rate: f64) -> Vec<GraphicEqPoint> {
+    let frequencies = rasterize_grid(None, None).unwrap_or_default();
+    let freqs: Vec<f64> = frequencies.iter().map(|&f| f as f64).collect();
+    dsp::response_db(
+        &profile.bands,
+        &profile.graphic_eq,
+        profile.preamp,
+        &freqs,
+        sample_rate,
+    )
+    .into_iter()
+    .zip(frequencies)
+    .map(|(gain, frequency)| GraphicEqPoint {
+        frequency,
+        gain: (gain * 10.0).round() as f32 / 10.0,
+    })
+    .collect()
+}
+
+// =============================================================================
+// Writers
+// =============================================================================
+
+/// Writes CamillaDSP `filters` and `pipeline` sections for two channels.
+fn to_camilladsp(profile: &EqProfile) -> String {
+    let mut out = format!("title: {}\nfilters:\n", quote(&profile.name));
+    out += &format!(
+        "  preamp:\n    type: Gain\n    parameters:\n      gain: {}\n",
+        profile.preamp
+    );
+
+    let mut names = vec!["preamp".to_string()];
+    for (i, band) in enabled_bands(profile).enumerate() {
+        let name = format!("band_{}", i + 1);
+        let kind = match band.filter_type {
+            FilterType::Peaking => "Peaking",
+            FilterType::LowShelf => "Lowshelf",
+            FilterType::HighShelf => "Highshelf",
+        };
+        out += &format!(
+            "  {}:\n    type: Biquad\n    parameters:\n      type: {}\n      freq: {}\n      gain: {}\n      q: {}\n",
+            name, kind, band.frequency, band.gain, band.q_factor
+        );
+        names.push(name);
+    }
+
+    out += "pipeline:\n  - type: Filter\n    channels: [0, 1]\n    names:\n";
+    for name in names {
+        out += &format!("      - {}\n", name);
+    }
+    out
+}
+
+/// Writes a PipeWire filter-chain module config creating an EQ sink.
+///
+/// The preamp is a `bq_highshelf` at 0 Hz, which applies a flat gain.
+fn to_pipewire(profile: &EqProfile) -> String {
+    let mut nodes = vec![format!(
+        "{{ type = builtin name = preamp label = bq_highshelf control = {{ \"Freq\" = 0 \"Q\" = 1.0 \"Gain\" = {} }} }}",
+        profile.preamp
+    )];
+    for (i, band) in enabled_bands(profile).enumerate() {
+        let label = match band.filter_type {
+            FilterType::Peaking => "bq_peaking",
+            FilterType::LowShelf => "bq_lowshelf",
+            FilterType::HighShelf => "bq_highshelf",
+        };
+        nodes.push(format!(
+            "{{ type = builtin name = eq_band_{} label = {} control = {{ \"Freq\" = {} \"Q\" = {} \"Gain\" = {} }} }}",
+            i + 1,
+            label,
+            band.frequency,
+            band.q_factor,
+            band.gain
+        ));
+    }
+
+    let names: Vec<String> = std::iter::once("preamp".to_string())
+        .chain((1..nodes.len()).map(|i| format!("eq_band_{}", i)))
+        .collect();
+    let links: Vec<String> = names
+        .windows(2)
+        .map(|pair| {
+            format!(
+                "{{ output = \"{}:Out\" input = \"{}:In\" }}",
+                pair[0], pair[1]
+            )
+        })
+        .collect();
+
+    let indent = |lines: Vec<String>| -> String {
+        lines
+            .iter()
+            .map(|line| format!("                    {}\n", line))
+            .collect()
+    };
+    let description = quote(&profile.name);
+    format!(
+        "context.modules = [\n    {{ name = libpipewire-module-filter-chain\n        args = {{\n            node.description = {}\n            media.name = {}\n            filter.graph = {{\n                nodes = [\n{}                ]\n                links = [\n{}                ]\n            }}\n            audio.channels = 2\n            audio.position = [ FL FR ]\n            capture.props = {{\n                node.name = \"effect_input.eqapo\"\n                media.class = Audio/Sink\n            }}\n            playback.props = {{\n                node.name = \"effect_output.eqapo\"\n                node.passive = true\n            }}\n        }}\n    }}\n]\n",
+        description,
+        description,
+        indent(nodes),
+        indent(links)
+    )
+}
+
+/// Writes an EasyEffects output preset with one equalizer.
+///
+/// # Errors
+///
+/// Returns an error if the profile has more enabled bands than EasyEffects
+/// supports.
+fn to_easyeffects(profile: &EqProfile) -> Result<String, String> {
+    let bands: Vec<&ParametricBand> = enabled_bands(profile).collect();
+    if bands.len() > EASYEFFECTS_MAX_BANDS {
+        return Err(format!(
+            "EasyEffects supports at most {} bands, profile has {}",
+            EASYEFFECTS_MAX_BANDS,
+            bands.len()
+        ));
+    }
+
+    let mut channel = serde_json::Map::new();
+    for (i, band) in bands.iter().enumerate() {
+        let kind = match band.filter_type {
+            FilterType::Peaking => "Bell",
+            FilterType::LowShelf => "Lo-shelf",
+            FilterType::HighShelf => "Hi-shelf",
+        };
+        channel.insert(
+            format!("band{}", i),
+            json!({
+                "frequency": json_number(band.frequency),
+                "gain": json_number(band.gain),
+                "mode": "APO (DR)",
+                "mute": false,
+                "q": json_number(band.q_factor),
+                "slope": "x1",
+                "solo": false,
+                "type": kind,
+                "width": 4.0
+            }),
+        );
+    }
+
+    let preset = json!({
+        "output": {
+            "blocklist": [],
+            "equalizer#0": {
+                "balance": 0.0,
+                "bypass": false,
+                "input-gain": json_number(profile.preamp),
+                "left": channel,
+                "mode": "IIR",
+                "num-bands": bands.len(),
+                "output-gain": 0.0,
+                "pitch-left": 0.0,
+                "pitch-right": 0.0,
+                "right": channel,
+                "split-channels": false
+            },
+            "plugins_order": ["equalizer#0"]
+        }
+    });
+    serde_json::to_string_pretty(&preset)
+        .map(|json| json + "\n")
+        .map_err(|e| format!("Failed to serialize EasyEffects preset: {}", e))
+}
+
+/// Writes a Peace preset.
+///
+/// Peace numbers its filter types starting at 1: peak is 1, low shelf 5
+/// and high shelf 6.
+fn to_peace(profile: &EqProfile) -> String {
+    let mut lines = vec![
+        "[General]".to_string(),
+        format!("PreAmp={}", profile.preamp),
+        "PostAmp=0".to_string(),
+        "[Filters]".to_string(),
+    ];
+    for (i, band) in enabled_bands(profile).enumerate() {
+        let code = match band.filter_type {
+            FilterType::Peaking => 1,
+            FilterType::LowShelf => 5,
+            FilterType::HighShelf => 6,
+        };
+        let n = i + 1;
+        lines.push(format!("Filter{}={}", n, code));
+        lines.push(format!("Frequency{}={}", n, band.frequency));
+        lines.push(format!("Gain{}={}", n, band.gain));
+        lines.push(format!("Quality{}={}", n, band.q_factor));
+    }
+    lines.join("\r\n") + "\r\n"
+}
+
+/// Writes JamesDSP `audio.conf` lines for its arbitrary-response EQ.
+fn to_jamesdsp(profile: &EqProfile, sample_rate: f64) -> String {
+    format!(
+        "graphiceq_enable=true\ngraphiceq_param=\"{}\"\n",
+        to_eapo_line(&sampled_response(profile, sample_rate))
+    )
+}
+
+/// Writes a 127-point GraphicEQ line as imported by Wavelet.
+fn to_wavelet(profile: &EqProfile, sample_rate: f64) -> String {
+    to_eapo_line(&sampled_response(profile, sample_rate)) + "\n"
+}
+
+/// Negates a coefficient without producing `-0`.
+fn negate(value: f64) -> f64 {
+    if value == 0.0 {
+        0.0
+    } else {
+        -value
+    }
+}
+
+/// Writes miniDSP biquad coefficients, returning the text and the number
+/// of biquads.
+///
+/// miniDSP expects `a1` and `a2` with the opposite sign of the usual
+/// convention. A non-zero preamp becomes a gain-only first biquad.
+fn to_minidsp(profile: &EqProfile, sample_rate: f64) -> (String, usize) {
+    let mut biquads = Vec::new();
+    if profile.preamp != 0.0 {
+        biquads.push(Biquad {
+            b0: 10f64.powf(profile.preamp as f64 / 20.0),
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+        });
+    }
+    biquads.extend(enabled_bands(profile).map(|band| Biquad::from_band(band, sample_rate)));
+
+    let lines: Vec<String> = biquads
+        .iter()
+        .enumerate()
+        .flat_map(|(i, bq)| {
+            [
+                format!("biquad{},", i + 1),
+                format!("b0={},", bq.b0),
+                format!("b1={},", bq.b1),
+                format!("b2={},", bq.b2),
+                format!("a1={},", negate(bq.a1)),
+                format!("a2={},", negate(bq.a2)),
+            ]
+        })
+        .collect();
+    let text = lines.join("\n");
+    let text = text.strip_suffix(',').unwrap_or(&text).to_string();
+    (text + "\n", biquads.len())
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Lists the supported export formats.
+#[tauri::command]
+pub fn list_export_formats() -> Vec<ExportFormatInfo> {
+    ExportFormat::ALL
+        .iter()
+        .map(|&id| ExportFormatInfo {
+            id,
+            label: id.label(),
+            extension: id.extension(),
+        })
+        .collect()
+}
+
+/// Exports a saved profile to a file in another software's format.
+///
+/// # Arguments
+///
+/// * `name` - The profile to export
+/// * `format` - Target format
+/// * `path` - Destination file
+/// * `sample_rate` - Sample rate in Hz for coefficient formats (default
+///   [`dsp::DEFAULT_SAMPLE_RATE`])
+///
+/// # Returns
+///
+/// Warnings about parts of the profile that were not exported.
+///
+/// # Errors
+///
+/// Returns an error if the profile cannot be loaded or exported, or the
+/// file cannot be written.
+#[tauri::command]
+pub fn export_profile(
+    name: String,
+    format: ExportFormat,
+    path: String,
+    sample_rate: Option<f64>,
+) -> Result<Vec<String>, String> {
+    let profile = load_profile(name)?;
+    let export = format.export(&profile, sample_rate.unwrap_or(dsp::DEFAULT_SAMPLE_RATE))?;
+    fs::write(&path, export.content).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(export.warnings)
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Profile used for the golden files in `tests/golden/export`.
+    fn fixture() -> EqProfile {
+        let band = |filter_type, frequency, gain, q_factor, enabled| ParametricBand {
+            filter_type,
+            frequency,
+            gain,
+            q_factor,
+            enabled,
+        };
+        EqProfile {
+            name: "HD 600 \"Studio\"".to_string(),
+            preamp: -6.5,
+            bands: vec![
+                band(FilterType::LowShelf, 105.0, 5.5, 0.7, true),
+                band(FilterType::Peaking, 150.5, -2.25, 0.5, true),
+                band(FilterType::Peaking, 3000.0, 4.0, 2.0, false),
+                band(FilterType::HighShelf, 10000.0, -3.0, 0.707, true),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn golden(format: ExportFormat) -> String {
+        format
+            .export(&fixture(), dsp::DEFAULT_SAMPLE_RATE)
+            .unwrap()
+            .content
+    }
+
+    #[test]
+    fn camilladsp_matches_golden() {
+        assert_eq!(
+            golden(ExportFormat::CamillaDsp),
+            include_str!("../tests/golden/export/camilladsp.yml")
+        );
+    }
+
+    #[test]
+    fn pipewire_matches_golden() {
+        assert_eq!(
+            golden(ExportFormat::PipeWire),
+            include_str!("../tests/golden/export/pipewire.conf")
+        );
+    }
+
+    #[test]
+    fn easyeffects_matches_golden() {
+        assert_eq!(
+            golden(ExportFormat::EasyEffects),
+            include_str!("../tests/golden/export/easyeffects.json")
+        );
+    }
+
+    #[test]
+    fn peace_matches_golden() {
+        assert_eq!(
+            golden(ExportFormat::Peace),
+            include_str!("../tests/golden/export/peace.peace")
+        );
+    }
+
+    #[test]
+    fn jamesdsp_matches_golden() {
+        assert_eq!(
+            golden(ExportFormat::JamesDsp),
+            include_str!("../tests/golden/export/jamesdsp.conf")
+        );
+    }
+
+    #[test]
+    fn wavelet_matches_golden() {
+        assert_eq!(
+            golden(ExportFormat::Wavelet),
+            include_str!("../tests/golden/export/wavelet.txt")
+        );
+    }
+
+    #[test]
+    fn minidsp_matches_golden() {
+        assert_eq!(
+            golden(ExportFormat::MiniDsp),
+            include_str!("../tests/golden/export/minidsp.txt")
+        );
+    }
+
+    #[test]
+    fn warns_about_unexported_graphic_eq() {
+        let profile = EqProfile {
+            graphic_eq: vec![
+                GraphicEqPoint {
+                    frequency: 100.0,
+                    gain: 2.0,
+                },
+                GraphicEqPoint {
+                    frequency: 1000.0,
+                    gain: 0.0,
+                },
+            ],
+            ..fixture()
+        };
+        let export = ExportFormat::CamillaDsp.export(&profile, 48000.0).unwrap();
+        assert_eq!(export.warnings.len(), 1);
+
+        let export = ExportFormat::Wavelet.export(&profile, 48000.0).unwrap();
+        assert!(export.warnings.is_empty());
+    }
+
+    #[test]
+    fn warns_about_unexported_stages() {
+        let cases = [
+            (
+                EqProfile {
+                    mid_side: Some(crate::mid_side::MidSide {
+                        width: 1.5,
+                        ..Default::default()
+                    }),
+                    ..fixture()
+                },
+                ExportFormat::CamillaDsp,
+                "CamillaDSP does not support mid/side processing; the mid/side stage was not exported",
+            ),
+            (
+                EqProfile {
+                    crossfeed: Some(crate::crossfeed::Crossfeed::default()),
+                    ..fixture()
+                },
+                ExportFormat::JamesDsp,
+                "JamesDSP does not support crossfeed; the crossfeed stage was not exported",
+            ),
+            (
+                EqProfile {
+                    convolution: Some(crate::types::ConvolutionStage {
+                        file: "room.wav".to_string(),
+                    }),
+                    ..fixture()
+                },
+                ExportFormat::Wavelet,
+                "Wavelet does not support convolution; the impulse response was not exported",
+            ),
+            (
+                EqProfile {
+                    vst_plugin: Some(crate::vst::VstPlugin {
+                        library: "limiter.dll".to_string(),
+                        chunk: None,
+                    }),
+                    ..fixture()
+                },
+                ExportFormat::Peace,
+                "Peace does not support VST plugins; the plugin was not exported",
+            ),
+            (
+                EqProfile {
+                    routing: Some(crate::routing::Routing::default()),
+                    ..fixture()
+                },
+                ExportFormat::EasyEffects,
+                "EasyEffects does not support channel routing; the routing and bass management were not exported",
+            ),
+        ];
+        for (profile, format, expected) in cases {
+            let export = format.export(&profile, 48000.0).unwrap();
+            assert_eq!(export.warnings, vec![expected]);
+        }
+    }
+
+    #[test]
+    fn easyeffects_rejects_too_many_bands() {
+        let profile = EqProfile {
+            bands: vec![fixture().bands[1].clone(); EASYEFFECTS_MAX_BANDS + 1],
+            ..fixture()
+        };
+        assert!(ExportFormat::EasyEffects.export(&profile, 48000.0).is_err());
+    }
+
+    #[test]
+    fn formats_serialize_lowercase() {
+        let json = serde_json::to_value(ExportFormat::ALL).unwrap();
+        assert_eq!(json[0], "camilladsp");
+        assert_eq!(json[6], "minidsp");
+        assert_eq!(list_export_formats().len(), ExportFormat::ALL.len());
+    }
+}
@@ -0,0 +1,641 @@
+//! FIR impulse responses rendered from profiles.
+//!
+//! The magnitude response of a profile (bands, GraphicEQ curve and preamp)
+//! is sampled on a dense FFT grid and turned into an impulse response:
+//!
+//! - **Linear phase**: zero-phase spectrum, inverse FFT, centered in the
+//!   output. Symmetric, with a latency of half the length.
+//! - **Minimum phase**: built from the real cepstrum of the log magnitude
+//!   (homomorphic method). No pre-ringing and minimal latency, matching the
+//!   phase behaviour of the IIR filters EqualizerAPO applies.
+//!
+//! The result is truncated to the requested length and shaped by a window
+//! (a symmetric window for linear phase, its falling half for minimum
+//! phase), then written as a 32-bit float WAV.
+//!
+//! Crossfeed mixes each channel into the other, which a per-channel impulse
+//! cannot do. Profiles with crossfeed are therefore rendered as a
+//! four-channel true-stereo impulse when [`FirOptions::true_stereo`] is set,
+//! for convolution engines that take one, and refused otherwise.
+//! EqualizerAPO's `Convolution:` filter convolves each channel with the
+//! matching channel of the file, so it cannot use a true-stereo impulse.
+//!
+//! Profiles with mid/side processing are refused: its bands act on the sum
+//! and difference of the channels, which no magnitude-only render of each
+//! path reproduces.
+
+use std::f64::consts::PI;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dsp;
+use crate::profile::load_profile;
+use crate::types::EqProfile;
+use crate::wav;
+
+/// Shortest impulse response, in samples.
+pub const MIN_FIR_LENGTH: usize = 64;
+
+/// Longest impulse response, in samples.
+pub const MAX_FIR_LENGTH: usize = 1 << 18;
+
+/// Smallest FFT used for the design, to keep the response finely sampled
+/// even for short filters.
+const MIN_FFT_SIZE: usize = 1 << 14;
+
+/// Phase response of the impulse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FirPhase {
+    Minimum,
+    Linear,
+}
+
+/// Window applied to the truncated impulse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FirWindow {
+    Rectangular,
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl FirWindow {
+    /// Returns the window value at position `x` in `0.0..=1.0`, where 0.5 is
+    /// the center.
+    fn value(self, x: f64) -> f64 {
+        let c = (2.0 * PI * x).cos();
+        match self {
+            FirWindow::Rectangular => 1.0,
+            FirWindow::Hann => 0.5 - 0.5 * c,
+            FirWindow::Hamming => 0.54 - 0.46 * c,
+            FirWindow::Blackman => 0.42 - 0.5 * c + 0.08 * (4.0 * PI * x).cos(),
+        }
+    }
+}
+
+/// Options for rendering an impulse response.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FirOptions {
+    /// Sample rate in Hz.
+    pub sample_rate: u32,
+    /// Length of the impulse in samples.
+    pub length: usize,
+    /// Minimum or linear phase.
+    pub phase: FirPhase,
+    /// Window applied after truncation.
+    pub window: FirWindow,
+    /// Render crossfeed as a true-stereo impulse; see [`render_impulse`].
+    pub true_stereo: bool,
+}
+
+impl Default for FirOptions {
+    fn default() -> Self {
+        Self {
+            sample_rate: 48000,
+            length: 16384,
+            phase: FirPhase::Minimum,
+            window: FirWindow::Hann,
+            true_stereo: false,
+        }
+    }
+}
+
+impl FirOptions {
+    /// Checks the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample rate is outside 8–384 kHz or the
+    /// length is outside [`MIN_FIR_LENGTH`]..=[`MAX_FIR_LENGTH`].
+    pub fn validate(&self) -> Result<(), String> {
+        if !(8000..=384_000).contains(&self.sample_rate) {
+            return Err(format!(
+                "Sample rate must be between 8000 and 384000 Hz, got {}",
+                self.sample_rate
+            ));
+        }
+        if !(MIN_FIR_LENGTH..=MAX_FIR_LENGTH).contains(&self.length) {
+            return Err(format!(
+                "Length must be between {} and {} samples, got {}",
+                MIN_FIR_LENGTH, MAX_FIR_LENGTH, self.length
+            ));
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// FFT
+// =============================================================================
+
+/// A complex number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+
+    fn exp(self) -> Complex {
+        let scale = self.re.exp();
+        Complex::new(scale * self.im.cos(), scale * self.im.sin())
+    }
+}
+
+/// In-place iterative radix-2 FFT. `data.len()` must be a power of two.
+/// The inverse transform is scaled by `1 / n`.
+fn fft(data: &mut [Complex], inverse: bool) {
+    let n = data.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f64;
+        let step = Complex::new(angle.cos(), angle.sin());
+        for start in (0..n).step_by(len) {
+            let mut w = Complex::new(1.0, 0.0);
+            for k in 0..len / 2 {
+                let a = data[start + k];
+                let b = data[start + k + len / 2].mul(w);
+                data[start + k] = Complex::new(a.re + b.re, a.im + b.im);
+                data[start + k + len / 2] = Complex::new(a.re - b.re, a.im - b.im);
+                w = w.mul(step);
+            }
+        }
+        len <<= 1;
+    }
+
+    if inverse {
+        let scale = 1.0 / n as f64;
+        for value in data.iter_mut() {
+            value.re *= scale;
+            value.im *= scale;
+        }
+    }
+}
+
+// =============================================================================
+// Design
+// =============================================================================
+
+/// Returns the frequencies of the `n / 2 + 1` non-negative FFT bins.
+fn bin_frequencies(n: usize, sample_rate: f64) -> Vec<f64> {
+    (0..=n / 2)
+        .map(|k| k as f64 * sample_rate / n as f64)
+        .collect()
+}
+
+/// Returns the response of the profile's bands, GraphicEQ curve and preamp
+/// in dB at each frequency.
+fn profile_db(profile: &EqProfile, freqs: &[f64], sample_rate: f64) -> Vec<f64> {
+    dsp::response_db(
+        &profile.bands,
+        &profile.graphic_eq,
+        profile.preamp,
+        freqs,
+        sample_rate,
+    )
+}
+
+/// Converts a response in dB to linear magnitudes.
+fn to_linear(db: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    db.into_iter().map(|db| 10f64.powf(db / 20.0)).collect()
+}
+
+/// Expands non-negative bins to a full, conjugate-symmetric spectrum.
+fn full_spectrum(half: &[Complex], n: usize) -> Vec<Complex> {
+    let mut spectrum = vec![Complex::new(0.0, 0.0); n];
+    spectrum[..half.len()].copy_from_slice(half);
+    for k in 1..n / 2 {
+        spectrum[n - k] = Complex::new(half[k].re, -half[k].im);
+    }
+    spectrum
+}
+
+/// Returns the FFT size used for an impulse of the given length.
+fn fft_size(length: usize) -> usize {
+    (length * 4).next_power_of_two().max(MIN_FFT_SIZE)
+}
+
+/// Turns the magnitudes of the `n / 2 + 1` non-negative bins into an
+/// impulse of `options.length` samples.
+fn impulse_from_magnitudes(magnitudes: &[f64], n: usize, options: &FirOptions) -> Vec<f64> {
+    let length = options.length;
+    match options.phase {
+        FirPhase::Linear => {
+            let half: Vec<Complex> = magnitudes.iter().map(|&m| Complex::new(m, 0.0)).collect();
+            let mut spectrum = full_spectrum(&half, n);
+            fft(&mut spectrum, true);
+
+            // Zero-phase impulse is centered on sample 0; rotate to the middle
+            let center = length / 2;
+            (0..length)
+                .map(|i| {
+                    let x = 0.5 + (i as f64 - center as f64) / length as f64;
+                    spectrum[(i + n - center) % n].re * options.window.value(x)
+                })
+                .collect()
+        }
+        FirPhase::Minimum => {
+            let half: Vec<Complex> = magnitudes
+                .iter()
+                .map(|&m| Complex::new(m.max(1e-10).ln(), 0.0))
+                .collect();
+            let mut cepstrum = full_spectrum(&half, n);
+            fft(&mut cepstrum, true);
+
+            // Fold the cepstrum onto positive quefrencies
+            for (i, value) in cepstrum.iter_mut().enumerate() {
+                let weight = match i {
+                    0 => 1.0,
+                    i if i < n / 2 => 2.0,
+                    i if i == n / 2 => 1.0,
+                    _ => 0.0,
+                };
+                value.re *= weight;
+                value.im = 0.0;
+            }
+            fft(&mut cepstrum, false);
+            let mut spectrum: Vec<Complex> = cepstrum.into_iter().map(Complex::exp).collect();
+            fft(&mut spectrum, true);
+
+            (0..length)
+                .map(|i| {
+                    spectrum[i].re * options.window.value(0.5 + 0.5 * i as f64 / length as f64)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Renders a profile as a mono impulse response.
+///
+/// Crossfeed is not included; see [`render_impulse`].
+///
+/// # Errors
+///
+/// Returns an error if the options are invalid.
+pub fn design_fir(profile: &EqProfile, options: &FirOptions) -> Result<Vec<f32>, String> {
+    options.validate()?;
+    let n = fft_size(options.length);
+    let sample_rate = options.sample_rate as f64;
+    let magnitudes = to_linear(profile_db(
+        profile,
+        &bin_frequencies(n, sample_rate),
+        sample_rate,
+    ));
+
+    Ok(impulse_from_magnitudes(&magnitudes, n, options)
+        .into_iter()
+        .map(|s| s as f32)
+        .collect())
+}
+
+/// Renders a profile as interleaved impulse channels for export.
+///
+/// Returns one channel, or with crossfeed and [`FirOptions::true_stereo`]
+/// four: left to left, left to right, right to left and right to right.
+/// The same-ear paths carry the direct response and the opposite-ear paths
+/// the crossfed one. The minimum-phase design reproduces the low-pass delay
+/// of the crossfed path by itself, so only the extra delay is added; linear
+/// phase removes it, so the full path delay is added back.
+///
+/// # Returns
+///
+/// The interleaved samples and the channel count.
+///
+/// # Errors
+///
+/// Returns an error if the profile has mid/side processing, has crossfeed
+/// but `true_stereo` is not set, or if the options or crossfeed parameters
+/// are invalid.
+pub fn render_impulse(
+    profile: &EqProfile,
+    options: &FirOptions,
+) -> Result<(Vec<f32>, u16), String> {
+    if profile.mid_side.is_some() {
+        return Err(
+            "Profiles with mid/side processing cannot be exported as an impulse \
+             response, since its bands and width act on the sum and difference \
+             of the channels"
+                .to_string(),
+        );
+    }
+    let Some(crossfeed) = &profile.crossfeed else {
+        return Ok((design_fir(profile, options)?, 1));
+    };
+    if !options.true_stereo {
+        return Err(
+            "Profiles with crossfeed can only be exported as a true-stereo impulse \
+             response, since the crossfed path mixes the channels"
+                .to_string(),
+        );
+    }
+    options.validate()?;
+    crossfeed.validate()?;
+
+    let n = fft_size(options.length);
+    let sample_rate = options.sample_rate as f64;
+    let freqs = bin_frequencies(n, sample_rate);
+    let base = profile_db(profile, &freqs, sample_rate);
+    let (direct, cross) = crossfeed.response_db(&freqs, sample_rate);
+
+    let same_ear = impulse_from_magnitudes(
+        &to_linear(base.iter().zip(&direct).map(|(b, d)| b + d)),
+        n,
+        options,
+    );
+    let opposite_ear = impulse_from_magnitudes(
+        &to_linear(base.iter().zip(&cross).map(|(b, c)| b + c)),
+        n,
+        options,
+    );
+
+    let delay = match options.phase {
+        FirPhase::Minimum => f64::from(crossfeed.delay_ms) / 1000.0,
+        FirPhase::Linear => crossfeed.delay_seconds(),
+    };
+    let shift = (delay * sample_rate).round() as usize;
+
+    let samples = (0..options.length)
+        .flat_map(|i| {
+            let same = same_ear[i] as f32;
+            let opposite = i.checked_sub(shift).map_or(0.0, |j| opposite_ear[j]) as f32;
+            [same, opposite, opposite, same]
+        })
+        .collect();
+    Ok((samples, 4))
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Renders a saved profile as a WAV impulse response.
+///
+/// The file is mono, or true stereo for profiles with crossfeed when
+/// requested in the options; see [`render_impulse`].
+///
+/// # Arguments
+///
+/// * `name` - The profile to render
+/// * `path` - Destination `.wav` file
+/// * `options` - Sample rate, length, phase, window and true-stereo
+///   rendering; defaults to [`FirOptions::default`]
+///
+/// # Errors
+///
+/// Returns an error if the profile cannot be loaded, has mid/side
+/// processing or crossfeed without true-stereo rendering, the options are invalid, or the file cannot be
+/// written.
+#[tauri::command]
+pub fn export_impulse_response(
+    name: String,
+    path: String,
+    options: Option<FirOptions>,
+) -> Result<(), String> {
+    let profile = load_profile(name)?;
+    let options = options.unwrap_or_default();
+    let (impulse, channels) = render_impulse(&profile, &options)?;
+    wav::write_float(Path::new(&path), &impulse, channels, options.sample_rate)
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crossfeed::{Crossfeed, CrossfeedPreset};
+    use crate::mid_side::MidSide;
+    use crate::types::{FilterType, ParametricBand};
+
+    fn peak_profile() -> EqProfile {
+        EqProfile {
+            name: "Peak".to_string(),
+            preamp: -3.0,
+            bands: vec![ParametricBand {
+                filter_type: FilterType::Peaking,
+                frequency: 1000.0,
+                gain: 6.0,
+                q_factor: 1.0,
+                enabled: true,
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Magnitude in dB of an impulse at `freq` Hz.
+    fn impulse_db(impulse: &[f32], freq: f64, sample_rate: f64) -> f64 {
+        let w = 2.0 * PI * freq / sample_rate;
+        let (re, im) = impulse
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (i, &s)| {
+                let phase = w * i as f64;
+                (re + s as f64 * phase.cos(), im - s as f64 * phase.sin())
+            });
+        10.0 * (re * re + im * im).log10()
+    }
+
+    #[test]
+    fn fft_round_trips() {
+        let original: Vec<Complex> = (0..8).map(|i| Complex::new(i as f64, 0.0)).collect();
+        let mut data = original.clone();
+        fft(&mut data, false);
+        assert!((data[0].re - 28.0).abs() < 1e-9);
+        fft(&mut data, true);
+        for (a, b) in data.iter().zip(&original) {
+            assert!((a.re - b.re).abs() < 1e-9 && a.im.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn flat_profile_is_an_impulse() {
+        let options = FirOptions {
+            length: 256,
+            window: FirWindow::Rectangular,
+            ..Default::default()
+        };
+        let impulse = design_fir(&EqProfile::default(), &options).unwrap();
+        assert!((impulse[0] - 1.0).abs() < 1e-6);
+        assert!(impulse[1..].iter().all(|s| s.abs() < 1e-6));
+    }
+
+    #[test]
+    fn minimum_phase_matches_response() {
+        let profile = peak_profile();
+        let options = FirOptions::default();
+        let impulse = design_fir(&profile, &options).unwrap();
+        let sr = options.sample_rate as f64;
+        for freq in [100.0, 1000.0, 5000.0] {
+            let expected = dsp::response_db(&profile.bands, &[], profile.preamp, &[freq], sr)[0];
+            let actual = impulse_db(&impulse, freq, sr);
+            assert!(
+                (actual - expected).abs() < 0.1,
+                "{} Hz: {} vs {}",
+                freq,
+                actual,
+                expected
+            );
+        }
+        // Energy is concentrated at the start
+        let peak = impulse
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .unwrap()
+            .0;
+        assert!(peak < 16);
+    }
+
+    #[test]
+    fn linear_phase_is_symmetric() {
+        let profile = peak_profile();
+        let options = FirOptions {
+            length: 4096,
+            phase: FirPhase::Linear,
+            window: FirWindow::Blackman,
+            ..Default::default()
+        };
+        let impulse = design_fir(&profile, &options).unwrap();
+        let center = options.length / 2;
+        for k in 1..200 {
+            assert!((impulse[center + k] - impulse[center - k]).abs() < 1e-6);
+        }
+        let actual = impulse_db(&impulse, 1000.0, 48000.0);
+        assert!((actual - 3.0).abs() < 0.2, "{}", actual);
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let profile = EqProfile::default();
+        for options in [
+            FirOptions {
+                length: 10,
+                ..Default::default()
+            },
+            FirOptions {
+                sample_rate: 1000,
+                ..Default::default()
+            },
+        ] {
+            assert!(design_fir(&profile, &options).is_err());
+        }
+    }
+
+    #[test]
+    fn crossfeed_needs_true_stereo() {
+        let profile = EqProfile {
+            crossfeed: Some(CrossfeedPreset::ChuMoy.crossfeed()),
+            ..peak_profile()
+        };
+        let err = render_impulse(&profile, &FirOptions::default()).unwrap_err();
+        assert!(err.contains("true-stereo"));
+
+        // Without crossfeed the render stays mono
+        let options = FirOptions::default();
+        let (mono, channels) = render_impulse(&peak_profile(), &options).unwrap();
+        assert_eq!(channels, 1);
+        assert_eq!(mono, design_fir(&peak_profile(), &options).unwrap());
+    }
+
+    #[test]
+    fn refuses_mid_side() {
+        let profile = EqProfile {
+            mid_side: Some(MidSide::default()),
+            ..peak_profile()
+        };
+        let options = FirOptions {
+            true_stereo: true,
+            ..Default::default()
+        };
+        let err = render_impulse(&profile, &options).unwrap_err();
+        assert!(err.contains("mid/side"));
+    }
+
+    #[test]
+    fn crossfeed_renders_true_stereo() {
+        let profile = EqProfile {
+            crossfeed: Some(CrossfeedPreset::ChuMoy.crossfeed()),
+            ..peak_profile()
+        };
+        let options = FirOptions {
+            true_stereo: true,
+            ..Default::default()
+        };
+        let (samples, channels) = render_impulse(&profile, &options).unwrap();
+        assert_eq!(channels, 4);
+        assert_eq!(samples.len(), options.length * 4);
+
+        let channel =
+            |c: usize| -> Vec<f32> { samples.iter().skip(c).step_by(4).copied().collect() };
+        let (same, opposite) = (channel(0), channel(1));
+        assert_eq!(channel(2), opposite);
+        assert_eq!(channel(3), same);
+
+        let sr = options.sample_rate as f64;
+        let crossfeed = profile.crossfeed.unwrap();
+        let (direct, cross) = crossfeed.response_db(&[100.0, 5000.0], sr);
+        let base = profile_db(&profile, &[100.0, 5000.0], sr);
+        for (i, freq) in [100.0, 5000.0].into_iter().enumerate() {
+            assert!((impulse_db(&same, freq, sr) - (base[i] + direct[i])).abs() < 0.1);
+            assert!((impulse_db(&opposite, freq, sr) - (base[i] + cross[i])).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn linear_phase_crossfeed_is_delayed() {
+        let profile = EqProfile {
+            crossfeed: Some(Crossfeed::default()),
+            ..Default::default()
+        };
+        let options = FirOptions {
+            length: 4096,
+            phase: FirPhase::Linear,
+            true_stereo: true,
+            ..Default::default()
+        };
+        let (samples, _) = render_impulse(&profile, &options).unwrap();
+        let peak = |offset: usize| {
+            (0..options.length)
+                .max_by(|&a, &b| {
+                    samples[a * 4 + offset]
+                        .abs()
+                        .total_cmp(&samples[b * 4 + offset].abs())
+                })
+                .unwrap()
+        };
+        let shift = (Crossfeed::default().delay_seconds() * 48000.0).round() as usize;
+        assert_eq!(peak(0), options.length / 2);
+        assert_eq!(peak(1), options.length / 2 + shift);
+    }
+}
@@ -0,0 +1,640 @@
+//! Conversion between GraphicEQ curves and parametric bands.
+//!
+//! [`fit_bands`] approximates a target curve (e.g. an AutoEQ GraphicEQ
+//! correction) with a limited number of editable [`ParametricBand`]s, and
+//! [`rasterize`] goes the other way, sampling the response of parametric
+//! bands onto GraphicEQ points.
+//!
+//! # Fitting
+//!
+//! Bands are added greedily: each step places a filter where the remaining
+//! error is largest (a peaking filter, or a shelf near either end of the
+//! range if shelves are allowed), tunes it against the residual, and then
+//! re-tunes all bands together with a coordinate search over log frequency,
+//! gain and log Q. Fitting stops early once another band no longer helps.
+//!
+//! The error is measured in dB on a log-spaced grid between
+//! [`FitConfig::min_frequency`] and [`FitConfig::max_frequency`], so every
+//! octave counts equally.
+
+use serde::{Deserialize, Serialize};
+
+use crate::dsp::{self, Biquad, DEFAULT_SAMPLE_RATE};
+use crate::graphic_eq::{self, GraphicEqPreset};
+use crate::types::{FilterType, GraphicEqPoint, ParametricBand};
+
+/// Maximum number of bands [`fit_bands`] will place.
+pub const MAX_FIT_BANDS: usize = 20;
+
+/// Number of points used by [`rasterize_grid`] when no preset is given,
+/// matching the density of AutoEQ's GraphicEQ files.
+pub const DEFAULT_GRAPHIC_POINTS: usize = 127;
+
+/// Maximum number of points [`rasterize_grid`] generates.
+pub const MAX_GRAPHIC_POINTS: usize = 1000;
+
+/// Number of grid points the fitting error is evaluated on.
+const FIT_POINTS: usize = 256;
+
+/// Full coordinate-search passes over all bands after each band is added.
+const MAX_REFINE_PASSES: usize = 60;
+
+/// Constraints for [`fit_bands`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FitConfig {
+    /// Maximum number of bands to use (1–[`MAX_FIT_BANDS`]).
+    pub num_bands: usize,
+    /// Lowest frequency that is fitted and where bands may be placed, in Hz.
+    pub min_frequency: f32,
+    /// Highest frequency that is fitted and where bands may be placed, in Hz.
+    pub max_frequency: f32,
+    /// Lowest allowed Q.
+    pub min_q: f32,
+    /// Highest allowed Q.
+    pub max_q: f32,
+    /// Largest allowed boost or cut per band, in dB.
+    pub max_gain: f32,
+    /// Whether low and high shelf filters may be used.
+    pub allow_shelves: bool,
+    /// Sample rate the filters are designed for, in Hz.
+    pub sample_rate: f64,
+}
+
+impl Default for FitConfig {
+    fn default() -> Self {
+        Self {
+            num_bands: 10,
+            min_frequency: 20.0,
+            max_frequency: 16000.0,
+            min_q: 0.18,
+            max_q: 6.0,
+            max_gain: 12.0,
+            allow_shelves: true,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+        }
+    }
+}
+
+impl FitConfig {
+    /// Checks that the constraints are usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first invalid constraint.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_FIT_BANDS).contains(&self.num_bands) {
+            return Err(format!(
+                "Number of bands must be between 1 and {}, got {}",
+                MAX_FIT_BANDS, self.num_bands
+            ));
+        }
+        let nyquist = self.sample_rate / 2.0;
+        if !(self.sample_rate.is_finite() && self.sample_rate > 0.0) {
+            return Err(format!("Invalid sample rate {}", self.sample_rate));
+        }
+        if !(self.min_frequency > 0.0
+            && self.min_frequency < self.max_frequency
+            && (self.max_frequency as f64) < nyquist)
+        {
+            return Err(format!(
+                "Frequency range must satisfy 0 < min < max < {} Hz, got {}–{} Hz",
+                nyquist, self.min_frequency, self.max_frequency
+            ));
+        }
+        if !(self.min_q > 0.0 && self.min_q <= self.max_q && self.max_q.is_finite()) {
+            return Err(format!(
+                "Q range must satisfy 0 < min <= max, got {}–{}",
+                self.min_q, self.max_q
+            ));
+        }
+        if !(self.max_gain > 0.0 && self.max_gain.is_finite()) {
+            return Err(format!(
+                "Maximum gain must be positive, got {}",
+                self.max_gain
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Result of [`fit_bands`].
+#[derive(Debug, Clone, Serialize)]
+pub struct FitResult {
+    /// Fitted bands, sorted by frequency. May be fewer than requested if
+    /// additional bands would not reduce the error.
+    pub bands: Vec<ParametricBand>,
+    /// Preamp that keeps the fitted response at or below 0 dB.
+    pub preamp: f32,
+    /// RMS difference between the target and the fitted response, in dB.
+    pub rms_error: f32,
+    /// Largest absolute difference between the target and the fitted
+    /// response, in dB.
+    pub max_error: f32,
+}
+
+// =============================================================================
+// Fitting
+// =============================================================================
+
+/// A band being fitted, in the coordinates the search works in.
+#[derive(Debug, Clone)]
+struct Candidate {
+    filter_type: FilterType,
+    log_freq: f64,
+    gain: f64,
+    log_q: f64,
+}
+
+impl Candidate {
+    fn to_band(&self) -> ParametricBand {
+        ParametricBand {
+            filter_type: self.filter_type.clone(),
+            frequency: 10f64.powf(self.log_freq) as f32,
+            gain: self.gain as f32,
+            q_factor: 10f64.powf(self.log_q) as f32,
+            enabled: true,
+        }
+    }
+}
+
+/// The fitting problem: target on a grid plus the constraints.
+struct Fitter<'a> {
+    config: &'a FitConfig,
+    freqs: Vec<f64>,
+    target: Vec<f64>,
+}
+
+impl Fitter<'_> {
+    fn response(&self, candidate: &Candidate) -> Vec<f64> {
+        let filter = Biquad::from_band(&candidate.to_band(), self.config.sample_rate);
+        self.freqs
+            .iter()
+            .map(|&f| filter.magnitude_db(f, self.config.sample_rate))
+            .collect()
+    }
+
+    /// Sum of squared errors of `base + response` against the target.
+    fn error(&self, base: &[f64], response: &[f64]) -> f64 {
+        self.target
+            .iter()
+            .zip(base)
+            .zip(response)
+            .map(|((t, b), r)| (t - b - r).powi(2))
+            .sum()
+    }
+
+    /// Clamps a candidate to the configured limits.
+    fn clamp(&self, mut candidate: Candidate) -> Candidate {
+        let c = self.config;
+        candidate.log_freq = candidate.log_freq.clamp(
+            (c.min_frequency as f64).log10(),
+            (c.max_frequency as f64).log10(),
+        );
+        candidate.gain = candidate
+            .gain
+            .clamp(-(c.max_gain as f64), c.max_gain as f64);
+        candidate.log_q = candidate
+            .log_q
+            .clamp((c.min_q as f64).log10(), (c.max_q as f64).log10());
+        candidate
+    }
+
+    /// Tunes one band against `base` (the response of all other bands) by
+    /// coordinate search. Returns the tuned band, its response and the error.
+    fn tune(&self, start: Candidate, base: &[f64]) -> (Candidate, Vec<f64>, f64) {
+        let mut best = self.clamp(start);
+        let mut best_response = self.response(&best);
+        let mut best_error = self.error(base, &best_response);
+        // Steps for log frequency (decades), gain (dB) and log Q
+        let mut steps = [0.05, 1.0, 0.1];
+
+        while steps[1] > 0.01 {
+            let mut improved = false;
+            for (dim, &step) in steps.iter().enumerate() {
+                for sign in [1.0, -1.0] {
+                    let mut trial = best.clone();
+                    match dim {
+                        0 => trial.log_freq += sign * step,
+                        1 => trial.gain += sign * step,
+                        _ => trial.log_q += sign * step,
+                    }
+                    let trial = self.clamp(trial);
+                    let response = self.response(&trial);
+                    let error = self.error(base, &response);
+                    if error < best_error - 1e-12 {
+                        (best, best_response, best_error) = (trial, response, error);
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                steps.iter_mut().for_each(|s| *s /= 2.0);
+            }
+        }
+        (best, best_response, best_error)
+    }
+
+    /// Re-tunes every band in turn until no band improves.
+    fn refine(&self, bands: &mut [Candidate], responses: &mut [Vec<f64>]) {
+        for _ in 0..MAX_REFINE_PASSES {
+            let mut improved = false;
+            for i in 0..bands.len() {
+                let base = sum_except(responses, Some(i), self.freqs.len());
+                let current = self.error(&base, &responses[i]);
+                let (band, response, error) = self.tune(bands[i].clone(), &base);
+                if error < current - 1e-9 {
+                    bands[i] = band;
+                    responses[i] = response;
+                    improved = true;
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+    }
+
+    /// Starting points for the next band, given the current residual.
+    fn candidates(&self, residual: &[f64]) -> Vec<Candidate> {
+        let (index, &gain) = residual
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .expect("fit grid is never empty");
+
+        let mut candidates = vec![Candidate {
+            filter_type: FilterType::Peaking,
+            log_freq: self.freqs[index].log10(),
+            gain,
+            log_q: 2f64.sqrt().log10(),
+        }];
+
+        if self.config.allow_shelves {
+            let shelf_q = std::f64::consts::FRAC_1_SQRT_2.log10();
+            let edge = residual.len() / 8;
+            candidates.push(Candidate {
+                filter_type: FilterType::LowShelf,
+                log_freq: self.freqs[edge].log10(),
+                gain: residual[..=edge].iter().sum::<f64>() / (edge + 1) as f64,
+                log_q: shelf_q,
+            });
+            candidates.push(Candidate {
+                filter_type: FilterType::HighShelf,
+                log_freq: self.freqs[residual.len() - 1 - edge].log10(),
+                gain: residual[residual.len() - 1 - edge..].iter().sum::<f64>() / (edge + 1) as f64,
+                log_q: shelf_q,
+            });
+        }
+        candidates
+    }
+}
+
+/// Returns the element-wise sum of all responses except `skip`.
+fn sum_except(responses: &[Vec<f64>], skip: Option<usize>, len: usize) -> Vec<f64> {
+    let mut total = vec![0.0; len];
+    for (i, response) in responses.iter().enumerate() {
+        if Some(i) != skip {
+            total.iter_mut().zip(response).for_each(|(t, r)| *t += r);
+        }
+    }
+    total
+}
+
+/// Rounds a value to the given number of decimal places.
+fn round_to(value: f32, decimals: i32) -> f32 {
+    let factor = 10f32.powi(decimals);
+    (value * factor).round() / factor
+}
+
+/// Approximates a target curve with up to `config.num_bands` parametric bands.
+///
+/// The target is interpolated the same way EqualizerAPO interpolates
+/// GraphicEQ points. Fitted values are rounded to 0.1 Hz, 0.01 dB and
+/// 0.01 Q, and the reported errors are for the rounded bands.
+///
+/// # Errors
+///
+/// Returns an error if the target is empty or invalid, or the configuration
+/// is invalid.
+pub fn fit_bands(target: &[GraphicEqPoint], config: &FitConfig) -> Result<FitResult, String> {
+    config.validate()?;
+    graphic_eq::validate_points(target)?;
+    if target.is_empty() {
+        return Err("Target curve has no points".to_string());
+    }
+
+    let freqs = dsp::log_spaced(
+        FIT_POINTS,
+        config.min_frequency as f64,
+        config.max_frequency as f64,
+    );
+    let fitter = Fitter {
+        config,
+        target: freqs
+            .iter()
+            .map(|&f| graphic_eq::gain_at(target, f))
+            .collect(),
+        freqs,
+    };
+
+    let len = fitter.freqs.len();
+    let zeros = vec![0.0; len];
+    let mut bands: Vec<Candidate> = Vec::new();
+    let mut responses: Vec<Vec<f64>> = Vec::new();
+    let mut error = fitter.error(&zeros, &zeros);
+
+    while bands.len() < config.num_bands {
+        let base = sum_except(&responses, None, len);
+        let residual: Vec<f64> = fitter
+            .target
+            .iter()
+            .zip(&base)
+            .map(|(t, b)| t - b)
+            .collect();
+
+        let Some((band, response, new_error)) = fitter
+            .candidates(&residual)
+            .into_iter()
+            .map(|start| fitter.tune(start, &base))
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+        else {
+            break;
+        };
+        // Stop once a band improves the fit by less than 0.1% of the error
+        if new_error >= error * 0.999 {
+            break;
+        }
+
+        bands.push(band);
+        responses.push(response);
+        fitter.refine(&mut bands, &mut responses);
+        error = fitter.error(&sum_except(&responses, None, len), &zeros);
+    }
+
+    let mut bands: Vec<ParametricBand> = bands
+        .into_iter()
+        .map(|c| {
+            let band = c.to_band();
+            ParametricBand {
+                frequency: round_to(band.frequency, 1),
+                gain: round_to(band.gain, 2),
+                q_factor: round_to(band.q_factor, 2),
+                ..band
+            }
+        })
+        .filter(|band| band.gain != 0.0)
+        .collect();
+    bands.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+
+    let fitted = dsp::response_db(&bands, &[], 0.0, &fitter.freqs, config.sample_rate);
+    let diffs: Vec<f64> = fitter
+        .target
+        .iter()
+        .zip(&fitted)
+        .map(|(t, f)| t - f)
+        .collect();
+    let rms_error = (diffs.iter().map(|d| d * d).sum::<f64>() / len as f64).sqrt();
+    let max_error = diffs.iter().fold(0.0f64, |m, d| m.max(d.abs()));
+    let peak = dsp::peak_gain_db(&bands, &[], 0.0, config.sample_rate);
+
+    Ok(FitResult {
+        bands,
+        preamp: if peak > 0.0 { -peak } else { 0.0 },
+        rms_error: round_to(rms_error as f32, 3),
+        max_error: round_to(max_error as f32, 3),
+    })
+}
+
+// =============================================================================
+// Rasterization
+// =============================================================================
+
+/// Returns the frequencies to rasterize onto: the preset's bands if given,
+/// otherwise `points` (default [`DEFAULT_GRAPHIC_POINTS`]) log-spaced
+/// frequencies from 20 Hz to 20 kHz.
+///
+/// # Errors
+///
+/// Returns an error if `points` is less than 2 or more than
+/// [`MAX_GRAPHIC_POINTS`].
+pub fn rasterize_grid(
+    preset: Option<GraphicEqPreset>,
+    points: Option<usize>,
+) -> Result<Vec<f32>, String> {
+    if let Some(preset) = preset {
+        return Ok(preset.frequencies().to_vec());
+    }
+    let points = points.unwrap_or(DEFAULT_GRAPHIC_POINTS);
+    if !(2..=MAX_GRAPHIC_POINTS).contains(&points) {
+        return Err(format!(
+            "Number of points must be between 2 and {}, got {}",
+            MAX_GRAPHIC_POINTS, points
+        ));
+    }
+    Ok(dsp::log_spaced(points, dsp::FREQ_MIN, dsp::FREQ_MAX)
+        .into_iter()
+        .map(|f| round_to(f as f32, 1))
+        .collect())
+}
+
+/// Samples the response of `bands` (without preamp) at each frequency.
+///
+/// Gains are rounded to 0.01 dB. Disabled bands are ignored.
+pub fn rasterize(
+    bands: &[ParametricBand],
+    frequencies: &[f32],
+    sample_rate: f64,
+) -> Vec<GraphicEqPoint> {
+    let freqs: Vec<f64> = frequencies.iter().map(|&f| f as f64).collect();
+    dsp::response_db(bands, &[], 0.0, &freqs, sample_rate)
+        .into_iter()
+        .zip(frequencies)
+        .map(|(gain, &frequency)| GraphicEqPoint {
+            frequency,
+            gain: round_to(gain as f32, 2),
+        })
+        .collect()
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Approximates a GraphicEQ curve with parametric bands.
+///
+/// # Arguments
+///
+/// * `target` - Curve to approximate
+/// * `config` - Band count and limits; defaults to [`FitConfig::default`]
+///
+/// # Errors
+///
+/// Returns an error if the target or configuration is invalid.
+#[tauri::command]
+pub fn fit_parametric_eq(
+    target: Vec<GraphicEqPoint>,
+    config: Option<FitConfig>,
+) -> Result<FitResult, String> {
+    fit_bands(&target, &config.unwrap_or_default())
+}
+
+/// Converts parametric bands to a GraphicEQ curve.
+///
+/// # Arguments
+///
+/// * `bands` - Bands to convert
+/// * `preset` - ISO layout to sample at; takes precedence over `points`
+/// * `points` - Number of log-spaced points (default [`DEFAULT_GRAPHIC_POINTS`])
+/// * `sample_rate` - Sample rate in Hz (default [`DEFAULT_SAMPLE_RATE`])
+///
+/// # Errors
+///
+/// Returns an error if `points` is out of range.
+#[tauri::command]
+pub fn parametric_to_graphic_eq(
+    bands: Vec<ParametricBand>,
+    preset: Option<GraphicEqPreset>,
+    points: Option<usize>,
+    sample_rate: Option<f64>,
+) -> Result<Vec<GraphicEqPoint>, String> {
+    let frequencies = rasterize_grid(preset, points)?;
+    Ok(rasterize(
+        &bands,
+        &frequencies,
+        sample_rate.unwrap_or(DEFAULT_SAMPLE_RATE),
+    ))
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_band;
+
+    fn target_from(bands: &[ParametricBand]) -> Vec<GraphicEqPoint> {
+        let grid = rasterize_grid(None, Some(400)).unwrap();
+        rasterize(bands, &grid, DEFAULT_SAMPLE_RATE)
+    }
+
+    #[test]
+    fn fits_a_single_peak() {
+        let target = target_from(&[test_band(FilterType::Peaking, 1000.0, 6.0, 2.0)]);
+        let config = FitConfig {
+            num_bands: 1,
+            ..Default::default()
+        };
+        let result = fit_bands(&target, &config).unwrap();
+
+        assert_eq!(result.bands.len(), 1);
+        let fitted = &result.bands[0];
+        assert_eq!(fitted.filter_type, FilterType::Peaking);
+        assert!((fitted.frequency - 1000.0).abs() < 30.0, "{:?}", fitted);
+        assert!((fitted.gain - 6.0).abs() < 0.3, "{:?}", fitted);
+        assert!(result.rms_error < 0.2, "{:?}", result);
+        assert!((result.preamp + 6.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn fits_shelf_and_peak_with_two_bands() {
+        let target = target_from(&[
+            test_band(FilterType::LowShelf, 105.0, 5.0, 0.7),
+            test_band(FilterType::Peaking, 3000.0, -4.0, 1.5),
+        ]);
+        let config = FitConfig {
+            num_bands: 2,
+            ..Default::default()
+        };
+        let result = fit_bands(&target, &config).unwrap();
+        assert!(result.rms_error < 0.5, "{:?}", result);
+        assert!(result.max_error < 1.5, "{:?}", result);
+    }
+
+    #[test]
+    fn flat_target_needs_no_bands() {
+        let target = GraphicEqPreset::Iso31.flat();
+        let result = fit_bands(&target, &FitConfig::default()).unwrap();
+        assert!(result.bands.is_empty());
+        assert_eq!(result.rms_error, 0.0);
+        assert_eq!(result.preamp, 0.0);
+    }
+
+    #[test]
+    fn respects_gain_and_q_limits() {
+        let target = target_from(&[test_band(FilterType::Peaking, 500.0, 15.0, 8.0)]);
+        let config = FitConfig {
+            num_bands: 3,
+            max_gain: 6.0,
+            max_q: 2.0,
+            allow_shelves: false,
+            ..Default::default()
+        };
+        let result = fit_bands(&target, &config).unwrap();
+        for band in &result.bands {
+            assert_eq!(band.filter_type, FilterType::Peaking);
+            assert!(band.gain.abs() <= 6.0, "{:?}", band);
+            assert!(band.q_factor <= 2.0 && band.q_factor >= config.min_q);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let target = GraphicEqPreset::Iso10.flat();
+        let bad = [
+            FitConfig {
+                num_bands: 0,
+                ..Default::default()
+            },
+            FitConfig {
+                min_frequency: 1000.0,
+                max_frequency: 100.0,
+                ..Default::default()
+            },
+            FitConfig {
+                min_q: 2.0,
+                max_q: 1.0,
+                ..Default::default()
+            },
+            FitConfig {
+                max_frequency: 30000.0,
+                ..Default::default()
+            },
+        ];
+        for config in bad {
+            assert!(fit_bands(&target, &config).is_err(), "{:?}", config);
+        }
+        assert!(fit_bands(&[], &FitConfig::default()).is_err());
+    }
+
+    #[test]
+    fn rasterize_samples_band_response() {
+        let bands = [test_band(FilterType::Peaking, 1000.0, 6.0, 1.41)];
+        let points = rasterize(
+            &bands,
+            GraphicEqPreset::Iso10.frequencies(),
+            DEFAULT_SAMPLE_RATE,
+        );
+        assert_eq!(points.len(), 10);
+        assert_eq!(points[5].frequency, 1000.0);
+        assert!((points[5].gain - 6.0).abs() < 0.01);
+        assert!(points[0].gain.abs() < 0.1);
+    }
+
+    #[test]
+    fn rasterize_grid_bounds() {
+        let grid = rasterize_grid(None, None).unwrap();
+        assert_eq!(grid.len(), DEFAULT_GRAPHIC_POINTS);
+        assert_eq!(grid[0], 20.0);
+        assert_eq!(*grid.last().unwrap(), 20000.0);
+        assert!(rasterize_grid(None, Some(1)).is_err());
+        assert_eq!(
+            rasterize_grid(Some(GraphicEqPreset::Iso15), Some(1))
+                .unwrap()
+                .len(),
+            15
+        );
+    }
+}
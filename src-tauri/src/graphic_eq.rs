@@ -0,0 +1,202 @@
+//! GraphicEQ curves: ISO band presets, interpolation and config output.
+//!
+//! A GraphicEQ curve is a list of [`GraphicEqPoint`]s written to
+//! EqualizerAPO as a single line:
+//!
+//! ```text
+//! GraphicEQ: 31.5 0; 63 2.5; 125 1; ...
+//! ```
+//!
+//! EqualizerAPO interpolates linearly in dB over log frequency between the
+//! points and holds the first and last gain beyond the ends of the curve;
+//! [`gain_at`] does the same so that the predicted response matches.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::GraphicEqPoint;
+
+/// Standard graphic equalizer layouts with ISO 266 center frequencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphicEqPreset {
+    /// 10 bands, one octave apart (31.5 Hz – 16 kHz).
+    Iso10,
+    /// 15 bands, two-thirds of an octave apart (25 Hz – 16 kHz).
+    Iso15,
+    /// 31 bands, one-third of an octave apart (20 Hz – 20 kHz).
+    Iso31,
+}
+
+const ISO_10: [f32; 10] = [
+    31.5, 63.0, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0,
+];
+
+const ISO_15: [f32; 15] = [
+    25.0, 40.0, 63.0, 100.0, 160.0, 250.0, 400.0, 630.0, 1000.0, 1600.0, 2500.0, 4000.0, 6300.0,
+    10000.0, 16000.0,
+];
+
+const ISO_31: [f32; 31] = [
+    20.0, 25.0, 31.5, 40.0, 50.0, 63.0, 80.0, 100.0, 125.0, 160.0, 200.0, 250.0, 315.0, 400.0,
+    500.0, 630.0, 800.0, 1000.0, 1250.0, 1600.0, 2000.0, 2500.0, 3150.0, 4000.0, 5000.0, 6300.0,
+    8000.0, 10000.0, 12500.0, 16000.0, 20000.0,
+];
+
+impl GraphicEqPreset {
+    /// Returns the center frequencies of the preset's bands, in Hz.
+    pub fn frequencies(self) -> &'static [f32] {
+        match self {
+            GraphicEqPreset::Iso10 => &ISO_10,
+            GraphicEqPreset::Iso15 => &ISO_15,
+            GraphicEqPreset::Iso31 => &ISO_31,
+        }
+    }
+
+    /// Returns a flat (0 dB) curve with the preset's bands.
+    pub fn flat(self) -> Vec<GraphicEqPoint> {
+        self.frequencies()
+            .iter()
+            .map(|&frequency| GraphicEqPoint {
+                frequency,
+                gain: 0.0,
+            })
+            .collect()
+    }
+}
+
+/// Checks that a curve can be written to EqualizerAPO.
+///
+/// # Errors
+///
+/// Returns an error if a frequency is not positive, a gain is not finite, or
+/// the points are not in strictly ascending frequency order.
+pub fn validate_points(points: &[GraphicEqPoint]) -> Result<(), String> {
+    for (i, point) in points.iter().enumerate() {
+        if !point.frequency.is_finite() || point.frequency <= 0.0 {
+            return Err(format!(
+                "GraphicEQ point {}: frequency must be positive, got {}",
+                i + 1,
+                point.frequency
+            ));
+        }
+        if !point.gain.is_finite() {
+            return Err(format!(
+                "GraphicEQ point {}: gain is not a finite number",
+                i + 1
+            ));
+        }
+    }
+    if let Some(pair) = points
+        .windows(2)
+        .find(|pair| pair[1].frequency <= pair[0].frequency)
+    {
+        return Err(format!(
+            "GraphicEQ frequencies must be ascending ({} Hz follows {} Hz)",
+            pair[1].frequency, pair[0].frequency
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the curve's gain in dB at `freq` Hz.
+///
+/// Returns 0 dB for an empty curve.
+pub fn gain_at(points: &[GraphicEqPoint], freq: f64) -> f64 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return 0.0;
+    };
+    if freq <= first.frequency as f64 {
+        return first.gain as f64;
+    }
+    if freq >= last.frequency as f64 {
+        return last.gain as f64;
+    }
+
+    let upper = points
+        .iter()
+        .position(|p| p.frequency as f64 >= freq)
+        .unwrap_or(points.len() - 1);
+    let (lo, hi) = (&points[upper - 1], &points[upper]);
+    let (log_lo, log_hi) = ((lo.frequency as f64).log10(), (hi.frequency as f64).log10());
+    let t = (freq.log10() - log_lo) / (log_hi - log_lo);
+    lo.gain as f64 + t * (hi.gain as f64 - lo.gain as f64)
+}
+
+/// Formats a curve as an EqualizerAPO `GraphicEQ:` line.
+///
+/// Values are written with the shortest representation that round-trips,
+/// so no precision is lost.
+pub fn to_eapo_line(points: &[GraphicEqPoint]) -> String {
+    let pairs: Vec<String> = points
+        .iter()
+        .map(|p| format!("{} {}", p.frequency, p.gain))
+        .collect();
+    format!("GraphicEQ: {}", pairs.join("; "))
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Returns a flat curve for one of the standard ISO layouts.
+#[tauri::command]
+pub fn get_graphic_eq_preset(preset: GraphicEqPreset) -> Vec<GraphicEqPoint> {
+    preset.flat()
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(frequency: f32, gain: f32) -> GraphicEqPoint {
+        GraphicEqPoint { frequency, gain }
+    }
+
+    #[test]
+    fn presets_have_expected_band_counts() {
+        assert_eq!(GraphicEqPreset::Iso10.flat().len(), 10);
+        assert_eq!(GraphicEqPreset::Iso15.flat().len(), 15);
+        assert_eq!(GraphicEqPreset::Iso31.flat().len(), 31);
+        for preset in [
+            GraphicEqPreset::Iso10,
+            GraphicEqPreset::Iso15,
+            GraphicEqPreset::Iso31,
+        ] {
+            assert!(validate_points(&preset.flat()).is_ok());
+        }
+    }
+
+    #[test]
+    fn gain_interpolates_over_log_frequency() {
+        let points = [point(100.0, 0.0), point(1000.0, 6.0)];
+        assert!((gain_at(&points, 316.227_77) - 3.0).abs() < 1e-4);
+        assert_eq!(gain_at(&points, 100.0), 0.0);
+        assert_eq!(gain_at(&points, 1000.0), 6.0);
+    }
+
+    #[test]
+    fn gain_holds_end_values() {
+        let points = [point(100.0, -2.0), point(1000.0, 4.0)];
+        assert_eq!(gain_at(&points, 20.0), -2.0);
+        assert_eq!(gain_at(&points, 20000.0), 4.0);
+        assert_eq!(gain_at(&[], 1000.0), 0.0);
+    }
+
+    #[test]
+    fn validate_rejects_bad_points() {
+        assert!(validate_points(&[point(0.0, 1.0)]).is_err());
+        assert!(validate_points(&[point(100.0, f32::NAN)]).is_err());
+        assert!(validate_points(&[point(200.0, 0.0), point(100.0, 0.0)]).is_err());
+        assert!(validate_points(&[]).is_ok());
+    }
+
+    #[test]
+    fn eapo_line_keeps_full_precision() {
+        let line = to_eapo_line(&[point(31.5, 0.0), point(105.5, -2.25)]);
+        assert_eq!(line, "GraphicEQ: 31.5 0; 105.5 -2.25");
+    }
+}
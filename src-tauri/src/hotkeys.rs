@@ -0,0 +1,441 @@
+//! Global keyboard shortcuts.
+//!
+//! Bindings are stored in [`HotkeySettings`] and registered with the OS through
+//! `tauri-plugin-global-shortcut`. Each [`HotkeyCommand`] maps to an [`Action`]
+//! that runs through [`crate::actions::dispatch`], so the binding logic and
+//! conflict detection here can be tested without an OS keyboard hook.
+//!
+//! # Default Bindings
+//!
+//! | Command            | Shortcut          |
+//! |--------------------|-------------------|
+//! | Next profile       | `Ctrl+Alt+Right`  |
+//! | Previous profile   | `Ctrl+Alt+Left`   |
+//! | Toggle EQ bypass   | `Ctrl+Alt+B`      |
+//! | Preamp up / down   | `Ctrl+Alt+Up/Down`|
+//! | Favorite 1–9       | `Ctrl+Alt+1`–`9`  |
+//!
+//! Shortcuts are disabled by default.
+
+use std::str::FromStr;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::actions::{dispatch, Action};
+use crate::notifications::{report_error, ErrorSource};
+use crate::profile::save_settings;
+use crate::types::{AppState, HotkeyBinding, HotkeyCommand, HotkeySettings};
+
+/// Preamp change per press of the preamp up/down shortcuts, in dB.
+pub const PREAMP_STEP_DB: f32 = 0.5;
+
+/// Returns the action a hotkey command performs.
+pub fn command_action(command: HotkeyCommand) -> Action {
+    match command {
+        HotkeyCommand::NextProfile => Action::NextProfile,
+        HotkeyCommand::PreviousProfile => Action::PreviousProfile,
+        HotkeyCommand::ToggleEq => Action::ToggleEq,
+        HotkeyCommand::PreampUp => Action::AdjustPreamp {
+            delta: PREAMP_STEP_DB,
+        },
+        HotkeyCommand::PreampDown => Action::AdjustPreamp {
+            delta: -PREAMP_STEP_DB,
+        },
+        HotkeyCommand::Favorite(slot) => Action::ApplyFavorite {
+            slot: slot as usize,
+        },
+    }
+}
+
+/// Parses and validates an accelerator string such as `"Ctrl+Alt+B"`.
+///
+/// A modifier is required unless the key is a function key, since a bare
+/// global letter or digit would make it impossible to type that character
+/// in any other application.
+///
+/// # Errors
+///
+/// Returns an error if the accelerator cannot be parsed or has no modifier.
+pub fn parse_shortcut(accelerator: &str) -> Result<Shortcut, String> {
+    let shortcut = Shortcut::from_str(accelerator)
+        .map_err(|e| format!("Invalid shortcut '{}': {}", accelerator, e))?;
+
+    if shortcut.mods.is_empty() && !is_function_key(shortcut.key) {
+        return Err(format!(
+            "Shortcut '{}' needs a modifier (Ctrl, Alt, Shift or Super)",
+            accelerator
+        ));
+    }
+
+    Ok(shortcut)
+}
+
+/// Returns `true` for F1–F24.
+fn is_function_key(key: Code) -> bool {
+    let name = key.to_string();
+    name.len() > 1 && name.starts_with('F') && name[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Two or more commands bound to the same key combination.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HotkeyConflict {
+    /// The shortcut as written in the first conflicting binding.
+    pub shortcut: String,
+    /// Commands sharing the shortcut.
+    pub commands: Vec<HotkeyCommand>,
+}
+
+/// Finds bindings that resolve to the same key combination.
+///
+/// Shortcuts are compared after parsing, so `"Ctrl+Alt+B"` and `"alt+control+b"`
+/// conflict. Unparseable shortcuts are skipped.
+pub fn find_conflicts(bindings: &[HotkeyBinding]) -> Vec<HotkeyConflict> {
+    let mut groups: Vec<(Shortcut, HotkeyConflict)> = Vec::new();
+
+    for binding in bindings {
+        let Ok(shortcut) = parse_shortcut(&binding.shortcut) else {
+            continue;
+        };
+        match groups.iter_mut().find(|(s, _)| *s == shortcut) {
+            Some((_, conflict)) => conflict.commands.push(binding.command),
+            None => groups.push((
+                shortcut,
+                HotkeyConflict {
+                    shortcut: binding.shortcut.clone(),
+                    commands: vec![binding.command],
+                },
+            )),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, conflict)| conflict)
+        .filter(|conflict| conflict.commands.len() > 1)
+        .collect()
+}
+
+/// Binds `command` to `shortcut`, or unbinds it if `shortcut` is `None`.
+///
+/// # Errors
+///
+/// Returns an error if the shortcut is invalid or already bound to another
+/// command. The bindings are left unchanged on error.
+pub fn rebind(
+    bindings: &mut Vec<HotkeyBinding>,
+    command: HotkeyCommand,
+    shortcut: Option<&str>,
+) -> Result<(), String> {
+    let Some(accelerator) = shortcut else {
+        bindings.retain(|b| b.command != command);
+        return Ok(());
+    };
+
+    let parsed = parse_shortcut(accelerator)?;
+    let conflict = bindings
+        .iter()
+        .find(|b| b.command != command && parse_shortcut(&b.shortcut).is_ok_and(|s| s == parsed));
+    if let Some(other) = conflict {
+        return Err(format!(
+            "'{}' is already bound to {}",
+            accelerator,
+            other.command.label()
+        ));
+    }
+
+    let binding = HotkeyBinding {
+        command,
+        shortcut: accelerator.to_string(),
+    };
+    match bindings.iter_mut().find(|b| b.command == command) {
+        Some(existing) => *existing = binding,
+        None => bindings.push(binding),
+    }
+    Ok(())
+}
+
+// =============================================================================
+// OS Registration
+// =============================================================================
+
+/// Registers the configured shortcuts with the OS, replacing any previous ones.
+///
+/// Nothing is registered when hotkeys are disabled. A shortcut that fails to
+/// register (e.g. because another application owns it) doesn't prevent the
+/// others from registering.
+///
+/// # Returns
+///
+/// The commands whose shortcut failed to register, with the reason.
+pub fn register_hotkeys(app: &AppHandle) -> Vec<(HotkeyCommand, String)> {
+    let config = app.state::<AppState>().settings.lock().hotkeys.clone();
+    let global_shortcut = app.global_shortcut();
+    let _ = global_shortcut.unregister_all();
+
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    let mut failures = Vec::new();
+    for binding in config.bindings {
+        let result = parse_shortcut(&binding.shortcut).and_then(|shortcut| {
+            let action = command_action(binding.command);
+            global_shortcut
+                .on_shortcut(shortcut, move |app, _shortcut, event| {
+                    if event.state == ShortcutState::Pressed {
+                        if let Err(e) = dispatch(app, action.clone()) {
+                            report_error(app, ErrorSource::Hotkey, &action.describe(), &e);
+                        }
+                    }
+                })
+                .map_err(|e| e.to_string())
+        });
+        if let Err(e) = result {
+            failures.push((binding.command, e));
+        }
+    }
+    failures
+}
+
+/// Registers the configured shortcuts and reports each one that fails to
+/// register through the error notifications.
+pub fn register_and_report(app: &AppHandle) {
+    for (command, e) in register_hotkeys(app) {
+        let action = format!("Register hotkey for {}", command.label());
+        report_error(app, ErrorSource::Hotkey, &action, &e);
+    }
+}
+
+/// A command and its binding, for the settings UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct HotkeyInfo {
+    /// Command id (e.g. `"toggle_eq"`).
+    pub command: HotkeyCommand,
+    /// Human-readable command name.
+    pub label: String,
+    /// Bound shortcut, if any.
+    pub shortcut: Option<String>,
+    /// Whether the shortcut is currently registered with the OS.
+    pub registered: bool,
+}
+
+/// Current hotkey configuration and status.
+#[derive(Debug, Clone, Serialize)]
+pub struct HotkeyStatus {
+    /// Whether hotkeys are enabled.
+    pub enabled: bool,
+    /// Every bindable command, bound or not.
+    pub hotkeys: Vec<HotkeyInfo>,
+    /// Bindings that share a key combination.
+    pub conflicts: Vec<HotkeyConflict>,
+}
+
+fn hotkey_status(app: &AppHandle, config: &HotkeySettings) -> HotkeyStatus {
+    let global_shortcut = app.global_shortcut();
+
+    let hotkeys = HotkeyCommand::all()
+        .into_iter()
+        .map(|command| {
+            let shortcut = config
+                .bindings
+                .iter()
+                .find(|b| b.command == command)
+                .map(|b| b.shortcut.clone());
+            let registered = config.enabled
+                && shortcut
+                    .as_deref()
+                    .and_then(|s| parse_shortcut(s).ok())
+                    .is_some_and(|s| global_shortcut.is_registered(s));
+            HotkeyInfo {
+                command,
+                label: command.label(),
+                shortcut,
+                registered,
+            }
+        })
+        .collect();
+
+    HotkeyStatus {
+        enabled: config.enabled,
+        hotkeys,
+        conflicts: find_conflicts(&config.bindings),
+    }
+}
+
+/// Lists every hotkey command with its binding and registration status.
+#[tauri::command]
+pub fn get_hotkeys(state: tauri::State<AppState>, app: AppHandle) -> HotkeyStatus {
+    let config = state.settings.lock().hotkeys.clone();
+    hotkey_status(&app, &config)
+}
+
+/// Binds a command to a shortcut, or unbinds it, and re-registers hotkeys.
+///
+/// A shortcut the OS refuses (e.g. because another application owns it) is
+/// still saved, reported as an error notification and shown as not
+/// registered in the returned status.
+///
+/// # Arguments
+///
+/// * `command` - Command id, e.g. `"toggle_eq"` or `"favorite_1"`
+/// * `shortcut` - Accelerator such as `"Ctrl+Alt+B"`, or `None` to unbind
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle
+///
+/// # Errors
+///
+/// Returns an error if the shortcut is invalid, conflicts with another
+/// binding, or settings cannot be saved.
+#[tauri::command]
+pub fn set_hotkey(
+    command: HotkeyCommand,
+    shortcut: Option<String>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<HotkeyStatus, String> {
+    let config = {
+        let mut settings = state.settings.lock();
+        let mut bindings = settings.hotkeys.bindings.clone();
+        rebind(&mut bindings, command, shortcut.as_deref())?;
+        settings.hotkeys.bindings = bindings;
+        save_settings(&settings)?;
+        settings.hotkeys.clone()
+    };
+
+    register_and_report(&app);
+    Ok(hotkey_status(&app, &config))
+}
+
+/// Enables or disables all global hotkeys.
+///
+/// Shortcuts that fail to register are reported as error notifications.
+///
+/// # Errors
+///
+/// Returns an error if settings cannot be saved.
+#[tauri::command]
+pub fn set_hotkeys_enabled(
+    enabled: bool,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<HotkeyStatus, String> {
+    let config = {
+        let mut settings = state.settings.lock();
+        settings.hotkeys.enabled = enabled;
+        save_settings(&settings)?;
+        settings.hotkeys.clone()
+    };
+
+    register_and_report(&app);
+    Ok(hotkey_status(&app, &config))
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(command: HotkeyCommand, shortcut: &str) -> HotkeyBinding {
+        HotkeyBinding {
+            command,
+            shortcut: shortcut.to_string(),
+        }
+    }
+
+    #[test]
+    fn command_action_mapping() {
+        assert_eq!(command_action(HotkeyCommand::ToggleEq), Action::ToggleEq);
+        assert_eq!(
+            command_action(HotkeyCommand::PreampDown),
+            Action::AdjustPreamp {
+                delta: -PREAMP_STEP_DB
+            }
+        );
+        assert_eq!(
+            command_action(HotkeyCommand::Favorite(4)),
+            Action::ApplyFavorite { slot: 4 }
+        );
+    }
+
+    #[test]
+    fn command_ids_round_trip() {
+        for command in HotkeyCommand::all() {
+            assert_eq!(HotkeyCommand::try_from(command.id()), Ok(command));
+        }
+        assert!(HotkeyCommand::try_from("favorite_10".to_string()).is_err());
+    }
+
+    #[test]
+    fn parse_shortcut_requires_modifier() {
+        assert!(parse_shortcut("Ctrl+Alt+B").is_ok());
+        assert!(parse_shortcut("F9").is_ok());
+        assert!(parse_shortcut("B").is_err());
+        assert!(parse_shortcut("Ctrl+Nonsense").is_err());
+    }
+
+    #[test]
+    fn default_bindings_have_no_conflicts() {
+        let config = HotkeySettings::default();
+        assert!(!config.enabled);
+        assert_eq!(config.bindings.len(), HotkeyCommand::all().len());
+        assert!(find_conflicts(&config.bindings).is_empty());
+        assert!(config
+            .bindings
+            .iter()
+            .all(|b| parse_shortcut(&b.shortcut).is_ok()));
+    }
+
+    #[test]
+    fn find_conflicts_ignores_case_and_order() {
+        let bindings = vec![
+            binding(HotkeyCommand::ToggleEq, "Ctrl+Alt+B"),
+            binding(HotkeyCommand::PreampUp, "alt+control+b"),
+            binding(HotkeyCommand::PreampDown, "Ctrl+Alt+Down"),
+        ];
+        let conflicts = find_conflicts(&bindings);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0].commands,
+            vec![HotkeyCommand::ToggleEq, HotkeyCommand::PreampUp]
+        );
+    }
+
+    #[test]
+    fn rebind_rejects_conflict() {
+        let mut bindings = vec![
+            binding(HotkeyCommand::ToggleEq, "Ctrl+Alt+B"),
+            binding(HotkeyCommand::PreampUp, "Ctrl+Alt+Up"),
+        ];
+        let before = bindings.clone();
+        assert!(rebind(&mut bindings, HotkeyCommand::PreampUp, Some("Ctrl+Alt+B")).is_err());
+        assert_eq!(bindings, before);
+    }
+
+    #[test]
+    fn rebind_replaces_adds_and_removes() {
+        let mut bindings = vec![binding(HotkeyCommand::ToggleEq, "Ctrl+Alt+B")];
+
+        rebind(&mut bindings, HotkeyCommand::ToggleEq, Some("Ctrl+Shift+B")).unwrap();
+        assert_eq!(
+            bindings,
+            vec![binding(HotkeyCommand::ToggleEq, "Ctrl+Shift+B")]
+        );
+
+        rebind(&mut bindings, HotkeyCommand::Favorite(1), Some("F9")).unwrap();
+        assert_eq!(bindings.len(), 2);
+
+        rebind(&mut bindings, HotkeyCommand::ToggleEq, None).unwrap();
+        assert_eq!(bindings, vec![binding(HotkeyCommand::Favorite(1), "F9")]);
+    }
+
+    #[test]
+    fn rebind_same_command_to_its_own_shortcut() {
+        let mut bindings = vec![binding(HotkeyCommand::ToggleEq, "Ctrl+Alt+B")];
+        assert!(rebind(&mut bindings, HotkeyCommand::ToggleEq, Some("ctrl+alt+b")).is_ok());
+    }
+}
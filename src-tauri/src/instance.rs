@@ -0,0 +1,193 @@
+//! Single-instance enforcement and launch argument forwarding.
+//!
+//! Only one EQAPO GUI process may run at a time, since two instances would
+//! fight over `settings.json`, the live config file and the tray icon. When the
+//! app is launched again, the second process exits immediately and its
+//! command-line arguments are forwarded to the running instance (via
+//! [`tauri_plugin_single_instance`], which uses a local IPC channel), where
+//! they are applied as if chosen from the tray.
+//!
+//! # Supported Arguments
+//!
+//! | Argument            | Effect                                      |
+//! |---------------------|---------------------------------------------|
+//! | `--profile <name>`  | Apply the named profile                     |
+//! | `--bypass`          | Bypass EQ processing                        |
+//! | `--no-bypass`       | Re-enable EQ processing                     |
+//! | `--show`            | Show and focus the main window              |
+//!
+//! A forwarded launch without any recognized argument shows the main window,
+//! matching what a user expects from double-clicking the app shortcut.
+
+use tauri::{plugin::TauriPlugin, AppHandle, Wry};
+
+use crate::actions::{dispatch, show_main_window, Action};
+use crate::notifications::{report_error, ErrorSource};
+
+/// Actions requested on the command line.
+#[derive(Debug, Default, PartialEq)]
+pub struct LaunchArgs {
+    /// Profile to apply, from `--profile <name>`.
+    pub profile: Option<String>,
+
+    /// Requested EQ state: `Some(false)` for `--bypass`, `Some(true)` for `--no-bypass`.
+    pub eq_enabled: Option<bool>,
+
+    /// Whether `--show` was given.
+    pub show: bool,
+}
+
+impl LaunchArgs {
+    /// Returns `true` if no action was requested.
+    pub fn is_empty(&self) -> bool {
+        self.profile.is_none() && self.eq_enabled.is_none() && !self.show
+    }
+
+    /// Converts the arguments into the actions to perform, in order.
+    ///
+    /// The profile is applied before the EQ state so that
+    /// `--profile X --bypass` leaves profile X selected but bypassed.
+    pub fn to_actions(&self) -> Vec<Action> {
+        let mut actions = Vec::new();
+        if let Some(name) = &self.profile {
+            actions.push(Action::ApplyProfile { name: name.clone() });
+        }
+        if let Some(enabled) = self.eq_enabled {
+            actions.push(Action::SetEqEnabled { enabled });
+        }
+        if self.show {
+            actions.push(Action::ShowWindow);
+        }
+        actions
+    }
+}
+
+/// Parses launch arguments (without the program name).
+///
+/// Both `--profile <name>` and `--profile=<name>` are accepted. Unknown
+/// arguments are ignored so that arguments added by the OS or an installer
+/// don't prevent the app from starting.
+pub fn parse_launch_args<S: AsRef<str>>(args: &[S]) -> LaunchArgs {
+    let mut parsed = LaunchArgs::default();
+
+    let mut iter = args.iter().map(AsRef::as_ref);
+    while let Some(arg) = iter.next() {
+        match arg {
+            "--profile" => {
+                if let Some(name) = iter.next() {
+                    parsed.profile = Some(name.to_string());
+                }
+            }
+            "--bypass" => parsed.eq_enabled = Some(false),
+            "--no-bypass" => parsed.eq_enabled = Some(true),
+            "--show" => parsed.show = true,
+            other => {
+                if let Some(name) = other.strip_prefix("--profile=") {
+                    parsed.profile = Some(name.to_string());
+                }
+            }
+        }
+    }
+
+    parsed
+}
+
+/// Applies launch arguments to the running instance.
+///
+/// Failures are reported to the error log and do not prevent the remaining
+/// actions from running.
+pub fn handle_launch_args(app: &AppHandle, args: &LaunchArgs) {
+    for action in args.to_actions() {
+        if let Err(e) = dispatch(app, action.clone()) {
+            report_error(app, ErrorSource::LaunchArgs, &action.describe(), &e);
+        }
+    }
+}
+
+/// Creates the single-instance plugin.
+///
+/// Must be registered before any other plugin so that a second instance exits
+/// before it initializes anything else.
+pub fn init() -> TauriPlugin<Wry> {
+    tauri_plugin_single_instance::init(|app, argv, _cwd| {
+        let args = parse_launch_args(argv.get(1..).unwrap_or_default());
+        if args.is_empty() {
+            show_main_window(app);
+        } else {
+            handle_launch_args(app, &args);
+        }
+    })
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_launch_args_empty() {
+        let args = parse_launch_args::<&str>(&[]);
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn parse_launch_args_profile_separate_value() {
+        let args = parse_launch_args(&["--profile", "Studio Monitors"]);
+        assert_eq!(args.profile.as_deref(), Some("Studio Monitors"));
+    }
+
+    #[test]
+    fn parse_launch_args_profile_equals_value() {
+        let args = parse_launch_args(&["--profile=Studio"]);
+        assert_eq!(args.profile.as_deref(), Some("Studio"));
+    }
+
+    #[test]
+    fn parse_launch_args_bypass_flags() {
+        assert_eq!(parse_launch_args(&["--bypass"]).eq_enabled, Some(false));
+        assert_eq!(parse_launch_args(&["--no-bypass"]).eq_enabled, Some(true));
+    }
+
+    #[test]
+    fn parse_launch_args_combined() {
+        let args = parse_launch_args(&["--show", "--profile", "Studio", "--bypass"]);
+        assert_eq!(
+            args,
+            LaunchArgs {
+                profile: Some("Studio".to_string()),
+                eq_enabled: Some(false),
+                show: true,
+            }
+        );
+    }
+
+    #[test]
+    fn launch_args_actions_in_order() {
+        let args = parse_launch_args(&["--bypass", "--show", "--profile", "Studio"]);
+        assert_eq!(
+            args.to_actions(),
+            vec![
+                Action::ApplyProfile {
+                    name: "Studio".to_string()
+                },
+                Action::SetEqEnabled { enabled: false },
+                Action::ShowWindow,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_launch_args_ignores_unknown() {
+        let args = parse_launch_args(&["--minimized", "-psn_0_12345"]);
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn parse_launch_args_profile_without_value() {
+        let args = parse_launch_args(&["--profile"]);
+        assert!(args.profile.is_none());
+    }
+}
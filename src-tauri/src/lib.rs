@@ -26,7 +26,32 @@
 //! | [`profile`]     | Profile and settings file I/O                  |
 //! | [`commands`]    | A/B testing Tauri command handlers             |
 //! | [`tray`]        | System tray menu and event handling            |
+//! | [`actions`]     | Shared EQ actions (apply profile, bypass, ...) |
+//! | [`instance`]    | Single-instance lock and argument forwarding   |
+//! | [`autoeq`]      | AutoEQ ParametricEQ/GraphicEQ import           |
+//! | [`convolution`] | Impulse import for convolution stages          |
+//! | [`crossfeed`]   | Headphone crossfeed stage                      |
+//! | [`rew`]         | REW filter settings import/export              |
+//! | [`routing`]     | Channel routing and bass management            |
+//! | [`export`]      | Export to other equalizer software formats     |
+//! | [`preset_import`]| Import presets from other equalizer software  |
+//! | [`automation`]  | Opt-in local HTTP/WebSocket automation API     |
+//! | [`osc`]         | Optional OSC control surface over UDP          |
+//! | [`fir`]         | FIR impulse-response rendering                 |
+//! | [`fitting`]     | GraphicEQ ↔ parametric band conversion         |
+//! | [`loudness`]    | ISO 226 loudness compensation                  |
+//! | [`graphic_eq`]  | GraphicEQ presets, interpolation and output    |
+//! | [`measurement`] | Measurement import and correction generation   |
+//! | [`mid_side`]    | Mid/side EQ and stereo width                   |
+//! | [`targets`]     | Target curve library and saved custom targets  |
+//! | [`validation`]  | Sample-rate-aware band validation and clamping |
+//! | [`vst`]         | VST plugin stage (`VSTPlugin:` lines)          |
+//! | [`hotkeys`]     | Global keyboard shortcuts                      |
+//! | [`notifications`]| Error log for tray/hotkey/launch actions      |
 //! | [`ab_test`]     | A/B/X blind testing session logic              |
+//! | [`dsp`]         | Biquad frequency response (mirrors the UI)     |
+//! | [`wav`]         | WAV file reading and writing                   |
+//! | [`cli`]         | Headless `eqapo-cli` command-line interface    |
 //! | [`audio_monitor`]| WASAPI peak metering (Windows only)           |
 //!
 //! # Data Flow
@@ -51,6 +76,11 @@
 //! - [`FilterType`] - Enum of supported EQ filter types
 //! - [`ParametricBand`] - Single EQ band configuration
 //! - [`EqProfile`] - Complete EQ profile with name, preamp, and bands
+//! - [`ProfileMetadata`] - Profile folder, tags and import source
+//! - [`ProfileSource`] - File a profile was imported from
+//! - [`GraphicEqPoint`] - Single point of a GraphicEQ curve
+//! - [`ConvolutionStage`] - Impulse response referenced by a profile
+//! - [`ConfigPrecision`] - Decimal places written to the config
 //! - [`AppSettings`] - Persistent application settings
 //! - [`AppState`] - Runtime state managed by Tauri
 //!
@@ -64,6 +94,9 @@
 //! }
 //! ```
 //!
+//! The `eqapo-cli` binary uses [`run_cli()`] instead, which manages profiles
+//! and settings without starting Tauri.
+//!
 //! # Platform Support
 //!
 //! While the core functionality works on any platform Tauri supports, the following
@@ -83,18 +116,93 @@ use tauri::WindowEvent;
 /// A/B and blind listening test session management.
 mod ab_test;
 
+/// Shared EQ actions used by the tray and remote controls.
+mod actions;
+
+/// AutoEQ result file and directory import.
+mod autoeq;
+
+/// Opt-in local HTTP/WebSocket automation API.
+mod automation;
+
+/// Headless command-line interface (`eqapo-cli` binary).
+mod cli;
+
 /// Tauri command handlers for A/B testing.
 mod commands;
 
+/// Biquad filter math and frequency response.
+mod dsp;
+
+/// Export of profiles to other equalizer software.
+mod export;
+
+/// Convolution stages and impulse response import.
+mod convolution;
+
+/// Headphone crossfeed expanded into EqualizerAPO directives.
+mod crossfeed;
+
+/// FIR impulse responses rendered from profiles.
+mod fir;
+
+/// Fitting parametric bands to curves and rasterizing them back.
+mod fitting;
+
+/// GraphicEQ curves: ISO presets, interpolation and config output.
+mod graphic_eq;
+
+/// Global keyboard shortcut bindings and registration.
+mod hotkeys;
+
+/// Single-instance enforcement and launch argument forwarding.
+mod instance;
+
+/// Volume-dependent loudness compensation (ISO 226).
+mod loudness;
+
+/// Measurement file import and target-curve correction.
+mod measurement;
+
+/// Mid/side bands and stereo width via channel matrixing.
+mod mid_side;
+
+/// Persistent error log and `action-error` notifications.
+mod notifications;
+
+/// OSC control surface listener and codec.
+mod osc;
+
+/// Import of presets from other equalizer software.
+mod preset_import;
+
 /// Profile and settings file I/O operations.
 mod profile;
 
+/// Room EQ Wizard filter settings import and export.
+mod rew;
+
+/// Channel matrices, swaps, mutes and bass management.
+mod routing;
+
+/// Target curves for correction.
+mod targets;
+
 /// System tray icon and menu handling.
 mod tray;
 
 /// Core data types shared across modules.
 mod types;
 
+/// Band validation against the output sample rate.
+mod validation;
+
+/// VST plugin stages hosted by EqualizerAPO.
+mod vst;
+
+/// WAV file encoding and header parsing.
+mod wav;
+
 /// Windows audio monitoring via WASAPI (Windows only).
 #[cfg(windows)]
 mod audio_monitor;
@@ -104,22 +212,52 @@ mod audio_monitor;
 // =============================================================================
 
 // Internal use
+use instance::{handle_launch_args, parse_launch_args};
+use notifications::{report_error, ErrorSource};
 use profile::load_settings;
 use tray::setup_tray;
 
 // Public API - these types are used by tests and could be used by external code
-pub use types::{AppSettings, AppState, EqProfile, FilterType, ParametricBand};
+pub use types::{
+    AppSettings, AppState, ConfigPrecision, ConvolutionStage, EqProfile, FilterType,
+    GraphicEqPoint, ParametricBand, ProfileMetadata, ProfileSource,
+};
+
+// Entry point for the headless `eqapo-cli` binary
+pub use cli::run_cli;
 
 // Re-export commands for Tauri handler
+use autoeq::{import_autoeq_directory, import_autoeq_file};
+use automation::{get_automation_status, regenerate_automation_token, set_automation_enabled};
 use commands::{
     apply_ab_option, finish_ab_session, get_ab_state, record_ab_answer, start_ab_session,
     update_ab_trim,
 };
+use convolution::{import_impulse, list_impulses, set_convolution, set_profile_convolution};
+use crossfeed::{list_crossfeed_presets, set_crossfeed, set_profile_crossfeed};
+use dsp::get_frequency_response;
+use export::{export_profile, list_export_formats};
+use fir::export_impulse_response;
+use fitting::{fit_parametric_eq, parametric_to_graphic_eq};
+use graphic_eq::get_graphic_eq_preset;
+use hotkeys::{get_hotkeys, set_hotkey, set_hotkeys_enabled};
+use loudness::{get_loudness_compensation, set_loudness};
+use measurement::{generate_correction_profile, import_measurement};
+use mid_side::{set_mid_side, set_profile_mid_side};
+use notifications::{clear_error_log, get_error_log, load_error_log};
+use osc::{get_osc_status, set_osc_enabled};
+use preset_import::import_preset;
 use profile::{
-    apply_profile, delete_profile, get_current_profile, get_settings, list_profiles, load_profile,
-    save_profile, set_current_profile, update_settings,
+    apply_profile, delete_profile, get_current_profile, get_settings, list_profile_summaries,
+    list_profiles, load_profile, save_profile, set_current_profile, set_favorites,
+    set_profile_metadata, update_settings,
 };
+use rew::{export_rew_filters, import_rew_filters};
+use routing::{set_profile_routing, set_routing};
+use targets::{delete_target, get_target_curve, list_targets, save_target};
 use tray::refresh_tray_menu;
+use validation::{set_validation_settings, validate_bands};
+use vst::{set_profile_vst_plugin, set_vst_plugin};
 
 #[cfg(windows)]
 use audio_monitor::{AudioMonitor, AudioOutputInfo, PeakMeterUpdate};
@@ -312,15 +450,18 @@ fn get_current_peak() -> Result<(), String> {
 /// This is the main entry point that sets up the entire application:
 ///
 /// 1. **Settings Loading**: Loads saved settings from `settings.json`, or uses defaults
-/// 2. **Plugin Registration**: Initializes Tauri plugins for dialogs, file access, etc.
-/// 3. **State Management**: Creates the [`AppState`] with settings, A/B session, and audio monitor
-/// 4. **System Tray**: Sets up the tray icon with profile switching menu
-/// 5. **Window Behavior**: Configures close-to-tray behavior
-/// 6. **Command Registration**: Registers all IPC command handlers
+/// 2. **Single Instance**: Forwards arguments to an already running instance and exits
+/// 3. **Plugin Registration**: Initializes Tauri plugins for dialogs, file access, etc.
+/// 4. **State Management**: Creates the [`AppState`] with settings, A/B session, and audio monitor
+/// 5. **System Tray**: Sets up the tray icon with profile switching menu
+/// 6. **Launch Arguments**: Applies `--profile`, `--bypass` and `--show` if given
+/// 7. **Window Behavior**: Configures close-to-tray behavior
+/// 8. **Command Registration**: Registers all IPC command handlers
 ///
 /// # Behavior
 ///
 /// - The application runs in the system tray
+/// - Only one instance runs at a time; launching again forwards the arguments
 /// - Closing the window hides it instead of exiting (close-to-tray)
 /// - Exit via the system tray "Quit" menu item
 ///
@@ -343,17 +484,42 @@ pub fn run() {
     let settings = load_settings();
 
     tauri::Builder::default()
+        // Must be registered first so a second instance exits immediately
+        .plugin(instance::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .manage(AppState {
             settings: Mutex::new(settings),
             ab_session: Mutex::new(None),
+            automation: Mutex::new(None),
+            osc: Mutex::new(None),
+            tray_status: Mutex::default(),
+            error_log: Mutex::new(load_error_log()),
             #[cfg(windows)]
             audio_monitor: Arc::new(AudioMonitor::new()),
         })
         .setup(|app| {
             setup_tray(app.handle())?;
+
+            // A failure to bind the port shouldn't prevent the app from starting
+            if let Err(e) = automation::sync_server(app.handle()) {
+                report_error(
+                    app.handle(),
+                    ErrorSource::Automation,
+                    "Start automation server",
+                    &e,
+                );
+            }
+            if let Err(e) = osc::sync_server(app.handle()) {
+                report_error(app.handle(), ErrorSource::Osc, "Start OSC listener", &e);
+            }
+            hotkeys::register_and_report(app.handle());
+
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            handle_launch_args(app.handle(), &parse_launch_args(&args));
+
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -365,6 +531,7 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             // Profile management
             list_profiles,
+            list_profile_summaries,
             load_profile,
             save_profile,
             apply_profile,
@@ -373,7 +540,58 @@ pub fn run() {
             set_current_profile,
             get_settings,
             update_settings,
+            set_favorites,
+            set_profile_metadata,
             refresh_tray_menu,
+            // Band validation
+            validate_bands,
+            set_validation_settings,
+            // Graphic EQ and frequency response
+            get_graphic_eq_preset,
+            get_frequency_response,
+            fit_parametric_eq,
+            parametric_to_graphic_eq,
+            // Convolution
+            import_impulse,
+            list_impulses,
+            set_convolution,
+            set_profile_convolution,
+            // Crossfeed
+            list_crossfeed_presets,
+            set_crossfeed,
+            set_profile_crossfeed,
+            // Loudness compensation
+            get_loudness_compensation,
+            set_loudness,
+            // Mid/side
+            set_mid_side,
+            set_profile_mid_side,
+            // Channel routing
+            set_routing,
+            set_profile_routing,
+            // VST plugins
+            set_vst_plugin,
+            set_profile_vst_plugin,
+            // AutoEQ import
+            import_autoeq_file,
+            import_autoeq_directory,
+            // REW filter settings
+            import_rew_filters,
+            export_rew_filters,
+            // Export to other equalizers
+            list_export_formats,
+            export_profile,
+            export_impulse_response,
+            // Import from other equalizers
+            import_preset,
+            // Measurements and correction
+            import_measurement,
+            generate_correction_profile,
+            // Target curves
+            list_targets,
+            get_target_curve,
+            save_target,
+            delete_target,
             // A/B testing
             start_ab_session,
             apply_ab_option,
@@ -381,6 +599,20 @@ pub fn run() {
             get_ab_state,
             finish_ab_session,
             update_ab_trim,
+            // Automation API
+            get_automation_status,
+            set_automation_enabled,
+            regenerate_automation_token,
+            // OSC control surface
+            get_osc_status,
+            set_osc_enabled,
+            // Global hotkeys
+            get_hotkeys,
+            set_hotkey,
+            set_hotkeys_enabled,
+            // Error log
+            get_error_log,
+            clear_error_log,
             // Audio monitoring
             get_audio_output_info,
             start_peak_meter,
@@ -430,6 +662,7 @@ mod tests {
             frequency: 1000.0,
             gain: 6.0,
             q_factor: 1.41,
+            enabled: true,
         };
         let line = band.to_eapo_line();
         assert_eq!(line, "Filter: ON PK Fc 1000 Hz Gain 6.0 dB Q 1.41");
@@ -442,6 +675,7 @@ mod tests {
             frequency: 100.0,
             gain: 3.5,
             q_factor: 0.71,
+            enabled: true,
         };
         let line = band.to_eapo_line();
         assert_eq!(line, "Filter: ON LSC Fc 100 Hz Gain 3.5 dB Q 0.71");
@@ -454,9 +688,10 @@ mod tests {
             frequency: 8000.0,
             gain: -2.0,
             q_factor: 0.707,
+            enabled: true,
         };
         let line = band.to_eapo_line();
-        assert_eq!(line, "Filter: ON HSC Fc 8000 Hz Gain -2.0 dB Q 0.71");
+        assert_eq!(line, "Filter: ON HSC Fc 8000 Hz Gain -2.0 dB Q 0.707");
     }
 
     #[test]
@@ -466,21 +701,42 @@ mod tests {
             frequency: 500.0,
             gain: -3.5,
             q_factor: 2.0,
+            enabled: true,
         };
         let line = band.to_eapo_line();
         assert!(line.contains("Gain -3.5 dB"));
     }
 
     #[test]
-    fn parametric_band_to_eapo_line_frequency_truncated() {
+    fn parametric_band_to_eapo_line_keeps_sub_hertz_frequency() {
         let band = ParametricBand {
             filter_type: FilterType::Peaking,
             frequency: 1234.567,
-            gain: 0.0,
+            gain: 0.25,
             q_factor: 1.0,
+            enabled: true,
         };
         let line = band.to_eapo_line();
-        assert!(line.contains("Fc 1234 Hz"));
+        assert_eq!(line, "Filter: ON PK Fc 1234.567 Hz Gain 0.25 dB Q 1.00");
+    }
+
+    #[test]
+    fn parametric_band_to_eapo_line_fixed_precision() {
+        let band = ParametricBand {
+            filter_type: FilterType::Peaking,
+            frequency: 1234.567,
+            gain: 0.26,
+            q_factor: 0.7049,
+            enabled: true,
+        };
+        let precision = ConfigPrecision {
+            frequency: Some(0),
+            gain: Some(1),
+            q_factor: Some(2),
+            preamp: None,
+        };
+        let line = band.to_eapo_line_with(&precision);
+        assert_eq!(line, "Filter: ON PK Fc 1235 Hz Gain 0.3 dB Q 0.70");
     }
 
     // =========================================================================
@@ -546,6 +802,7 @@ mod tests {
             frequency: 1000.0,
             gain: 6.0,
             q_factor: 1.41,
+            enabled: true,
         };
 
         let json = serde_json::to_string(&band).unwrap();
@@ -566,11 +823,300 @@ mod tests {
                 frequency: 1000.0,
                 gain: 6.0,
                 q_factor: 1.41,
+                enabled: true,
             }],
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&profile).unwrap();
         assert!(json.contains("\"name\":\"Test Profile\""));
         assert!(json.contains("\"preamp\":-3.5"));
     }
+
+    #[test]
+    fn eq_profile_omits_empty_graphic_eq() {
+        let profile = EqProfile {
+            name: "Parametric".to_string(),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&profile).unwrap();
+        assert!(!json.contains("graphic_eq"));
+    }
+
+    // =========================================================================
+    // Config Generation Tests
+    // =========================================================================
+
+    #[test]
+    fn config_content_writes_graphic_eq_before_filters() {
+        let profile = EqProfile {
+            preamp: -2.0,
+            bands: default_bands(),
+            graphic_eq: vec![
+                GraphicEqPoint {
+                    frequency: 20.0,
+                    gain: -1.5,
+                },
+                GraphicEqPoint {
+                    frequency: 20000.0,
+                    gain: 0.5,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let content = profile::build_config_content(&profile, true, &ConfigPrecision::default());
+        let lines: Vec<&str> = content.split("\r\n").collect();
+        let graphic = lines.iter().position(|l| l.starts_with("GraphicEQ:"));
+        let filter = lines.iter().position(|l| l.starts_with("Filter:"));
+        assert_eq!(
+            graphic.map(|i| lines[i]),
+            Some("GraphicEQ: 20 -1.5; 20000 0.5")
+        );
+        assert!(graphic < filter);
+
+        let bypassed = profile::build_config_content(&profile, false, &ConfigPrecision::default());
+        assert!(!bypassed.contains("GraphicEQ"));
+    }
+
+    #[test]
+    fn config_content_writes_convolution_after_preamp() {
+        let profile = EqProfile {
+            preamp: -6.0,
+            bands: default_bands(),
+            graphic_eq: vec![GraphicEqPoint {
+                frequency: 1000.0,
+                gain: 1.0,
+            }],
+            convolution: Some(ConvolutionStage {
+                file: "room.wav".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let content = profile::build_config_content(&profile, true, &ConfigPrecision::default());
+        let lines: Vec<&str> = content.split("\r\n").collect();
+        let position = |prefix: &str| lines.iter().position(|l| l.starts_with(prefix));
+        let convolution = position("Convolution:");
+        assert_eq!(
+            convolution.map(|i| lines[i]),
+            Some("Convolution: impulses\\room.wav")
+        );
+        assert!(position("Preamp:") < convolution);
+        assert!(convolution < position("GraphicEQ:"));
+        assert!(convolution < position("Filter:"));
+
+        let bypassed = profile::build_config_content(&profile, false, &ConfigPrecision::default());
+        assert!(!bypassed.contains("Convolution"));
+    }
+
+    #[test]
+    fn config_content_writes_crossfeed_before_other_stages() {
+        let profile = EqProfile {
+            preamp: -6.0,
+            bands: default_bands(),
+            convolution: Some(ConvolutionStage {
+                file: "room.wav".to_string(),
+            }),
+            crossfeed: Some(crossfeed::Crossfeed::default()),
+            ..Default::default()
+        };
+
+        let content = profile::build_config_content(&profile, true, &ConfigPrecision::default());
+        let lines: Vec<&str> = content.split("\r\n").collect();
+        let position = |line: &str| lines.iter().position(|l| l.starts_with(line));
+        let start = position("Copy: XFL=L XFR=R");
+        let reset = position("Channel: all");
+        assert!(position("Preamp:") < start);
+        assert!(start < reset);
+        assert!(reset < position("Convolution:"));
+        assert!(reset < position("Filter: ON PK"));
+
+        let bypassed = profile::build_config_content(&profile, false, &ConfigPrecision::default());
+        assert!(!bypassed.contains("XFL"));
+    }
+
+    #[test]
+    fn config_content_writes_mid_side_before_crossfeed() {
+        let profile = EqProfile {
+            bands: default_bands(),
+            crossfeed: Some(crossfeed::Crossfeed::default()),
+            mid_side: Some(mid_side::MidSide {
+                width: 1.5,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let content = profile::build_config_content(&profile, true, &ConfigPrecision::default());
+        let lines: Vec<&str> = content.split("\r\n").collect();
+        let position = |line: &str| lines.iter().position(|l| l.starts_with(line));
+        let split = position("Copy: MID=");
+        let merge = position("Copy: L=MID+1.5*SIDE");
+        assert!(position("Preamp:") < split);
+        assert!(split < merge);
+        assert!(merge < position("Copy: XFL=L XFR=R"));
+        assert!(merge < position("Filter: ON PK"));
+    }
+
+    #[test]
+    fn config_content_wraps_stages_in_routing() {
+        let profile = EqProfile {
+            bands: default_bands(),
+            mid_side: Some(mid_side::MidSide::default()),
+            routing: Some(routing::Routing {
+                preset: Some(routing::MixPreset::Downmix51ToStereo),
+                bass_management: Some(routing::BassManagement::default()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let content = profile::build_config_content(&profile, true, &ConfigPrecision::default());
+        let lines: Vec<&str> = content.split("\r\n").collect();
+        let position = |line: &str| lines.iter().position(|l| l.starts_with(line));
+        let downmix = position("Copy: L=0.414*L");
+        let crossover = position("Copy: LFE=LFE+L+R");
+        assert!(position("Preamp:") < downmix);
+        assert!(downmix < position("Copy: MID="));
+        assert!(position("Filter: ON PK") < crossover);
+        assert_eq!(lines.last(), Some(&"Channel: all"));
+    }
+
+    #[test]
+    fn config_content_writes_vst_plugin_after_bands() {
+        let profile = EqProfile {
+            bands: default_bands(),
+            vst_plugin: Some(vst::VstPlugin {
+                library: "limiter.dll".to_string(),
+                chunk: Some("AAAA".to_string()),
+            }),
+            routing: Some(routing::Routing {
+                bass_management: Some(routing::BassManagement::default()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let content = profile::build_config_content(&profile, true, &ConfigPrecision::default());
+        let lines: Vec<&str> = content.split("\r\n").collect();
+        let position = |line: &str| lines.iter().position(|l| l.starts_with(line));
+        let plugin = position("VSTPlugin: Library \"limiter.dll\" ChunkData \"AAAA\"");
+        assert!(plugin.is_some());
+        assert!(position("Filter: ON PK") < plugin);
+        assert!(plugin < position("Copy: LFE=LFE+L+R"));
+    }
+
+    // =========================================================================
+    // Config Round-Trip Tests
+    // =========================================================================
+
+    /// Writes a profile to config text and parses it back with the importer.
+    fn reparse_config(profile: &EqProfile) -> EqProfile {
+        let content = profile::build_config_content(profile, true, &ConfigPrecision::default());
+        let mut parsed = EqProfile::default();
+        for line in content.split("\r\n") {
+            if line.starts_with("Preamp:") {
+                parsed.preamp = autoeq::parse_preamp_line(line).unwrap();
+            } else if line.starts_with("GraphicEQ:") {
+                parsed.graphic_eq = autoeq::parse_graphic_eq_line(line).unwrap();
+            } else if line.starts_with("Filter") {
+                parsed.bands.push(autoeq::parse_filter_line(line).unwrap());
+            }
+        }
+        parsed
+    }
+
+    fn assert_same_filters(expected: &EqProfile, actual: &EqProfile) {
+        assert_eq!(expected.preamp.to_bits(), actual.preamp.to_bits());
+        assert_eq!(expected.bands.len(), actual.bands.len());
+        for (a, b) in expected.bands.iter().zip(&actual.bands) {
+            assert_eq!(a.filter_type, b.filter_type);
+            assert_eq!(a.frequency.to_bits(), b.frequency.to_bits(), "{:?}", a);
+            assert_eq!(a.gain.to_bits(), b.gain.to_bits(), "{:?}", a);
+            assert_eq!(a.q_factor.to_bits(), b.q_factor.to_bits(), "{:?}", a);
+        }
+        assert_eq!(expected.graphic_eq, actual.graphic_eq);
+    }
+
+    #[test]
+    fn config_round_trips_autoeq_values() {
+        let band = |filter_type, frequency, gain, q_factor| ParametricBand {
+            filter_type,
+            frequency,
+            gain,
+            q_factor,
+            enabled: true,
+        };
+        let profile = EqProfile {
+            preamp: -6.25,
+            bands: vec![
+                band(FilterType::LowShelf, 105.5, 5.5, 0.7),
+                band(FilterType::Peaking, 150.25, -2.125, 0.5),
+                band(FilterType::Peaking, 1234.567, 0.05, 1.333_333_4),
+                band(FilterType::HighShelf, 10000.0, -3.1, 0.612_345_7),
+            ],
+            graphic_eq: vec![
+                GraphicEqPoint {
+                    frequency: 20.5,
+                    gain: -0.25,
+                },
+                GraphicEqPoint {
+                    frequency: 19999.9,
+                    gain: 1.125,
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_same_filters(&profile, &reparse_config(&profile));
+    }
+
+    #[test]
+    fn config_round_trips_arbitrary_values() {
+        // Deterministic pseudo-random values covering the full f32 mantissa
+        let mut seed: u32 = 0x1234_5678;
+        let mut next = move |low: f32, high: f32| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            low + (high - low) * (seed >> 8) as f32 / (1u32 << 24) as f32
+        };
+
+        for _ in 0..50 {
+            let profile = EqProfile {
+                preamp: next(-20.0, 0.0),
+                bands: (0..10)
+                    .map(|_| ParametricBand {
+                        filter_type: FilterType::Peaking,
+                        frequency: next(20.0, 20000.0),
+                        gain: next(-12.0, 12.0),
+                        q_factor: next(0.1, 10.0),
+                        enabled: true,
+                    })
+                    .collect(),
+                ..Default::default()
+            };
+            assert_same_filters(&profile, &reparse_config(&profile));
+        }
+    }
+
+    #[test]
+    fn saved_profile_json_round_trips_before_config() {
+        let profile = EqProfile {
+            name: "Round Trip".to_string(),
+            preamp: -3.3,
+            bands: vec![ParametricBand {
+                filter_type: FilterType::Peaking,
+                frequency: 62.75,
+                gain: -0.35,
+                q_factor: 4.321,
+                enabled: true,
+            }],
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string_pretty(&profile).unwrap();
+        let reloaded: EqProfile = serde_json::from_str(&json).unwrap();
+        assert_same_filters(&profile, &reloaded);
+        assert_same_filters(&profile, &reparse_config(&reloaded));
+    }
 }
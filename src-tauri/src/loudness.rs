@@ -0,0 +1,331 @@
+//! Volume-dependent loudness compensation.
+//!
+//! The ear loses sensitivity to bass (and, less so, to the top octave) faster
+//! than to the midrange as the level drops, so content balanced at a loud
+//! reference level sounds thin when played quietly. The ISO 226:2003
+//! equal-loudness contours describe by how much: the compensation at each
+//! frequency is the difference between the contour at the listening level
+//! and the contour at the reference level, normalized to 0 dB at 1 kHz.
+//!
+//! The curve is approximated with a few shelf and peaking bands using
+//! [`fit_bands`], which are appended after the profile's own bands. The
+//! preamp is lowered by the largest boost, so compensation never clips.
+
+use tauri::AppHandle;
+
+use crate::actions::{apply_settings, notify_settings_changed};
+use crate::dsp::DEFAULT_SAMPLE_RATE;
+use crate::fitting::{fit_bands, FitConfig, FitResult};
+use crate::profile::save_settings;
+use crate::types::{AppState, EqProfile, GraphicEqPoint, LoudnessSettings};
+use crate::validation;
+
+// =============================================================================
+// ISO 226:2003
+// =============================================================================
+
+/// Frequencies of the ISO 226:2003 parameter table, in Hz.
+const ISO_FREQUENCIES: [f64; 29] = [
+    20.0, 25.0, 31.5, 40.0, 50.0, 63.0, 80.0, 100.0, 125.0, 160.0, 200.0, 250.0, 315.0, 400.0,
+    500.0, 630.0, 800.0, 1000.0, 1250.0, 1600.0, 2000.0, 2500.0, 3150.0, 4000.0, 5000.0, 6300.0,
+    8000.0, 10000.0, 12500.0,
+];
+
+/// Exponent for loudness perception (`α_f`).
+const ALPHA_F: [f64; 29] = [
+    0.532, 0.506, 0.480, 0.455, 0.432, 0.409, 0.387, 0.367, 0.349, 0.330, 0.315, 0.301, 0.288,
+    0.276, 0.267, 0.259, 0.253, 0.250, 0.246, 0.244, 0.243, 0.243, 0.243, 0.242, 0.242, 0.245,
+    0.254, 0.271, 0.301,
+];
+
+/// Magnitude of the linear transfer function normalized at 1 kHz (`L_U`),
+/// in dB.
+const TRANSFER_DB: [f64; 29] = [
+    -31.6, -27.2, -23.0, -19.1, -15.9, -13.0, -10.3, -8.1, -6.2, -4.5, -3.1, -2.0, -1.1, -0.4, 0.0,
+    0.3, 0.5, 0.0, -2.7, -4.1, -1.0, 1.7, 2.5, 1.2, -2.1, -7.1, -11.2, -10.7, -3.1,
+];
+
+/// Threshold of hearing (`T_f`), in dB SPL.
+const THRESHOLD_DB: [f64; 29] = [
+    78.5, 68.7, 59.5, 51.1, 44.0, 37.5, 31.5, 26.5, 22.1, 17.9, 14.4, 11.4, 8.6, 6.2, 4.4, 3.0,
+    2.2, 2.4, 3.5, 1.7, -1.3, -4.2, -6.0, -5.4, -1.5, 6.0, 12.6, 13.9, 12.3,
+];
+
+/// Index of 1 kHz in the table, where compensation is 0 dB.
+const REFERENCE_INDEX: usize = 17;
+
+/// Loudness levels covered by the standard, in phon.
+pub const LEVEL_RANGE: (f32, f32) = (20.0, 90.0);
+
+/// Reference levels accepted in the settings, in phon.
+pub const REFERENCE_RANGE: (f32, f32) = (40.0, 90.0);
+
+// =============================================================================
+// Fitting
+// =============================================================================
+
+/// Number of bands used to approximate the compensation curve.
+const COMPENSATION_BANDS: usize = 3;
+
+/// Largest gain of a single compensation band, in dB.
+const MAX_BAND_GAIN: f32 = 24.0;
+
+/// Compensation smaller than this everywhere produces no bands, in dB.
+const MIN_COMPENSATION_DB: f64 = 0.1;
+
+// =============================================================================
+// Curves
+// =============================================================================
+
+/// Returns the sound pressure level in dB at each table frequency that is
+/// as loud as a 1 kHz tone at `phon`.
+pub fn equal_loudness_contour(phon: f64) -> [f64; 29] {
+    std::array::from_fn(|i| {
+        let af = 4.47e-3 * (10f64.powf(0.025 * phon) - 1.15)
+            + (0.4 * 10f64.powf((THRESHOLD_DB[i] + TRANSFER_DB[i]) / 10.0 - 9.0)).powf(ALPHA_F[i]);
+        10.0 / ALPHA_F[i] * af.log10() - TRANSFER_DB[i] + 94.0
+    })
+}
+
+/// Checks that the reference and listening levels are within range.
+///
+/// # Errors
+///
+/// Returns an error if a value is not finite, the reference level is
+/// outside [`REFERENCE_RANGE`], or the listening level (reference plus
+/// volume) is outside [`LEVEL_RANGE`].
+pub fn validate_settings(loudness: &LoudnessSettings) -> Result<(), String> {
+    let (min_reference, max_reference) = REFERENCE_RANGE;
+    if !(min_reference..=max_reference).contains(&loudness.reference_level) {
+        return Err(format!(
+            "Reference level must be between {} and {} phon, got {}",
+            min_reference, max_reference, loudness.reference_level
+        ));
+    }
+    let (min_level, max_level) = LEVEL_RANGE;
+    let level = loudness.reference_level + loudness.volume;
+    if !(min_level..=max_level).contains(&level) {
+        return Err(format!(
+            "Listening level must be between {} and {} phon, got {} ({} {:+} dB)",
+            min_level, max_level, level, loudness.reference_level, loudness.volume
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the compensation curve for the settings, 0 dB at 1 kHz.
+///
+/// The curve has one point per ISO 226 table frequency (20 Hz–12.5 kHz).
+///
+/// # Errors
+///
+/// Returns an error if the settings are out of range.
+pub fn compensation_curve(loudness: &LoudnessSettings) -> Result<Vec<GraphicEqPoint>, String> {
+    validate_settings(loudness)?;
+    let reference = equal_loudness_contour(loudness.reference_level.into());
+    let current = equal_loudness_contour(f64::from(loudness.reference_level + loudness.volume));
+    let at_1k = current[REFERENCE_INDEX] - reference[REFERENCE_INDEX];
+
+    Ok(ISO_FREQUENCIES
+        .iter()
+        .enumerate()
+        .map(|(i, &frequency)| GraphicEqPoint {
+            frequency: frequency as f32,
+            gain: (current[i] - reference[i] - at_1k) as f32,
+        })
+        .collect())
+}
+
+/// Approximates the compensation curve with shelf and peaking bands.
+///
+/// Returns no bands when listening at the reference level.
+///
+/// # Errors
+///
+/// Returns an error if the settings are out of range.
+pub fn compensation_bands(loudness: &LoudnessSettings) -> Result<FitResult, String> {
+    let curve = compensation_curve(loudness)?;
+    if curve
+        .iter()
+        .all(|p| f64::from(p.gain).abs() < MIN_COMPENSATION_DB)
+    {
+        return Ok(FitResult {
+            bands: Vec::new(),
+            preamp: 0.0,
+            rms_error: 0.0,
+            max_error: 0.0,
+        });
+    }
+
+    let config = FitConfig {
+        num_bands: COMPENSATION_BANDS,
+        min_frequency: ISO_FREQUENCIES[0] as f32,
+        max_frequency: ISO_FREQUENCIES[ISO_FREQUENCIES.len() - 1] as f32,
+        min_q: 0.3,
+        max_q: 2.0,
+        max_gain: MAX_BAND_GAIN,
+        allow_shelves: true,
+        sample_rate: DEFAULT_SAMPLE_RATE,
+    };
+    fit_bands(&curve, &config)
+}
+
+/// Appends the compensation bands to a profile if enabled in the settings,
+/// and lowers its preamp by their largest boost.
+///
+/// # Errors
+///
+/// Returns an error if compensation is enabled and the settings are out of
+/// range.
+pub fn apply_compensation(
+    profile: &mut EqProfile,
+    loudness: &LoudnessSettings,
+) -> Result<(), String> {
+    if !loudness.enabled {
+        return Ok(());
+    }
+    let compensation = compensation_bands(loudness)?;
+    profile.bands.extend(compensation.bands);
+    profile.preamp += compensation.preamp;
+    Ok(())
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Generates the compensation bands for a reference level and volume.
+///
+/// # Arguments
+///
+/// * `reference_level` - Reference listening level in phon
+/// * `volume` - Volume relative to the reference in dB
+///
+/// # Returns
+///
+/// The bands, the preamp needed to avoid clipping and the fit error.
+///
+/// # Errors
+///
+/// Returns an error if the levels are out of range.
+#[tauri::command]
+pub fn get_loudness_compensation(reference_level: f32, volume: f32) -> Result<FitResult, String> {
+    compensation_bands(&LoudnessSettings {
+        enabled: true,
+        reference_level,
+        volume,
+    })
+}
+
+/// Sets the loudness compensation settings and rewrites the live config.
+///
+/// Call this whenever the volume changes so the compensation follows it.
+///
+/// # Arguments
+///
+/// * `loudness` - Enabled flag, reference level and current volume
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for notifications
+///
+/// # Errors
+///
+/// Returns an error if the levels are out of range, or the config or
+/// settings cannot be written.
+#[tauri::command]
+pub fn set_loudness(
+    loudness: LoudnessSettings,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    validate_settings(&loudness)?;
+    let sample_rate = validation::resolve_sample_rate(&state);
+    {
+        let mut settings = state.settings.lock();
+        let mut updated = settings.clone();
+        updated.loudness = loudness;
+        apply_settings(&mut updated, sample_rate)?;
+        *settings = updated;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsp;
+    use crate::graphic_eq;
+
+    fn settings(volume: f32) -> LoudnessSettings {
+        LoudnessSettings {
+            enabled: true,
+            volume,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn contour_matches_standard() {
+        let contour = equal_loudness_contour(40.0);
+        assert!((contour[REFERENCE_INDEX] - 40.0).abs() < 0.2);
+        // 40 phon: 99.85 dB SPL at 20 Hz, 64.37 dB at 100 Hz (ISO 226:2003)
+        assert!((contour[0] - 99.85).abs() < 0.1, "{}", contour[0]);
+        assert!((contour[7] - 64.37).abs() < 0.1, "{}", contour[7]);
+    }
+
+    #[test]
+    fn quiet_listening_boosts_bass() {
+        let curve = compensation_curve(&settings(-30.0)).unwrap();
+        let gain = |freq: f64| graphic_eq::gain_at(&curve, freq);
+        assert!(gain(1000.0).abs() < 1e-3);
+        assert!(gain(50.0) > 10.0, "{}", gain(50.0));
+        assert!(gain(100.0) > 5.0, "{}", gain(100.0));
+        assert!(gain(3000.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn reference_level_needs_no_bands() {
+        let result = compensation_bands(&settings(0.0)).unwrap();
+        assert!(result.bands.is_empty());
+        assert_eq!(result.preamp, 0.0);
+    }
+
+    #[test]
+    fn bands_follow_curve_without_clipping() {
+        let loudness = settings(-25.0);
+        let result = compensation_bands(&loudness).unwrap();
+        assert!(!result.bands.is_empty() && result.bands.len() <= COMPENSATION_BANDS);
+        assert!(result.max_error < 1.5, "{}", result.max_error);
+
+        let mut profile = EqProfile::default();
+        apply_compensation(&mut profile, &loudness).unwrap();
+        let peak = dsp::peak_gain_db(&profile.bands, &[], profile.preamp, DEFAULT_SAMPLE_RATE);
+        assert!(peak <= 0.01, "{}", peak);
+
+        // Disabled compensation leaves the profile alone
+        let mut untouched = EqProfile::default();
+        let disabled = LoudnessSettings {
+            enabled: false,
+            ..loudness
+        };
+        apply_compensation(&mut untouched, &disabled).unwrap();
+        assert!(untouched.bands.is_empty());
+    }
+
+    #[test]
+    fn rejects_levels_outside_the_standard() {
+        assert!(validate_settings(&settings(-70.0)).is_err());
+        assert!(validate_settings(&settings(10.0)).is_err());
+        assert!(validate_settings(&settings(f32::NAN)).is_err());
+        let low_reference = LoudnessSettings {
+            reference_level: 30.0,
+            ..settings(0.0)
+        };
+        assert!(validate_settings(&low_reference).is_err());
+    }
+}
@@ -0,0 +1,524 @@
+//! Frequency-response measurements and automatic correction.
+//!
+//! Measurements are read from REW text exports, `.frd` files and AutoEQ
+//! CSV files. [`generate_correction`] compares a measurement against a
+//! [`Target`], optionally after fractional-octave smoothing, and fits
+//! parametric bands so that measurement + EQ matches the target as closely
+//! as the band, gain and Q limits allow.
+//!
+//! # Supported Formats
+//!
+//! | Format     | Layout                                                  |
+//! |------------|---------------------------------------------------------|
+//! | REW `.txt` | `*` comment header, then `freq spl [phase]` per line    |
+//! | `.frd`     | `freq spl [phase]` per line, `*`/`#`/`;` comments        |
+//! | AutoEQ CSV | `frequency,raw,...` header; the `raw` column is used     |
+//!
+//! Columns may be separated by spaces, tabs, commas or semicolons.
+//!
+//! # Level Alignment
+//!
+//! Measurements and targets rarely share an absolute level, so the
+//! measurement is shifted to match the target on average between
+//! [`ALIGN_MIN_HZ`] and [`ALIGN_MAX_HZ`] before the correction is computed.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dsp;
+use crate::fitting::{fit_bands, FitConfig};
+use crate::graphic_eq::gain_at;
+use crate::targets::Target;
+use crate::types::{EqProfile, GraphicEqPoint, ProfileSource};
+
+/// Lower edge of the band used to align measurement and target levels, in Hz.
+pub const ALIGN_MIN_HZ: f64 = 500.0;
+
+/// Upper edge of the band used to align measurement and target levels, in Hz.
+pub const ALIGN_MAX_HZ: f64 = 2000.0;
+
+/// Largest smoothing denominator accepted (1/96 octave).
+pub const MAX_SMOOTHING_FRACTION: u32 = 96;
+
+/// Number of log-spaced points the correction curve is computed on
+/// (about 1/48 octave resolution).
+const CORRECTION_POINTS: usize = 480;
+
+/// File format of a measurement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeasurementFormat {
+    /// Room EQ Wizard text export.
+    RewText,
+    /// Frequency response data (`.frd`).
+    Frd,
+    /// AutoEQ measurement CSV.
+    AutoEqCsv,
+}
+
+impl MeasurementFormat {
+    /// Detects the format from the file extension and content.
+    pub fn detect(path: &Path, content: &str) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("frd") => MeasurementFormat::Frd,
+            Some("csv") => MeasurementFormat::AutoEqCsv,
+            _ if content.trim_start().starts_with("frequency,") => MeasurementFormat::AutoEqCsv,
+            _ => MeasurementFormat::RewText,
+        }
+    }
+
+    /// Returns the format identifier stored in [`ProfileSource::format`] of
+    /// corrections generated from this measurement.
+    pub fn format_id(self) -> &'static str {
+        match self {
+            MeasurementFormat::RewText => "rew-measurement",
+            MeasurementFormat::Frd => "frd-measurement",
+            MeasurementFormat::AutoEqCsv => "autoeq-measurement",
+        }
+    }
+}
+
+/// A frequency-response measurement.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Measurement {
+    /// Name derived from the file name.
+    pub name: String,
+    /// Detected file format.
+    pub format: MeasurementFormat,
+    /// Measured level in dB at each frequency, sorted by frequency.
+    pub points: Vec<GraphicEqPoint>,
+}
+
+/// A generated correction.
+#[derive(Debug, Clone, Serialize)]
+pub struct Correction {
+    /// The fitted profile, with a preamp that avoids clipping.
+    pub profile: EqProfile,
+    /// The ideal correction (target minus aligned measurement) that the
+    /// profile approximates.
+    pub correction: Vec<GraphicEqPoint>,
+    /// RMS difference between measurement + EQ and the target, in dB.
+    pub rms_error: f32,
+    /// Largest difference between measurement + EQ and the target, in dB.
+    pub max_error: f32,
+}
+
+// =============================================================================
+// Parsing
+// =============================================================================
+
+/// Returns the index of the first header field matching any of `names`.
+fn find_column(headers: &[String], names: &[&str]) -> Option<usize> {
+    headers
+        .iter()
+        .position(|h| names.iter().any(|name| h.contains(name)))
+}
+
+/// Parses a frequency/level curve from measurement or target file content.
+///
+/// Comment lines start with `*`, `#`, `;` or `//`. An optional header line
+/// selects the columns: the first one containing `freq` or `hz` for the
+/// frequency, and `raw` if present (AutoEQ), otherwise the first containing
+/// `spl`, `db`, `magnitude`, `gain` or `value`. Without a header the first
+/// two columns are used. Points at or below 0 Hz are skipped, and repeated
+/// frequencies keep their first value.
+///
+/// # Errors
+///
+/// Returns an error naming the line if a data line is malformed, or if fewer
+/// than two points are found.
+pub fn parse_curve(content: &str) -> Result<Vec<GraphicEqPoint>, String> {
+    let (mut freq_column, mut value_column) = (0, 1);
+    let mut points: Vec<GraphicEqPoint> = Vec::new();
+    let mut header_seen = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        if line.is_empty() || line.starts_with(['*', '#', ';']) || line.starts_with("//") {
+            continue;
+        }
+
+        let fields: Vec<&str> = line
+            .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+            .filter(|f| !f.is_empty())
+            .collect();
+
+        if fields[0].parse::<f64>().is_err() {
+            if header_seen || !points.is_empty() {
+                return Err(format!("Line {}: unexpected text '{}'", index + 1, line));
+            }
+            let headers: Vec<String> = fields.iter().map(|f| f.to_ascii_lowercase()).collect();
+            freq_column = find_column(&headers, &["freq", "hz"]).unwrap_or(0);
+            value_column = headers
+                .iter()
+                .position(|h| h == "raw")
+                .or_else(|| find_column(&headers, &["spl", "db", "magnitude", "gain", "value"]))
+                .unwrap_or(if freq_column == 0 { 1 } else { 0 });
+            header_seen = true;
+            continue;
+        }
+
+        let parse = |column: usize, field: &str| {
+            fields
+                .get(column)
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("Line {}: missing or invalid {}", index + 1, field))
+        };
+        let frequency = parse(freq_column, "frequency")?;
+        let gain = parse(value_column, "level")?;
+        if frequency > 0.0 {
+            points.push(GraphicEqPoint {
+                frequency: frequency as f32,
+                gain: gain as f32,
+            });
+        }
+    }
+
+    points.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+    points.dedup_by(|later, earlier| later.frequency == earlier.frequency);
+    if points.len() < 2 {
+        return Err("Curve must have at least two points".to_string());
+    }
+    Ok(points)
+}
+
+/// Reads a frequency/level curve from a file. See [`parse_curve`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed.
+pub fn read_curve_file(path: &Path) -> Result<Vec<GraphicEqPoint>, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    parse_curve(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Reads a measurement file, detecting its format.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed.
+pub fn read_measurement(path: &Path) -> Result<Measurement, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    Ok(Measurement {
+        name: path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string(),
+        format: MeasurementFormat::detect(path, &content),
+        points: parse_curve(&content)?,
+    })
+}
+
+// =============================================================================
+// Smoothing and Correction
+// =============================================================================
+
+/// Applies 1/`fraction` octave smoothing.
+///
+/// Each point becomes the average level (in dB) of all points within half
+/// the smoothing width on either side. `fraction` 0 returns the curve
+/// unchanged.
+pub fn smooth(points: &[GraphicEqPoint], fraction: u32) -> Vec<GraphicEqPoint> {
+    if fraction == 0 {
+        return points.to_vec();
+    }
+
+    let half_width = 2f64.powf(1.0 / (2.0 * fraction as f64));
+    let mut prefix = Vec::with_capacity(points.len() + 1);
+    prefix.push(0.0f64);
+    for point in points {
+        prefix.push(prefix.last().unwrap() + point.gain as f64);
+    }
+
+    points
+        .iter()
+        .map(|point| {
+            let f = point.frequency as f64;
+            let lo = points.partition_point(|p| (p.frequency as f64) < f / half_width);
+            let hi = points.partition_point(|p| (p.frequency as f64) <= f * half_width);
+            GraphicEqPoint {
+                frequency: point.frequency,
+                gain: ((prefix[hi] - prefix[lo]) / (hi - lo) as f64) as f32,
+            }
+        })
+        .collect()
+}
+
+/// Computes the ideal correction: target minus the level-aligned
+/// measurement, on a log-spaced grid from 20 Hz to 20 kHz.
+pub fn correction_curve(
+    measurement: &[GraphicEqPoint],
+    target: &[GraphicEqPoint],
+) -> Vec<GraphicEqPoint> {
+    let grid = dsp::log_spaced(CORRECTION_POINTS, dsp::FREQ_MIN, dsp::FREQ_MAX);
+    let diffs: Vec<f64> = grid
+        .iter()
+        .map(|&f| gain_at(target, f) - gain_at(measurement, f))
+        .collect();
+
+    let aligned: Vec<f64> = grid
+        .iter()
+        .zip(&diffs)
+        .filter(|(f, _)| (ALIGN_MIN_HZ..=ALIGN_MAX_HZ).contains(*f))
+        .map(|(_, d)| *d)
+        .collect();
+    let offset = aligned.iter().sum::<f64>() / aligned.len().max(1) as f64;
+
+    grid.iter()
+        .zip(diffs)
+        .map(|(&frequency, diff)| GraphicEqPoint {
+            frequency: frequency as f32,
+            gain: (diff - offset) as f32,
+        })
+        .collect()
+}
+
+/// Generates a parametric correction for a measurement.
+///
+/// # Arguments
+///
+/// * `measurement` - The measured response
+/// * `target` - The response to correct towards
+/// * `smoothing` - Optional 1/N octave smoothing applied to the measurement
+/// * `config` - Band count and limits for the fitted bands
+/// * `name` - Name of the generated profile
+///
+/// # Errors
+///
+/// Returns an error if the smoothing fraction, target or fit configuration
+/// is invalid.
+pub fn generate_correction(
+    measurement: &Measurement,
+    target: &Target,
+    smoothing: Option<u32>,
+    config: &FitConfig,
+    name: &str,
+) -> Result<Correction, String> {
+    let fraction = smoothing.unwrap_or(0);
+    if fraction > MAX_SMOOTHING_FRACTION {
+        return Err(format!(
+            "Smoothing must be between 1/1 and 1/{} octave, got 1/{}",
+            MAX_SMOOTHING_FRACTION, fraction
+        ));
+    }
+
+    let measured = smooth(&measurement.points, fraction);
+    let correction = correction_curve(&measured, &target.points()?);
+    let fit = fit_bands(&correction, config)?;
+
+    Ok(Correction {
+        profile: EqProfile {
+            name: name.to_string(),
+            preamp: fit.preamp,
+            bands: fit.bands,
+            ..Default::default()
+        },
+        correction,
+        rms_error: fit.rms_error,
+        max_error: fit.max_error,
+    })
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Reads a measurement file (REW text export, `.frd` or AutoEQ CSV).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed.
+#[tauri::command]
+pub fn import_measurement(path: String) -> Result<Measurement, String> {
+    read_measurement(Path::new(&path))
+}
+
+/// Generates a correction profile from a measurement file.
+///
+/// The profile is not saved; its metadata records the measurement file.
+///
+/// # Arguments
+///
+/// * `path` - Measurement file
+/// * `target` - Target curve to correct towards
+/// * `smoothing` - Optional 1/N octave smoothing of the measurement
+/// * `config` - Band count and limits; defaults to [`FitConfig::default`]
+/// * `name` - Profile name; defaults to the measurement file name
+///
+/// # Errors
+///
+/// Returns an error if the measurement cannot be read, or the target,
+/// smoothing or configuration is invalid.
+#[tauri::command]
+pub fn generate_correction_profile(
+    path: String,
+    target: Target,
+    smoothing: Option<u32>,
+    config: Option<FitConfig>,
+    name: Option<String>,
+) -> Result<Correction, String> {
+    let measurement = read_measurement(Path::new(&path))?;
+    let name = name.unwrap_or_else(|| measurement.name.clone());
+
+    let mut correction = generate_correction(
+        &measurement,
+        &target,
+        smoothing,
+        &config.unwrap_or_default(),
+        &name,
+    )?;
+    correction.profile.metadata.source = Some(ProfileSource {
+        format: measurement.format.format_id().to_string(),
+        path,
+    });
+    Ok(correction)
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fitting::rasterize;
+    use crate::types::{FilterType, ParametricBand};
+
+    fn point(frequency: f32, gain: f32) -> GraphicEqPoint {
+        GraphicEqPoint { frequency, gain }
+    }
+
+    #[test]
+    fn parses_rew_text_export() {
+        let content = "* Measurement data measured by REW V5.20\n\
+            * Freq(Hz) SPL(dB) Phase(degrees)\n\
+            0.000 70.0 0.0\n\
+            20.000 75.5 -10.2\n\
+            1000.000 80.0 5.0\n";
+        let points = parse_curve(content).unwrap();
+        assert_eq!(points, vec![point(20.0, 75.5), point(1000.0, 80.0)]);
+    }
+
+    #[test]
+    fn parses_frd_with_tabs_and_comments() {
+        let content = "# speaker.frd\n; comment\n100\t85.2\t0\n50\t83.1\t0\n50\t99\t0\n";
+        let points = parse_curve(content).unwrap();
+        assert_eq!(points, vec![point(50.0, 83.1), point(100.0, 85.2)]);
+    }
+
+    #[test]
+    fn parses_autoeq_csv_raw_column() {
+        let content = "frequency,error,raw,smoothed\n20.0,1.0,-3.5,-3.0\n1000.0,0.0,0.25,0.0\n";
+        let points = parse_curve(content).unwrap();
+        assert_eq!(points, vec![point(20.0, -3.5), point(1000.0, 0.25)]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse_curve("20 1\n").is_err());
+        let err = parse_curve("20 1\n100 2\nhello\n").unwrap_err();
+        assert!(err.starts_with("Line 3"), "{}", err);
+        assert!(parse_curve("20 1\n100\n").is_err());
+    }
+
+    #[test]
+    fn detects_format() {
+        assert_eq!(
+            MeasurementFormat::detect(Path::new("a.frd"), ""),
+            MeasurementFormat::Frd
+        );
+        assert_eq!(
+            MeasurementFormat::detect(Path::new("a.txt"), "frequency,raw\n"),
+            MeasurementFormat::AutoEqCsv
+        );
+        assert_eq!(
+            MeasurementFormat::detect(Path::new("a.txt"), "* REW\n"),
+            MeasurementFormat::RewText
+        );
+    }
+
+    #[test]
+    fn smoothing_averages_within_window() {
+        let ripple: Vec<GraphicEqPoint> = (0..200)
+            .map(|i| point(100.0 + i as f32, if i % 2 == 0 { 1.0 } else { -1.0 }))
+            .collect();
+        let smoothed = smooth(&ripple, 3);
+        assert!(smoothed[100].gain.abs() < 0.05);
+        assert_eq!(smooth(&ripple, 0), ripple);
+    }
+
+    #[test]
+    fn correction_is_aligned_to_target() {
+        let measurement = [point(20.0, 80.0), point(20000.0, 80.0)];
+        let target = [point(20.0, 0.0), point(20000.0, 0.0)];
+        let correction = correction_curve(&measurement, &target);
+        assert!(correction.iter().all(|p| p.gain.abs() < 1e-4));
+    }
+
+    #[test]
+    fn generated_correction_cancels_a_dip() {
+        // Flat measurement at 75 dB with a 6 dB dip at 2 kHz
+        let dip = ParametricBand {
+            filter_type: FilterType::Peaking,
+            frequency: 2000.0,
+            gain: -6.0,
+            q_factor: 2.0,
+            enabled: true,
+        };
+        let grid = dsp::log_spaced(300, 20.0, 20000.0);
+        let grid: Vec<f32> = grid.iter().map(|&f| f as f32).collect();
+        let points = rasterize(&[dip], &grid, dsp::DEFAULT_SAMPLE_RATE)
+            .into_iter()
+            .map(|p| point(p.frequency, p.gain + 75.0))
+            .collect();
+        let measurement = Measurement {
+            name: "Speaker".to_string(),
+            format: MeasurementFormat::Frd,
+            points,
+        };
+        let config = FitConfig {
+            num_bands: 2,
+            ..Default::default()
+        };
+
+        let result =
+            generate_correction(&measurement, &Target::Flat, None, &config, "Fix").unwrap();
+        assert_eq!(result.profile.name, "Fix");
+        assert!(result.rms_error < 0.3, "{:?}", result);
+        let main = result
+            .profile
+            .bands
+            .iter()
+            .max_by(|a, b| a.gain.total_cmp(&b.gain))
+            .unwrap();
+        assert!((main.frequency - 2000.0).abs() < 100.0, "{:?}", main);
+        assert!(result.profile.preamp < 0.0);
+    }
+
+    #[test]
+    fn rejects_excessive_smoothing() {
+        let measurement = Measurement {
+            name: "m".to_string(),
+            format: MeasurementFormat::Frd,
+            points: vec![point(20.0, 0.0), point(20000.0, 0.0)],
+        };
+        let result = generate_correction(
+            &measurement,
+            &Target::Flat,
+            Some(500),
+            &FitConfig::default(),
+            "x",
+        );
+        assert!(result.is_err());
+    }
+}
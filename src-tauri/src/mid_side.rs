@@ -0,0 +1,304 @@
+//! Mid/side EQ and stereo width.
+//!
+//! Left and right are matrixed into a mid (sum) and a side (difference)
+//! signal, each with its own bands, and matrixed back with the side signal
+//! scaled by the stereo width:
+//!
+//! ```text
+//! Copy: MID=0.5*L+0.5*R SIDE=0.5*L+-0.5*R
+//! Channel: MID
+//! Filter: …                              ; mid bands
+//! Channel: SIDE
+//! Filter: …                              ; side bands
+//! Copy: L=MID+1.2*SIDE R=MID+-1.2*SIDE
+//! Channel: all
+//! ```
+//!
+//! With a width of 1 and no bands the stage passes the signal through
+//! unchanged. A width of 0 folds the stereo image to mono; widths above 1
+//! exaggerate it and can raise peak levels on wide recordings.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::actions::notify_settings_changed;
+use crate::dsp;
+use crate::profile::{load_profile, save_settings, write_profile};
+use crate::types::{format_config_number, AppState, ConfigPrecision, ParametricBand};
+use crate::validation;
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Virtual channels holding the mid and side signals.
+const MID: &str = "MID";
+const SIDE: &str = "SIDE";
+
+/// Allowed stereo width range; 1 leaves the image unchanged.
+pub const WIDTH_RANGE: (f32, f32) = (0.0, 2.0);
+
+// =============================================================================
+// Types
+// =============================================================================
+
+fn default_width() -> f32 {
+    1.0
+}
+
+/// Mid/side stage of a profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MidSide {
+    /// Bands applied to the mid (L + R) signal.
+    #[serde(default)]
+    pub mid_bands: Vec<ParametricBand>,
+
+    /// Bands applied to the side (L − R) signal.
+    #[serde(default)]
+    pub side_bands: Vec<ParametricBand>,
+
+    /// Gain of the side signal when matrixing back to left and right.
+    #[serde(default = "default_width")]
+    pub width: f32,
+}
+
+impl Default for MidSide {
+    fn default() -> Self {
+        Self {
+            mid_bands: Vec::new(),
+            side_bands: Vec::new(),
+            width: default_width(),
+        }
+    }
+}
+
+impl MidSide {
+    /// Checks the width and bands.
+    ///
+    /// Bands are checked as in [`validation::check_bands`] against
+    /// `sample_rate`, usually from [`validation::resolve_sample_rate`];
+    /// values are never clamped here.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the width is out of range or a band has an
+    /// error-level issue.
+    pub fn validate(&self, sample_rate: u32) -> Result<(), String> {
+        let (min_width, max_width) = WIDTH_RANGE;
+        if !(min_width..=max_width).contains(&self.width) {
+            return Err(format!(
+                "Stereo width must be between {} and {}, got {}",
+                min_width, max_width, self.width
+            ));
+        }
+        for (label, bands) in [("Mid", &self.mid_bands), ("Side", &self.side_bands)] {
+            let report = validation::check_bands(&mut bands.clone(), sample_rate, false);
+            if report.has_errors() {
+                return Err(format!("{} bands: {}", label, report.error_message()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the EqualizerAPO directives for the stage.
+    ///
+    /// Disabled bands are skipped. The last line resets the channel
+    /// selection to `all`, so filters that follow apply to every channel
+    /// again.
+    #[must_use]
+    pub fn config_lines(&self, precision: &ConfigPrecision) -> Vec<String> {
+        let width = format_config_number(self.width, None, 0);
+        let mut lines = vec![
+            format!("; Mid/side: width {}", width),
+            format!("Copy: {m}=0.5*L+0.5*R {s}=0.5*L+-0.5*R", m = MID, s = SIDE),
+        ];
+        for (channel, bands) in [(MID, &self.mid_bands), (SIDE, &self.side_bands)] {
+            if bands.iter().any(|band| band.enabled) {
+                lines.push(format!("Channel: {}", channel));
+                lines.extend(
+                    bands
+                        .iter()
+                        .filter(|band| band.enabled)
+                        .map(|band| band.to_eapo_line_with(precision)),
+                );
+            }
+        }
+        lines.extend([
+            format!(
+                "Copy: L={m}+{w}*{s} R={m}+-{w}*{s}",
+                m = MID,
+                s = SIDE,
+                w = width
+            ),
+            String::from("Channel: all"),
+        ]);
+        lines
+    }
+
+    /// Returns the largest gain in dB the stage can apply to either output
+    /// channel at each frequency.
+    ///
+    /// `L = M + w·S` with `|M| + |S| ≤ 1` for full-scale input, so the worst
+    /// case is whichever of the mid path and the scaled side path is louder.
+    pub fn response_db(&self, freqs: &[f64], sample_rate: f64) -> Vec<f64> {
+        let mid = dsp::response_db(&self.mid_bands, &[], 0.0, freqs, sample_rate);
+        let side = dsp::response_db(&self.side_bands, &[], 0.0, freqs, sample_rate);
+        let width_db = 20.0 * f64::from(self.width).log10();
+
+        mid.iter()
+            .zip(&side)
+            .map(|(m, s)| m.max(s + width_db))
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Sets or removes the mid/side stage of a saved profile.
+///
+/// # Arguments
+///
+/// * `name` - The profile name
+/// * `mid_side` - The new stage, or `None` to remove it
+/// * `state` - Tauri managed state, for the validation sample rate
+///
+/// # Errors
+///
+/// Returns an error if the stage is invalid or the profile cannot be loaded
+/// or written.
+#[tauri::command]
+pub fn set_profile_mid_side(
+    name: String,
+    mid_side: Option<MidSide>,
+    state: tauri::State<AppState>,
+) -> Result<(), String> {
+    if let Some(mid_side) = &mid_side {
+        mid_side.validate(validation::resolve_sample_rate(&state))?;
+    }
+    let mut profile = load_profile(name)?;
+    profile.mid_side = mid_side;
+    write_profile(&profile)
+}
+
+/// Sets or removes the mid/side stage of the current EQ.
+///
+/// The stage is written to the config on the next apply.
+///
+/// # Arguments
+///
+/// * `mid_side` - The new stage, or `None` to remove it
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for notifications
+///
+/// # Errors
+///
+/// Returns an error if the stage is invalid or settings cannot be saved.
+#[tauri::command]
+pub fn set_mid_side(
+    mid_side: Option<MidSide>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if let Some(mid_side) = &mid_side {
+        mid_side.validate(validation::resolve_sample_rate(&state))?;
+    }
+    {
+        let mut settings = state.settings.lock();
+        settings.mid_side = mid_side;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_band, FilterType};
+
+    #[test]
+    fn matrixes_through_mid_and_side() {
+        let stage = MidSide {
+            mid_bands: vec![
+                test_band(FilterType::Peaking, 100.0, 2.0, 1.0),
+                ParametricBand {
+                    enabled: false,
+                    ..test_band(FilterType::Peaking, 200.0, 1.0, 1.0)
+                },
+            ],
+            side_bands: vec![test_band(FilterType::Peaking, 8000.0, -1.5, 1.0)],
+            width: 1.25,
+        };
+        let lines = stage.config_lines(&ConfigPrecision::default());
+
+        assert_eq!(lines[1], "Copy: MID=0.5*L+0.5*R SIDE=0.5*L+-0.5*R");
+        assert_eq!(lines[2], "Channel: MID");
+        assert_eq!(lines[3], "Filter: ON PK Fc 100 Hz Gain 2.0 dB Q 1.00");
+        assert_eq!(lines[4], "Channel: SIDE");
+        assert_eq!(lines[5], "Filter: ON PK Fc 8000 Hz Gain -1.5 dB Q 1.00");
+        assert_eq!(lines[6], "Copy: L=MID+1.25*SIDE R=MID+-1.25*SIDE");
+        assert_eq!(lines[7], "Channel: all");
+        assert_eq!(lines.len(), 8);
+    }
+
+    #[test]
+    fn width_only_stage_has_no_channel_sections() {
+        let stage = MidSide {
+            width: 0.0,
+            ..Default::default()
+        };
+        let lines = stage.config_lines(&ConfigPrecision::default());
+        assert_eq!(lines[2], "Copy: L=MID+0*SIDE R=MID+-0*SIDE");
+        assert!(!lines.iter().any(|l| l.starts_with("Channel: MID")));
+    }
+
+    #[test]
+    fn rejects_invalid_width_and_bands() {
+        assert!(MidSide::default().validate(48000).is_ok());
+        let wide = MidSide {
+            width: 2.5,
+            ..Default::default()
+        };
+        assert!(wide.validate(48000).is_err());
+        let bad_band = MidSide {
+            side_bands: vec![test_band(FilterType::Peaking, f32::NAN, 0.0, 1.0)],
+            ..Default::default()
+        };
+        let err = bad_band.validate(48000).unwrap_err();
+        assert!(err.starts_with("Side bands"), "{}", err);
+    }
+
+    #[test]
+    fn checks_bands_against_sample_rate() {
+        let stage = MidSide {
+            side_bands: vec![test_band(FilterType::Peaking, 22000.0, 2.0, 1.0)],
+            ..Default::default()
+        };
+        assert!(stage.validate(48000).is_ok());
+        assert!(stage.validate(44100).is_err());
+    }
+
+    #[test]
+    fn response_takes_louder_of_mid_and_scaled_side() {
+        let stage = MidSide {
+            mid_bands: vec![test_band(FilterType::Peaking, 100.0, 3.0, 1.0)],
+            width: 2.0,
+            ..Default::default()
+        };
+        let response = stage.response_db(&[100.0, 10000.0], 48000.0);
+        assert!((response[0] - 6.02).abs() < 0.05, "{:?}", response);
+        assert!((response[1] - 6.02).abs() < 0.05, "{:?}", response);
+
+        let mono = MidSide {
+            width: 0.0,
+            ..Default::default()
+        };
+        assert!(mono.response_db(&[1000.0], 48000.0)[0].abs() < 1e-9);
+    }
+}
@@ -0,0 +1,187 @@
+//! Error reporting for actions triggered outside the main window.
+//!
+//! The tray menu, global hotkeys, forwarded launch arguments and the
+//! background automation and OSC servers have no caller to return an error
+//! to. Their failures are passed to [`report_error`], which records them in a
+//! persistent error log (`error_log.json` in the app directory) and emits an
+//! `action-error` event so the frontend can show a notification.
+//!
+//! # Events
+//!
+//! | Event          | Payload           | Emitted when                     |
+//! |----------------|-------------------|----------------------------------|
+//! | `action-error` | [`ErrorLogEntry`] | A background action failed       |
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::profile::ensure_dirs;
+use crate::types::AppState;
+
+/// Maximum number of entries kept in the error log; older entries are dropped.
+pub const MAX_ERROR_LOG_ENTRIES: usize = 100;
+
+/// Where a reported error came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSource {
+    /// The system tray menu.
+    Tray,
+    /// A global keyboard shortcut.
+    Hotkey,
+    /// Arguments passed on the command line or forwarded by a second instance.
+    LaunchArgs,
+    /// The local automation API.
+    Automation,
+    /// The OSC control surface listener.
+    Osc,
+}
+
+/// A single failed action in the error log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorLogEntry {
+    /// When the error occurred, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Where the failed action was triggered.
+    pub source: ErrorSource,
+    /// Human-readable description of the action, e.g. `Apply profile "Rock"`.
+    pub action: String,
+    /// The error message.
+    pub message: String,
+}
+
+/// Returns the path of the persistent error log.
+fn error_log_path() -> Result<PathBuf, String> {
+    Ok(ensure_dirs()?.join("error_log.json"))
+}
+
+/// Loads the error log from disk.
+///
+/// Returns an empty log if the file doesn't exist or can't be parsed.
+pub fn load_error_log() -> Vec<ErrorLogEntry> {
+    error_log_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Writes the error log to disk.
+fn save_error_log(entries: &[ErrorLogEntry]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize error log: {}", e))?;
+    fs::write(error_log_path()?, json).map_err(|e| format!("Failed to write error log: {}", e))
+}
+
+/// Appends an entry to the log, dropping the oldest entries beyond
+/// [`MAX_ERROR_LOG_ENTRIES`].
+pub fn push_entry(log: &mut Vec<ErrorLogEntry>, entry: ErrorLogEntry) {
+    log.push(entry);
+    if log.len() > MAX_ERROR_LOG_ENTRIES {
+        let excess = log.len() - MAX_ERROR_LOG_ENTRIES;
+        log.drain(..excess);
+    }
+}
+
+/// Records a failed background action and notifies the frontend.
+///
+/// The error is also written to stderr, so it is visible even if the log
+/// file cannot be written.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle
+/// * `source` - Where the action was triggered
+/// * `action` - Human-readable description of the action
+/// * `message` - The error message
+pub fn report_error(app: &AppHandle, source: ErrorSource, action: &str, message: &str) {
+    eprintln!("{} failed: {}", action, message);
+
+    let entry = ErrorLogEntry {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        source,
+        action: action.to_string(),
+        message: message.to_string(),
+    };
+
+    {
+        let state = app.state::<AppState>();
+        let mut log = state.error_log.lock();
+        push_entry(&mut log, entry.clone());
+        if let Err(e) = save_error_log(&log) {
+            eprintln!("{}", e);
+        }
+    }
+
+    let _ = app.emit("action-error", entry);
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Returns the error log, oldest entry first.
+#[tauri::command]
+pub fn get_error_log(state: State<AppState>) -> Vec<ErrorLogEntry> {
+    state.error_log.lock().clone()
+}
+
+/// Clears the error log.
+///
+/// # Errors
+///
+/// Returns an error if the log file cannot be written.
+#[tauri::command]
+pub fn clear_error_log(state: State<AppState>) -> Result<(), String> {
+    let mut log = state.error_log.lock();
+    log.clear();
+    save_error_log(&log)
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message: &str) -> ErrorLogEntry {
+        ErrorLogEntry {
+            timestamp: 0,
+            source: ErrorSource::Tray,
+            action: "Apply profile \"Rock\"".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn push_entry_drops_oldest_beyond_limit() {
+        let mut log = Vec::new();
+        for i in 0..MAX_ERROR_LOG_ENTRIES + 3 {
+            push_entry(&mut log, entry(&i.to_string()));
+        }
+        assert_eq!(log.len(), MAX_ERROR_LOG_ENTRIES);
+        assert_eq!(log[0].message, "3");
+        assert_eq!(
+            log.last().unwrap().message,
+            (MAX_ERROR_LOG_ENTRIES + 2).to_string()
+        );
+    }
+
+    #[test]
+    fn entry_serializes_source_as_snake_case() {
+        let mut e = entry("Profile not found");
+        e.source = ErrorSource::LaunchArgs;
+        let json = serde_json::to_value(&e).unwrap();
+        assert_eq!(json["source"], "launch_args");
+        assert_eq!(json["message"], "Profile not found");
+    }
+}
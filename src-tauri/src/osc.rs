@@ -0,0 +1,1145 @@
+//! OSC (Open Sound Control) control surface support.
+//!
+//! An optional UDP listener that lets hardware and tablet controllers
+//! (TouchOSC, Open Stage Control, ...) drive the EQ. Incoming messages are
+//! mapped to [`Action`]s and run through [`crate::actions::dispatch`], and the
+//! resulting state is sent back so that faders and buttons stay in sync.
+//!
+//! # Address Space
+//!
+//! Band numbers are 1-based to match the labels on a control surface.
+//!
+//! | Address                  | Arguments             | Effect                          |
+//! |--------------------------|-----------------------|---------------------------------|
+//! | `/eq/profile`            | `s` name or `i` index | Apply a profile (index 0-based) |
+//! | `/eq/preamp`             | `f` dB                | Set the preamp                  |
+//! | `/eq/bypass`             | `i`/`f`/`T`/`F`       | Non-zero bypasses the EQ        |
+//! | `/eq/band/{n}/gain`      | `f` dB                | Set band gain                   |
+//! | `/eq/band/{n}/freq`      | `f` Hz                | Set band frequency              |
+//! | `/eq/band/{n}/q`         | `f`                   | Set band Q                      |
+//! | `/eq/band/{n}/enabled`   | `i`/`f`/`T`/`F`       | Enable or disable a band        |
+//! | `/eq/ab`                 | `s` option            | Switch A/B option               |
+//! | `/eq/ab/{option}`        | none, or non-zero     | Switch A/B option (buttons)     |
+//! | `/eq/sync`               | any                   | Request a full state update     |
+//!
+//! A/B options are the same as for [`apply_ab_option`](crate::commands::apply_ab_option):
+//! `A`, `B`, `X`, `1` and `2`. Button presses that send `0` on release are
+//! ignored, so a momentary button only triggers once.
+//!
+//! # Feedback
+//!
+//! Every controller that has recently sent an accepted message receives the
+//! following whenever settings change (from any source):
+//!
+//! - `/eq/profile s` - Current profile name (empty if none)
+//! - `/eq/preamp f`
+//! - `/eq/bypass i` - `1` when bypassed
+//! - `/eq/band/count i`
+//! - `/eq/band/{n}/gain f`, `/eq/band/{n}/freq f`, `/eq/band/{n}/q f`,
+//!   `/eq/band/{n}/enabled i`
+//!
+//! Switching an A/B option echoes `/eq/ab s` back to the controllers.
+//!
+//! Errors (e.g. an unknown profile) are reported to the sender as
+//! `/eq/error s`.
+//!
+//! # Security
+//!
+//! OSC has no authentication. The listener binds to `127.0.0.1` unless LAN
+//! access is enabled in [`OscSettings`], and even then only accepts
+//! datagrams from loopback and the configured allowlist. Datagrams from any
+//! other source are dropped without a reply. Senders only receive feedback
+//! once one of their messages has been carried out, and the feedback list is
+//! capped and expires idle controllers.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, EventId, Listener, Manager};
+
+use crate::actions::{dispatch, Action, BandPatch};
+use crate::profile::{list_profiles, save_settings};
+use crate::types::{AppSettings, AppState, OscSettings};
+
+/// Largest datagram accepted; OSC messages from controllers are tiny.
+const MAX_PACKET_SIZE: usize = 8192;
+
+/// How often the receive loop checks whether it should stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// =============================================================================
+// OSC Codec
+// =============================================================================
+
+/// A single OSC argument.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+    String(String),
+    Blob(Vec<u8>),
+    Long(i64),
+    Double(f64),
+    Bool(bool),
+    Nil,
+}
+
+impl OscArg {
+    /// Returns the argument as a number, if it is numeric or boolean.
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            OscArg::Int(v) => Some(*v as f32),
+            OscArg::Float(v) => Some(*v),
+            OscArg::Long(v) => Some(*v as f32),
+            OscArg::Double(v) => Some(*v as f32),
+            OscArg::Bool(v) => Some(if *v { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+
+    /// Returns the argument as a boolean; numbers are `true` when non-zero.
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_f32().map(|v| v != 0.0)
+    }
+}
+
+/// An OSC message: an address pattern and its arguments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub address: String,
+    pub args: Vec<OscArg>,
+}
+
+impl OscMessage {
+    pub fn new(address: impl Into<String>, args: Vec<OscArg>) -> Self {
+        Self {
+            address: address.into(),
+            args,
+        }
+    }
+
+    /// Encodes the message as an OSC packet.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_padded_str(&mut buf, &self.address);
+
+        let mut tags = String::from(",");
+        for arg in &self.args {
+            tags.push(match arg {
+                OscArg::Int(_) => 'i',
+                OscArg::Float(_) => 'f',
+                OscArg::String(_) => 's',
+                OscArg::Blob(_) => 'b',
+                OscArg::Long(_) => 'h',
+                OscArg::Double(_) => 'd',
+                OscArg::Bool(true) => 'T',
+                OscArg::Bool(false) => 'F',
+                OscArg::Nil => 'N',
+            });
+        }
+        write_padded_str(&mut buf, &tags);
+
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(v) => buf.extend_from_slice(&v.to_be_bytes()),
+                OscArg::Float(v) => buf.extend_from_slice(&v.to_be_bytes()),
+                OscArg::String(v) => write_padded_str(&mut buf, v),
+                OscArg::Blob(v) => {
+                    buf.extend_from_slice(&(v.len() as i32).to_be_bytes());
+                    buf.extend_from_slice(v);
+                    pad(&mut buf);
+                }
+                OscArg::Long(v) => buf.extend_from_slice(&v.to_be_bytes()),
+                OscArg::Double(v) => buf.extend_from_slice(&v.to_be_bytes()),
+                OscArg::Bool(_) | OscArg::Nil => {}
+            }
+        }
+
+        buf
+    }
+}
+
+/// Writes a NUL-terminated string padded to a multiple of 4 bytes.
+fn write_padded_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+    pad(buf);
+}
+
+/// Pads the buffer with NUL bytes to a multiple of 4 bytes.
+fn pad(buf: &mut Vec<u8>) {
+    while !buf.len().is_multiple_of(4) {
+        buf.push(0);
+    }
+}
+
+/// Cursor over an OSC packet.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or("Unexpected end of OSC packet")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn read_str(&mut self) -> Result<String, String> {
+        let rest = &self.data[self.pos..];
+        let len = rest
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or("Unterminated OSC string")?;
+        let s = std::str::from_utf8(&rest[..len])
+            .map_err(|_| "OSC string is not valid UTF-8".to_string())?
+            .to_string();
+        // Skip the terminator and padding
+        self.take((len + 4) & !3)?;
+        Ok(s)
+    }
+
+    fn read_blob(&mut self) -> Result<Vec<u8>, String> {
+        let len = i32::from_be_bytes(self.take_array()?);
+        let len = usize::try_from(len).map_err(|_| "Negative OSC blob size".to_string())?;
+        let blob = self.take(len)?.to_vec();
+        self.take((4 - len % 4) % 4)?;
+        Ok(blob)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+}
+
+/// Decodes an OSC packet (a message or a bundle) into its messages.
+///
+/// Bundles are flattened and their time tags ignored; messages are returned
+/// in the order they appear.
+///
+/// # Errors
+///
+/// Returns an error if the packet is malformed or uses an unsupported type tag.
+pub fn decode_packet(data: &[u8]) -> Result<Vec<OscMessage>, String> {
+    let mut messages = Vec::new();
+    decode_into(data, &mut messages)?;
+    Ok(messages)
+}
+
+fn decode_into(data: &[u8], messages: &mut Vec<OscMessage>) -> Result<(), String> {
+    let mut reader = Reader { data, pos: 0 };
+
+    if data.starts_with(b"#bundle\0") {
+        reader.take(16)?; // "#bundle\0" + time tag
+        while !reader.is_empty() {
+            let size = i32::from_be_bytes(reader.take_array()?);
+            let size = usize::try_from(size)
+                .map_err(|_| "Negative OSC bundle element size".to_string())?;
+            decode_into(reader.take(size)?, messages)?;
+        }
+        return Ok(());
+    }
+
+    let address = reader.read_str()?;
+    if !address.starts_with('/') {
+        return Err(format!("Invalid OSC address: {}", address));
+    }
+
+    // Very old senders omit the type tag string entirely
+    let mut args = Vec::new();
+    if !reader.is_empty() {
+        let tags = reader.read_str()?;
+        let tags = tags
+            .strip_prefix(',')
+            .ok_or("OSC type tag string must start with ','")?;
+
+        for tag in tags.chars() {
+            let arg = match tag {
+                'i' => OscArg::Int(i32::from_be_bytes(reader.take_array()?)),
+                'f' => OscArg::Float(f32::from_be_bytes(reader.take_array()?)),
+                's' | 'S' => OscArg::String(reader.read_str()?),
+                'b' => OscArg::Blob(reader.read_blob()?),
+                'h' => OscArg::Long(i64::from_be_bytes(reader.take_array()?)),
+                'd' => OscArg::Double(f64::from_be_bytes(reader.take_array()?)),
+                'T' => OscArg::Bool(true),
+                'F' => OscArg::Bool(false),
+                'N' | 'I' => OscArg::Nil,
+                other => return Err(format!("Unsupported OSC type tag: {}", other)),
+            };
+            args.push(arg);
+        }
+    }
+
+    messages.push(OscMessage { address, args });
+    Ok(())
+}
+
+// =============================================================================
+// Address Mapping
+// =============================================================================
+
+/// What to do in response to an incoming message.
+#[derive(Debug, PartialEq)]
+enum OscCommand {
+    /// Run an action.
+    Dispatch(Action),
+    /// Apply the profile at this index in the profile list.
+    ProfileIndex(usize),
+    /// Send the full state back to the sender.
+    Sync,
+    /// Nothing to do (e.g. a button release).
+    Ignore,
+}
+
+/// Maps an incoming message to a command.
+///
+/// # Errors
+///
+/// Returns an error for unknown addresses or missing/mistyped arguments.
+fn parse_command(msg: &OscMessage) -> Result<OscCommand, String> {
+    let segments: Vec<&str> = msg.address.trim_matches('/').split('/').collect();
+    let first = msg.args.first();
+
+    let number = || {
+        first
+            .and_then(OscArg::as_f32)
+            .ok_or_else(|| format!("{} expects a numeric argument", msg.address))
+    };
+    let boolean = || {
+        first
+            .and_then(OscArg::as_bool)
+            .ok_or_else(|| format!("{} expects a numeric or boolean argument", msg.address))
+    };
+
+    let action = match segments.as_slice() {
+        ["eq", "profile"] => match first {
+            Some(OscArg::String(name)) => Action::ApplyProfile { name: name.clone() },
+            Some(arg) => {
+                let index = arg
+                    .as_f32()
+                    .filter(|v| *v >= 0.0)
+                    .ok_or("/eq/profile expects a name or a non-negative index")?;
+                return Ok(OscCommand::ProfileIndex(index as usize));
+            }
+            None => return Err("/eq/profile expects a name or an index".to_string()),
+        },
+        ["eq", "preamp"] => Action::SetPreamp { preamp: number()? },
+        ["eq", "bypass"] => Action::SetEqEnabled {
+            enabled: !boolean()?,
+        },
+        ["eq", "band", n, param] => {
+            let index = n
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .ok_or_else(|| format!("Invalid band number: {}", n))?;
+            let patch = match *param {
+                "gain" => BandPatch {
+                    gain: Some(number()?),
+                    ..Default::default()
+                },
+                "freq" => BandPatch {
+                    frequency: Some(number()?),
+                    ..Default::default()
+                },
+                "q" => BandPatch {
+                    q_factor: Some(number()?),
+                    ..Default::default()
+                },
+                "enabled" => BandPatch {
+                    enabled: Some(boolean()?),
+                    ..Default::default()
+                },
+                other => return Err(format!("Unknown band parameter: {}", other)),
+            };
+            Action::UpdateBand { index, patch }
+        }
+        ["eq", "ab"] => match first {
+            Some(OscArg::String(option)) => Action::ApplyAbOption {
+                option: option.clone(),
+            },
+            _ => return Err("/eq/ab expects an option name".to_string()),
+        },
+        ["eq", "ab", option] => {
+            // Momentary buttons send 1 on press and 0 on release
+            if first.and_then(OscArg::as_bool) == Some(false) {
+                return Ok(OscCommand::Ignore);
+            }
+            Action::ApplyAbOption {
+                option: option.to_string(),
+            }
+        }
+        ["eq", "sync"] => return Ok(OscCommand::Sync),
+        _ => return Err(format!("Unknown OSC address: {}", msg.address)),
+    };
+
+    Ok(OscCommand::Dispatch(action))
+}
+
+/// Builds the feedback messages describing the given settings.
+fn feedback_messages(settings: &AppSettings) -> Vec<OscMessage> {
+    let mut messages = vec![
+        OscMessage::new(
+            "/eq/profile",
+            vec![OscArg::String(
+                settings.current_profile.clone().unwrap_or_default(),
+            )],
+        ),
+        OscMessage::new("/eq/preamp", vec![OscArg::Float(settings.preamp)]),
+        OscMessage::new(
+            "/eq/bypass",
+            vec![OscArg::Int(i32::from(!settings.eq_enabled))],
+        ),
+        OscMessage::new(
+            "/eq/band/count",
+            vec![OscArg::Int(settings.bands.len() as i32)],
+        ),
+    ];
+
+    for (i, band) in settings.bands.iter().enumerate() {
+        let n = i + 1;
+        messages.extend([
+            OscMessage::new(
+                format!("/eq/band/{}/gain", n),
+                vec![OscArg::Float(band.gain)],
+            ),
+            OscMessage::new(
+                format!("/eq/band/{}/freq", n),
+                vec![OscArg::Float(band.frequency)],
+            ),
+            OscMessage::new(
+                format!("/eq/band/{}/q", n),
+                vec![OscArg::Float(band.q_factor)],
+            ),
+            OscMessage::new(
+                format!("/eq/band/{}/enabled", n),
+                vec![OscArg::Int(i32::from(band.enabled))],
+            ),
+        ]);
+    }
+
+    messages
+}
+
+// =============================================================================
+// Access Control
+// =============================================================================
+
+/// Maximum number of controllers that receive feedback; the least recently
+/// seen controller is dropped when another one connects.
+const MAX_CLIENTS: usize = 16;
+
+/// Controllers that haven't sent an accepted message for this long stop
+/// receiving feedback.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Returns whether messages from `ip` are accepted.
+///
+/// Loopback is always allowed; other hosts only when LAN access is enabled
+/// and they are in the allowlist.
+fn is_allowed(settings: &OscSettings, ip: IpAddr) -> bool {
+    ip.is_loopback() || (settings.allow_lan && settings.allowed_hosts.contains(&ip))
+}
+
+/// Controllers that receive feedback, with the time of their last accepted
+/// message.
+#[derive(Debug, Default)]
+struct ClientList {
+    clients: Vec<(SocketAddr, Instant)>,
+}
+
+impl ClientList {
+    /// Records an accepted message from `addr`, evicting the least recently
+    /// seen controller if the list is full.
+    fn touch(&mut self, addr: SocketAddr, now: Instant) {
+        if let Some(entry) = self.clients.iter_mut().find(|(a, _)| *a == addr) {
+            entry.1 = now;
+            return;
+        }
+        if self.clients.len() >= MAX_CLIENTS {
+            if let Some(oldest) = self
+                .clients
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, seen))| *seen)
+                .map(|(i, _)| i)
+            {
+                self.clients.swap_remove(oldest);
+            }
+        }
+        self.clients.push((addr, now));
+    }
+
+    /// Drops expired controllers and returns the remaining addresses.
+    fn active(&mut self, now: Instant) -> Vec<SocketAddr> {
+        self.clients
+            .retain(|(_, seen)| now.saturating_duration_since(*seen) < CLIENT_TIMEOUT);
+        self.clients.iter().map(|(addr, _)| *addr).collect()
+    }
+}
+
+/// Addresses of controllers that receive feedback.
+type Clients = Arc<Mutex<ClientList>>;
+
+// =============================================================================
+// Server
+// =============================================================================
+
+/// What incoming messages act on.
+///
+/// Implemented by [`AppHandle`]; tests substitute a recording double.
+pub trait OscTarget: Send + 'static {
+    /// Performs an action.
+    fn dispatch(&self, action: Action) -> Result<(), String>;
+
+    /// Returns the current settings.
+    fn settings(&self) -> AppSettings;
+
+    /// Returns the profile names in list order.
+    fn profiles(&self) -> Result<Vec<String>, String>;
+}
+
+impl OscTarget for AppHandle {
+    fn dispatch(&self, action: Action) -> Result<(), String> {
+        dispatch(self, action).map(|_| ())
+    }
+
+    fn settings(&self) -> AppSettings {
+        self.state::<AppState>().settings.lock().clone()
+    }
+
+    fn profiles(&self) -> Result<Vec<String>, String> {
+        list_profiles()
+    }
+}
+
+/// A running OSC listener.
+///
+/// The listener is stopped when dropped.
+pub struct OscServer {
+    port: u16,
+    socket: Arc<UdpSocket>,
+    clients: Clients,
+    running: Arc<AtomicBool>,
+    listener: Option<(AppHandle, EventId)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl OscServer {
+    /// Starts listening for OSC messages as configured by `settings`.
+    ///
+    /// Binds to `127.0.0.1`, or to all interfaces if `allow_lan` is set.
+    /// Messages from sources rejected by the allowlist are dropped without a
+    /// reply, so the listener can't be used to reflect traffic.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - What incoming messages act on
+    /// * `settings` - Port, feedback port and access configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port cannot be bound.
+    pub fn start<T: OscTarget>(target: T, settings: &OscSettings) -> Result<Self, String> {
+        let port = settings.port;
+        let host = if settings.allow_lan {
+            Ipv4Addr::UNSPECIFIED
+        } else {
+            Ipv4Addr::LOCALHOST
+        };
+        let socket = UdpSocket::bind((host, port))
+            .map_err(|e| format!("Failed to start OSC listener on port {}: {}", port, e))?;
+        socket
+            .set_read_timeout(Some(POLL_INTERVAL))
+            .map_err(|e| format!("Failed to configure OSC socket: {}", e))?;
+        let port = socket.local_addr().map(|a| a.port()).unwrap_or(port);
+        let socket = Arc::new(socket);
+        let clients: Clients = Arc::default();
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread = {
+            let socket = Arc::clone(&socket);
+            let clients = Arc::clone(&clients);
+            let running = Arc::clone(&running);
+            let settings = settings.clone();
+            thread::spawn(move || {
+                let mut buf = [0u8; MAX_PACKET_SIZE];
+                while running.load(Ordering::Relaxed) {
+                    let Ok((len, source)) = socket.recv_from(&mut buf) else {
+                        continue;
+                    };
+                    if !is_allowed(&settings, source.ip()) {
+                        continue;
+                    }
+                    let reply_to = SocketAddr::new(
+                        source.ip(),
+                        settings.feedback_port.unwrap_or(source.port()),
+                    );
+                    handle_packet(&target, &socket, &clients, reply_to, &buf[..len]);
+                }
+            })
+        };
+
+        Ok(Self {
+            port,
+            socket,
+            clients,
+            running,
+            listener: None,
+            thread: Some(thread),
+        })
+    }
+
+    /// Starts the listener for the app and keeps controllers in sync with
+    /// settings changes made anywhere in the app.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the port cannot be bound.
+    pub fn start_for_app(app: &AppHandle, settings: &OscSettings) -> Result<Self, String> {
+        let mut server = Self::start(app.clone(), settings)?;
+
+        let socket = Arc::clone(&server.socket);
+        let clients = Arc::clone(&server.clients);
+        let listener = app.listen_any("settings-changed", move |event| {
+            if let Ok(settings) = serde_json::from_str::<AppSettings>(event.payload()) {
+                let packets: Vec<Vec<u8>> = feedback_messages(&settings)
+                    .iter()
+                    .map(OscMessage::encode)
+                    .collect();
+                for addr in clients.lock().active(Instant::now()) {
+                    for packet in &packets {
+                        let _ = socket.send_to(packet, addr);
+                    }
+                }
+            }
+        });
+        server.listener = Some((app.clone(), listener));
+
+        Ok(server)
+    }
+
+    /// Returns the port the listener is bound to.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for OscServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some((app, listener)) = self.listener.take() {
+            app.unlisten(listener);
+        }
+        if let Some(handle) = self.thread.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Handles one received datagram from an allowed source.
+///
+/// The sender is registered for feedback once one of its messages has been
+/// carried out.
+fn handle_packet<T: OscTarget>(
+    target: &T,
+    socket: &UdpSocket,
+    clients: &Clients,
+    reply_to: SocketAddr,
+    data: &[u8],
+) {
+    let send = |msg: &OscMessage, addr: &SocketAddr| {
+        let _ = socket.send_to(&msg.encode(), addr);
+    };
+    let send_error = |e: String| {
+        send(
+            &OscMessage::new("/eq/error", vec![OscArg::String(e)]),
+            &reply_to,
+        )
+    };
+
+    let messages = match decode_packet(data) {
+        Ok(messages) => messages,
+        Err(e) => {
+            send_error(e);
+            return;
+        }
+    };
+
+    for msg in &messages {
+        let result = parse_command(msg).and_then(|command| match command {
+            OscCommand::Dispatch(action) => {
+                let ab_option = match &action {
+                    Action::ApplyAbOption { option } => Some(option.clone()),
+                    _ => None,
+                };
+                target.dispatch(action)?;
+                if let Some(option) = ab_option {
+                    let echo = OscMessage::new("/eq/ab", vec![OscArg::String(option)]);
+                    let mut clients = clients.lock();
+                    clients.touch(reply_to, Instant::now());
+                    for addr in clients.active(Instant::now()) {
+                        send(&echo, &addr);
+                    }
+                }
+                Ok(())
+            }
+            OscCommand::ProfileIndex(index) => {
+                let profiles = target.profiles()?;
+                let name = profiles
+                    .get(index)
+                    .ok_or_else(|| format!("No profile at index {}", index))?;
+                target.dispatch(Action::ApplyProfile { name: name.clone() })
+            }
+            OscCommand::Sync => {
+                for feedback in feedback_messages(&target.settings()) {
+                    send(&feedback, &reply_to);
+                }
+                Ok(())
+            }
+            OscCommand::Ignore => Ok(()),
+        });
+
+        match result {
+            Ok(()) => clients.lock().touch(reply_to, Instant::now()),
+            Err(e) => send_error(e),
+        }
+    }
+}
+
+// =============================================================================
+// Server Lifecycle
+// =============================================================================
+
+/// Starts, restarts or stops the listener to match the current settings.
+///
+/// # Errors
+///
+/// Returns an error if the listener cannot be started.
+pub fn sync_server(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let config = state.settings.lock().osc.clone();
+
+    let mut server = state.osc.lock();
+    // Stop the old listener first so the port is free for a restart
+    *server = None;
+    if config.enabled {
+        *server = Some(OscServer::start_for_app(app, &config)?);
+    }
+
+    Ok(())
+}
+
+/// Status of the OSC listener for the settings UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct OscStatus {
+    /// Whether OSC is enabled in settings.
+    pub enabled: bool,
+    /// Whether the listener is currently running.
+    pub running: bool,
+    /// Configured UDP port.
+    pub port: u16,
+    /// Configured feedback port, if any.
+    pub feedback_port: Option<u16>,
+    /// Whether the listener accepts controllers on the network.
+    pub allow_lan: bool,
+    /// LAN controllers allowed to send messages.
+    pub allowed_hosts: Vec<IpAddr>,
+}
+
+fn osc_status(state: &AppState) -> OscStatus {
+    let config = state.settings.lock().osc.clone();
+    let running = state.osc.lock().as_ref().map(OscServer::port);
+
+    OscStatus {
+        enabled: config.enabled,
+        running: running.is_some(),
+        port: running.unwrap_or(config.port),
+        feedback_port: config.feedback_port,
+        allow_lan: config.allow_lan,
+        allowed_hosts: config.allowed_hosts,
+    }
+}
+
+/// Returns the OSC listener status.
+#[tauri::command]
+pub fn get_osc_status(state: tauri::State<AppState>) -> OscStatus {
+    osc_status(&state)
+}
+
+/// Enables or disables the OSC listener and persists the configuration.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether the listener should run
+/// * `port` - Optional new listen port; keeps the current port if `None`
+/// * `feedback_port` - Port controllers listen on; `None` replies to the
+///   sender's source port
+/// * `allow_lan` - Optionally listen on all interfaces; keeps the current
+///   setting if `None`
+/// * `allowed_hosts` - Optional new allowlist of LAN controller addresses;
+///   keeps the current list if `None`
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle
+///
+/// # Errors
+///
+/// Returns an error if settings cannot be saved or the listener cannot start
+/// (e.g. the port is already in use).
+#[tauri::command]
+pub fn set_osc_enabled(
+    enabled: bool,
+    port: Option<u16>,
+    feedback_port: Option<u16>,
+    allow_lan: Option<bool>,
+    allowed_hosts: Option<Vec<IpAddr>>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<OscStatus, String> {
+    {
+        let mut settings = state.settings.lock();
+        settings.osc.enabled = enabled;
+        if let Some(port) = port {
+            settings.osc.port = port;
+        }
+        settings.osc.feedback_port = feedback_port;
+        if let Some(allow_lan) = allow_lan {
+            settings.osc.allow_lan = allow_lan;
+        }
+        if let Some(allowed_hosts) = allowed_hosts {
+            settings.osc.allowed_hosts = allowed_hosts;
+        }
+        save_settings(&settings)?;
+    }
+
+    sync_server(&app)?;
+    Ok(osc_status(&state))
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(address: &str, args: Vec<OscArg>) -> OscMessage {
+        OscMessage::new(address, args)
+    }
+
+    // =========================================================================
+    // Codec Tests
+    // =========================================================================
+
+    #[test]
+    fn encode_matches_spec_layout() {
+        let packet = msg("/eq/preamp", vec![OscArg::Float(-3.0)]).encode();
+        let mut expected = b"/eq/preamp\0\0,f\0\0".to_vec();
+        expected.extend_from_slice(&(-3.0f32).to_be_bytes());
+        assert_eq!(packet, expected);
+    }
+
+    #[test]
+    fn round_trip_all_types() {
+        let original = msg(
+            "/test",
+            vec![
+                OscArg::Int(-7),
+                OscArg::Float(1.5),
+                OscArg::String("Studio".to_string()),
+                OscArg::Blob(vec![1, 2, 3]),
+                OscArg::Long(1 << 40),
+                OscArg::Double(0.25),
+                OscArg::Bool(true),
+                OscArg::Bool(false),
+                OscArg::Nil,
+            ],
+        );
+        let decoded = decode_packet(&original.encode()).unwrap();
+        assert_eq!(decoded, vec![original]);
+    }
+
+    #[test]
+    fn decode_bundle_flattens_messages() {
+        let first = msg("/eq/bypass", vec![OscArg::Int(1)]).encode();
+        let second = msg("/eq/preamp", vec![OscArg::Float(-2.0)]).encode();
+
+        let mut bundle = b"#bundle\0".to_vec();
+        bundle.extend_from_slice(&1u64.to_be_bytes());
+        for element in [&first, &second] {
+            bundle.extend_from_slice(&(element.len() as i32).to_be_bytes());
+            bundle.extend_from_slice(element);
+        }
+
+        let decoded = decode_packet(&bundle).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[1].address, "/eq/preamp");
+    }
+
+    #[test]
+    fn decode_without_type_tags() {
+        let decoded = decode_packet(b"/eq/sync\0\0\0\0").unwrap();
+        assert_eq!(decoded, vec![msg("/eq/sync", vec![])]);
+    }
+
+    #[test]
+    fn decode_rejects_truncated_packet() {
+        let mut packet = msg("/eq/preamp", vec![OscArg::Float(1.0)]).encode();
+        packet.truncate(packet.len() - 2);
+        assert!(decode_packet(&packet).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_invalid_address() {
+        assert!(decode_packet(b"eq\0\0,\0\0\0").is_err());
+    }
+
+    // =========================================================================
+    // Address Mapping Tests
+    // =========================================================================
+
+    #[test]
+    fn parse_profile_by_name_and_index() {
+        assert_eq!(
+            parse_command(&msg("/eq/profile", vec![OscArg::String("Studio".into())])),
+            Ok(OscCommand::Dispatch(Action::ApplyProfile {
+                name: "Studio".to_string()
+            }))
+        );
+        assert_eq!(
+            parse_command(&msg("/eq/profile", vec![OscArg::Int(2)])),
+            Ok(OscCommand::ProfileIndex(2))
+        );
+        assert!(parse_command(&msg("/eq/profile", vec![OscArg::Int(-1)])).is_err());
+    }
+
+    #[test]
+    fn parse_bypass_accepts_numbers_and_bools() {
+        let bypassed = Ok(OscCommand::Dispatch(Action::SetEqEnabled {
+            enabled: false,
+        }));
+        assert_eq!(
+            parse_command(&msg("/eq/bypass", vec![OscArg::Float(1.0)])),
+            bypassed
+        );
+        assert_eq!(
+            parse_command(&msg("/eq/bypass", vec![OscArg::Bool(true)])),
+            bypassed
+        );
+        assert_eq!(
+            parse_command(&msg("/eq/bypass", vec![OscArg::Int(0)])),
+            Ok(OscCommand::Dispatch(Action::SetEqEnabled { enabled: true }))
+        );
+    }
+
+    #[test]
+    fn parse_band_gain_is_one_based() {
+        assert_eq!(
+            parse_command(&msg("/eq/band/1/gain", vec![OscArg::Float(3.5)])),
+            Ok(OscCommand::Dispatch(Action::UpdateBand {
+                index: 0,
+                patch: BandPatch {
+                    gain: Some(3.5),
+                    ..Default::default()
+                },
+            }))
+        );
+        assert!(parse_command(&msg("/eq/band/0/gain", vec![OscArg::Float(1.0)])).is_err());
+    }
+
+    #[test]
+    fn parse_ab_button_ignores_release() {
+        assert_eq!(
+            parse_command(&msg("/eq/ab/B", vec![OscArg::Float(1.0)])),
+            Ok(OscCommand::Dispatch(Action::ApplyAbOption {
+                option: "B".to_string()
+            }))
+        );
+        assert_eq!(
+            parse_command(&msg("/eq/ab/B", vec![OscArg::Float(0.0)])),
+            Ok(OscCommand::Ignore)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_missing_argument_and_unknown_address() {
+        assert!(parse_command(&msg("/eq/preamp", vec![])).is_err());
+        assert!(parse_command(&msg("/eq/unknown", vec![])).is_err());
+    }
+
+    #[test]
+    fn feedback_describes_state() {
+        let settings = AppSettings {
+            preamp: -4.0,
+            eq_enabled: false,
+            ..Default::default()
+        };
+        let messages = feedback_messages(&settings);
+
+        assert!(messages.contains(&msg("/eq/preamp", vec![OscArg::Float(-4.0)])));
+        assert!(messages.contains(&msg("/eq/bypass", vec![OscArg::Int(1)])));
+        assert!(messages.contains(&msg("/eq/profile", vec![OscArg::String(String::new())])));
+    }
+
+    // =========================================================================
+    // Access Control Tests
+    // =========================================================================
+
+    #[test]
+    fn lan_hosts_need_opt_in_and_allowlist() {
+        let lan_host: IpAddr = "192.168.1.20".parse().unwrap();
+        let mut settings = OscSettings::default();
+
+        assert!(is_allowed(&settings, IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert!(!is_allowed(&settings, lan_host));
+
+        settings.allowed_hosts = vec![lan_host];
+        assert!(!is_allowed(&settings, lan_host));
+
+        settings.allow_lan = true;
+        assert!(is_allowed(&settings, lan_host));
+        assert!(!is_allowed(&settings, "192.168.1.21".parse().unwrap()));
+    }
+
+    #[test]
+    fn client_list_is_capped_and_expires() {
+        let start = Instant::now();
+        let addr = |port: u16| SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let mut clients = ClientList::default();
+
+        for port in 0..MAX_CLIENTS as u16 + 1 {
+            clients.touch(addr(port), start + Duration::from_secs(port.into()));
+        }
+        let active = clients.active(start);
+        assert_eq!(active.len(), MAX_CLIENTS);
+        assert!(!active.contains(&addr(0)));
+
+        let later = start + CLIENT_TIMEOUT + Duration::from_secs(MAX_CLIENTS as u64);
+        clients.touch(addr(1), later);
+        assert_eq!(clients.active(later), vec![addr(1)]);
+    }
+
+    // =========================================================================
+    // Loopback Tests
+    // =========================================================================
+
+    /// Records dispatched actions instead of touching the app.
+    #[derive(Clone, Default)]
+    struct RecordingTarget {
+        actions: Arc<Mutex<Vec<Action>>>,
+    }
+
+    impl OscTarget for RecordingTarget {
+        fn dispatch(&self, action: Action) -> Result<(), String> {
+            self.actions.lock().push(action);
+            Ok(())
+        }
+
+        fn settings(&self) -> AppSettings {
+            AppSettings {
+                preamp: -1.5,
+                ..Default::default()
+            }
+        }
+
+        fn profiles(&self) -> Result<Vec<String>, String> {
+            Ok(vec!["Flat".to_string(), "Studio".to_string()])
+        }
+    }
+
+    fn controller() -> UdpSocket {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        socket
+    }
+
+    fn receive(socket: &UdpSocket) -> OscMessage {
+        let mut buf = [0u8; MAX_PACKET_SIZE];
+        let (len, _) = socket.recv_from(&mut buf).unwrap();
+        decode_packet(&buf[..len]).unwrap().remove(0)
+    }
+
+    #[test]
+    fn handle_packet_registers_sender_after_accepted_message() {
+        let target = RecordingTarget::default();
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let controller = controller();
+        let reply_to = controller.local_addr().unwrap();
+        let clients = Clients::default();
+
+        handle_packet(&target, &server, &clients, reply_to, b"garbage");
+        assert_eq!(receive(&controller).address, "/eq/error");
+        assert!(clients.lock().active(Instant::now()).is_empty());
+
+        let packet = msg("/eq/profile", vec![OscArg::Int(1)]).encode();
+        handle_packet(&target, &server, &clients, reply_to, &packet);
+        assert_eq!(
+            *target.actions.lock(),
+            vec![Action::ApplyProfile {
+                name: "Studio".to_string()
+            }]
+        );
+        assert_eq!(clients.lock().active(Instant::now()), vec![reply_to]);
+    }
+
+    #[test]
+    fn server_dispatches_and_replies_over_udp() {
+        let target = RecordingTarget::default();
+        let settings = OscSettings {
+            enabled: true,
+            port: 0,
+            ..Default::default()
+        };
+        let server = OscServer::start(target.clone(), &settings).unwrap();
+        let controller = controller();
+        let server_addr = SocketAddr::from((Ipv4Addr::LOCALHOST, server.port()));
+
+        let band = msg("/eq/band/3/gain", vec![OscArg::Float(-6.0)]);
+        controller.send_to(&band.encode(), server_addr).unwrap();
+        controller
+            .send_to(&msg("/eq/sync", vec![]).encode(), server_addr)
+            .unwrap();
+
+        // Packets are handled in order, so the band update is done once the
+        // sync reply arrives
+        let mut reply = Vec::new();
+        while reply.len() < 4 {
+            reply.push(receive(&controller));
+        }
+        assert!(reply.contains(&msg("/eq/preamp", vec![OscArg::Float(-1.5)])));
+        assert_eq!(
+            *target.actions.lock(),
+            vec![Action::UpdateBand {
+                index: 2,
+                patch: BandPatch {
+                    gain: Some(-6.0),
+                    ..Default::default()
+                },
+            }]
+        );
+        assert_eq!(
+            server.clients.lock().active(Instant::now()),
+            vec![controller.local_addr().unwrap()]
+        );
+    }
+}
@@ -0,0 +1,1019 @@
+//! Import of presets from other equalizer software.
+//!
+//! Each supported format implements [`PresetImporter`]. The format of a
+//! file is detected from its content alone, trying [`IMPORTERS`] in order:
+//!
+//! | Format      | Detected by                                             |
+//! |-------------|---------------------------------------------------------|
+//! | EasyEffects | JSON object with an `output`/`input` equalizer          |
+//! | Poweramp    | JSON object (or array) with a `bands` list              |
+//! | CamillaDSP  | YAML with a top-level `filters:` mapping                |
+//! | Peace       | INI with `[General]`/`[Filters]` sections               |
+//! | foobar2000  | `.feq`: exactly 18 lines of integer gains               |
+//! | Wavelet     | A `GraphicEQ:` line                                     |
+//!
+//! Constructs without an equivalent in this app (other filter types,
+//! per-channel differences, extra effects) are skipped and reported as
+//! warnings. The resulting profile is not saved; it is returned ready to be
+//! reviewed and passed to `save_profile`.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::autoeq::parse_graphic_eq_line;
+use crate::profile::sanitize_profile_name;
+use crate::types::{EqProfile, FilterType, GraphicEqPoint, ParametricBand, ProfileSource};
+
+/// Band frequencies of foobar2000's 18-band graphic equalizer, in Hz.
+const FOOBAR_FREQUENCIES: [f32; 18] = [
+    55.0, 77.0, 110.0, 156.0, 220.0, 311.0, 440.0, 622.0, 880.0, 1200.0, 1800.0, 2500.0, 3500.0,
+    5000.0, 7000.0, 10000.0, 14000.0, 20000.0,
+];
+
+/// A format that presets can be imported from.
+pub trait PresetImporter: Sync {
+    /// Identifier stored in [`ProfileSource::format`].
+    fn id(&self) -> &'static str;
+
+    /// Returns whether `content` looks like this format.
+    fn detect(&self, content: &str) -> bool;
+
+    /// Parses `content` into a profile named `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content is malformed or contains nothing
+    /// that can be imported.
+    fn parse(&self, name: &str, content: &str) -> Result<PresetImport, String>;
+}
+
+/// A parsed preset.
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetImport {
+    /// Identifier of the detected format.
+    pub format: &'static str,
+    /// The imported profile.
+    pub profile: EqProfile,
+    /// Parts of the preset that were skipped or approximated.
+    pub warnings: Vec<String>,
+}
+
+impl PresetImport {
+    fn new(format: &'static str, name: &str) -> Self {
+        Self {
+            format,
+            profile: EqProfile {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Returns an error if nothing was imported.
+    fn finish(self) -> Result<Self, String> {
+        if self.profile.bands.is_empty() && self.profile.graphic_eq.is_empty() {
+            return Err("Preset contains no supported filters".to_string());
+        }
+        Ok(self)
+    }
+}
+
+/// The importers, in detection order.
+pub static IMPORTERS: &[&dyn PresetImporter] = &[
+    &EasyEffects,
+    &Poweramp,
+    &CamillaDsp,
+    &Peace,
+    &Foobar2000,
+    &Wavelet,
+];
+
+/// Returns the importer for `content`, if any format is recognized.
+pub fn detect_importer(content: &str) -> Option<&'static dyn PresetImporter> {
+    IMPORTERS
+        .iter()
+        .copied()
+        .find(|importer| importer.detect(content))
+}
+
+/// Builds a band, checking that frequency and Q are positive.
+fn make_band(
+    filter_type: FilterType,
+    frequency: f32,
+    gain: f32,
+    q_factor: f32,
+    enabled: bool,
+) -> Result<ParametricBand, String> {
+    if !(frequency > 0.0 && frequency.is_finite()) {
+        return Err(format!("Invalid frequency {}", frequency));
+    }
+    if !(q_factor > 0.0 && q_factor.is_finite() && gain.is_finite()) {
+        return Err(format!("Invalid Q or gain at {} Hz", frequency));
+    }
+    Ok(ParametricBand {
+        filter_type,
+        frequency,
+        gain,
+        q_factor,
+        enabled,
+    })
+}
+
+/// Reads a number from a JSON value, accepting numeric strings.
+fn json_f32(value: Option<&Value>) -> Option<f32> {
+    match value? {
+        Value::Number(n) => n.as_f64().map(|v| v as f32),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// EasyEffects
+// =============================================================================
+
+/// EasyEffects preset JSON (`output` or `input` pipeline).
+pub struct EasyEffects;
+
+impl EasyEffects {
+    /// Returns the pipeline object and the keys of its equalizer plugins.
+    fn equalizers(json: &Value) -> Option<(&serde_json::Map<String, Value>, Vec<&String>)> {
+        let pipeline = json
+            .get("output")
+            .or_else(|| json.get("input"))?
+            .as_object()?;
+        let keys: Vec<&String> = pipeline
+            .keys()
+            .filter(|k| *k == "equalizer" || k.starts_with("equalizer#"))
+            .collect();
+        (!keys.is_empty()).then_some((pipeline, keys))
+    }
+}
+
+impl PresetImporter for EasyEffects {
+    fn id(&self) -> &'static str {
+        "easyeffects"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        serde_json::from_str::<Value>(content)
+            .ok()
+            .is_some_and(|json| Self::equalizers(&json).is_some())
+    }
+
+    fn parse(&self, name: &str, content: &str) -> Result<PresetImport, String> {
+        let json: Value =
+            serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let (pipeline, keys) = Self::equalizers(&json).ok_or("No equalizer in preset")?;
+        let mut import = PresetImport::new(self.id(), name);
+
+        let others: Vec<&String> = pipeline
+            .keys()
+            .filter(|k| !keys.contains(k) && *k != "blocklist" && *k != "plugins_order")
+            .collect();
+        if !others.is_empty() {
+            import.warnings.push(format!(
+                "Other effects were skipped: {}",
+                others
+                    .iter()
+                    .map(|k| k.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        if keys.len() > 1 {
+            import.warnings.push(format!(
+                "{} equalizers found; only the first was imported",
+                keys.len()
+            ));
+        }
+
+        let eq = &pipeline[keys[0].as_str()];
+        if eq.get("bypass").and_then(Value::as_bool) == Some(true) {
+            import
+                .warnings
+                .push("The equalizer was bypassed; its bands are imported enabled".to_string());
+        }
+        import.profile.preamp = json_f32(eq.get("input-gain")).unwrap_or(0.0)
+            + json_f32(eq.get("output-gain")).unwrap_or(0.0);
+        if eq.get("split-channels").and_then(Value::as_bool) == Some(true)
+            && eq.get("left") != eq.get("right")
+        {
+            import
+                .warnings
+                .push("Channels differ; only the left channel was imported".to_string());
+        }
+
+        let left = eq
+            .get("left")
+            .and_then(Value::as_object)
+            .ok_or("Equalizer has no bands")?;
+        let count = eq
+            .get("num-bands")
+            .and_then(Value::as_u64)
+            .unwrap_or(left.len() as u64);
+        for index in 0..count {
+            let Some(band) = left.get(&format!("band{}", index)) else {
+                continue;
+            };
+            let kind = band.get("type").and_then(Value::as_str).unwrap_or("Bell");
+            let filter_type = match kind {
+                "Bell" => FilterType::Peaking,
+                "Lo-shelf" => FilterType::LowShelf,
+                "Hi-shelf" => FilterType::HighShelf,
+                "Off" => continue,
+                other => {
+                    import.warnings.push(format!(
+                        "Band {}: unsupported type '{}' skipped",
+                        index + 1,
+                        other
+                    ));
+                    continue;
+                }
+            };
+            let muted = band.get("mute").and_then(Value::as_bool) == Some(true);
+            let result = make_band(
+                filter_type,
+                json_f32(band.get("frequency")).unwrap_or(0.0),
+                json_f32(band.get("gain")).unwrap_or(0.0),
+                json_f32(band.get("q")).unwrap_or(0.0),
+                !muted,
+            );
+            match result {
+                Ok(band) => import.profile.bands.push(band),
+                Err(e) => return Err(format!("Band {}: {}", index + 1, e)),
+            }
+        }
+        import.finish()
+    }
+}
+
+// =============================================================================
+// Poweramp
+// =============================================================================
+
+/// Poweramp equalizer preset JSON, as exported by Poweramp or generated by
+/// AutoEQ. Band types are 1 (low shelf), 2 (high shelf) and 3 (peaking).
+pub struct Poweramp;
+
+impl Poweramp {
+    /// Returns the preset object, taking the first of an array.
+    fn preset(json: &Value) -> Option<&Value> {
+        let preset = match json {
+            Value::Array(items) => items.first()?,
+            other => other,
+        };
+        preset.get("bands")?.as_array()?;
+        Some(preset)
+    }
+}
+
+impl PresetImporter for Poweramp {
+    fn id(&self) -> &'static str {
+        "poweramp"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        serde_json::from_str::<Value>(content)
+            .ok()
+            .is_some_and(|json| Self::preset(&json).is_some())
+    }
+
+    fn parse(&self, name: &str, content: &str) -> Result<PresetImport, String> {
+        let json: Value =
+            serde_json::from_str(content).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let preset = Self::preset(&json).ok_or("No bands in preset")?;
+        let mut import = PresetImport::new(self.id(), name);
+
+        if let Value::Array(items) = &json {
+            if items.len() > 1 {
+                import.warnings.push(format!(
+                    "{} presets found; only the first was imported",
+                    items.len()
+                ));
+            }
+        }
+        import.profile.preamp = json_f32(preset.get("preamp")).unwrap_or(0.0);
+        let parametric = preset.get("parametric").and_then(Value::as_bool) != Some(false);
+
+        for (index, band) in preset["bands"].as_array().into_iter().flatten().enumerate() {
+            let frequency = json_f32(band.get("frequency")).unwrap_or(0.0);
+            let gain = json_f32(band.get("gain")).unwrap_or(0.0);
+            if !parametric {
+                import
+                    .profile
+                    .graphic_eq
+                    .push(GraphicEqPoint { frequency, gain });
+                continue;
+            }
+            let filter_type = match band.get("type").and_then(Value::as_u64) {
+                Some(1) => FilterType::LowShelf,
+                Some(2) => FilterType::HighShelf,
+                Some(3) | None => FilterType::Peaking,
+                Some(other) => {
+                    import.warnings.push(format!(
+                        "Band {}: unsupported type {} skipped",
+                        index + 1,
+                        other
+                    ));
+                    continue;
+                }
+            };
+            if band.get("channels").and_then(Value::as_u64).unwrap_or(0) != 0 {
+                import.warnings.push(format!(
+                    "Band {}: applied to one channel only in Poweramp; imported for both",
+                    index + 1
+                ));
+            }
+            let q_factor = json_f32(band.get("q")).unwrap_or(0.707);
+            let band = make_band(filter_type, frequency, gain, q_factor, true)
+                .map_err(|e| format!("Band {}: {}", index + 1, e))?;
+            import.profile.bands.push(band);
+        }
+
+        import
+            .profile
+            .graphic_eq
+            .sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+        import.finish()
+    }
+}
+
+// =============================================================================
+// CamillaDSP
+// =============================================================================
+
+/// A node of the YAML subset used by CamillaDSP configs.
+#[derive(Debug, Clone, PartialEq)]
+enum Yaml {
+    Scalar(String),
+    Map(Vec<(String, Yaml)>),
+    List(Vec<Yaml>),
+}
+
+impl Yaml {
+    fn get(&self, key: &str) -> Option<&Yaml> {
+        match self {
+            Yaml::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Yaml::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_f32(&self) -> Option<f32> {
+        self.as_str()?.parse().ok()
+    }
+}
+
+/// Removes a trailing `# comment` that is not inside quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('#', None) if i == 0 || line[..i].ends_with(' ') => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses a scalar or flow list (`[0, 1]`).
+fn yaml_value(text: &str) -> Yaml {
+    let text = text.trim();
+    if let Some(inner) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return Yaml::List(
+            inner
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(yaml_value)
+                .collect(),
+        );
+    }
+    let unquoted = text
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .or_else(|| text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')))
+        .unwrap_or(text);
+    Yaml::Scalar(unquoted.replace("\\\"", "\""))
+}
+
+/// Splits `key: value` outside quotes.
+fn yaml_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['"', '\'', '[']) {
+        return None;
+    }
+    let (key, value) = text.split_once(':')?;
+    (value.is_empty() || value.starts_with(' ')).then(|| (key.trim(), value.trim()))
+}
+
+/// Parses the block starting at `lines[*i]` with the given indentation.
+fn yaml_block(lines: &mut [(usize, String)], i: &mut usize, indent: usize) -> Yaml {
+    if lines
+        .get(*i)
+        .is_some_and(|(_, text)| text == "-" || text.starts_with("- "))
+    {
+        let mut items = Vec::new();
+        while *i < lines.len() && lines[*i].0 == indent && lines[*i].1.starts_with('-') {
+            let rest = lines[*i].1[1..].trim_start().to_string();
+            if rest.is_empty() {
+                *i += 1;
+                let child = lines.get(*i).map_or(indent + 2, |(n, _)| *n);
+                items.push(yaml_block(lines, i, child));
+            } else if yaml_key(&rest).is_some() {
+                // `- key: value` starts a mapping indented past the dash
+                let offset = lines[*i].1.len() - rest.len();
+                lines[*i] = (indent + offset, rest);
+                items.push(yaml_block(lines, i, indent + offset));
+            } else {
+                items.push(yaml_value(&rest));
+                *i += 1;
+            }
+        }
+        return Yaml::List(items);
+    }
+
+    let mut entries = Vec::new();
+    while *i < lines.len() && lines[*i].0 == indent && !lines[*i].1.starts_with("- ") {
+        let Some((key, value)) =
+            yaml_key(&lines[*i].1).map(|(k, v)| (k.to_string(), v.to_string()))
+        else {
+            *i += 1;
+            continue;
+        };
+        *i += 1;
+        let child = if !value.is_empty() {
+            yaml_value(&value)
+        } else {
+            match lines.get(*i) {
+                Some((n, _)) if *n > indent => {
+                    let n = *n;
+                    yaml_block(lines, i, n)
+                }
+                Some((n, text)) if *n == indent && text.starts_with('-') => {
+                    yaml_block(lines, i, indent)
+                }
+                _ => Yaml::Scalar(String::new()),
+            }
+        };
+        entries.push((key, child));
+    }
+    Yaml::Map(entries)
+}
+
+/// Parses the YAML subset used by CamillaDSP: block mappings and lists,
+/// scalars and flow lists of scalars.
+fn parse_yaml(content: &str) -> Yaml {
+    let mut lines: Vec<(usize, String)> = content
+        .lines()
+        .map(strip_comment)
+        .filter(|line| !line.trim().is_empty() && line.trim() != "---")
+        .map(|line| {
+            let text = line.trim_start();
+            (line.len() - text.len(), text.trim_end().to_string())
+        })
+        .collect();
+    let indent = lines.first().map_or(0, |(n, _)| *n);
+    yaml_block(&mut lines, &mut 0, indent)
+}
+
+/// CamillaDSP configuration YAML.
+///
+/// Filters are imported in the order of the first `Filter` pipeline step,
+/// or in file order without a pipeline. `Gain` filters add to the preamp.
+pub struct CamillaDsp;
+
+impl CamillaDsp {
+    /// Adds one filter definition to the import; unsupported filters are
+    /// skipped with a warning.
+    fn import_filter(name: &str, filter: &Yaml, import: &mut PresetImport) -> Result<(), String> {
+        let params = filter.get("parameters");
+        let param = |key: &str| params.and_then(|p| p.get(key)).and_then(Yaml::as_f32);
+
+        match filter.get("type").and_then(Yaml::as_str) {
+            Some("Gain") => {
+                let gain = param("gain").unwrap_or(0.0);
+                let linear =
+                    params.and_then(|p| p.get("scale")).and_then(Yaml::as_str) == Some("linear");
+                if linear {
+                    import.profile.preamp += 20.0 * gain.abs().max(1e-6).log10();
+                } else {
+                    import.profile.preamp += gain;
+                }
+                if params
+                    .and_then(|p| p.get("inverted"))
+                    .and_then(Yaml::as_str)
+                    == Some("true")
+                {
+                    import
+                        .warnings
+                        .push(format!("Filter '{}': polarity inversion ignored", name));
+                }
+            }
+            Some("Biquad") => {
+                let kind = params
+                    .and_then(|p| p.get("type"))
+                    .and_then(Yaml::as_str)
+                    .unwrap_or_default();
+                let filter_type = match kind {
+                    "Peaking" => FilterType::Peaking,
+                    "Lowshelf" => FilterType::LowShelf,
+                    "Highshelf" => FilterType::HighShelf,
+                    other => {
+                        import.warnings.push(format!(
+                            "Filter '{}': unsupported biquad type '{}' skipped",
+                            name, other
+                        ));
+                        return Ok(());
+                    }
+                };
+                // Bandwidth in octaves, or shelf slope in dB/octave (12 = S 1)
+                let q_factor = match (param("q"), param("bandwidth"), param("slope")) {
+                    (Some(q), _, _) => q,
+                    (None, Some(bw), _) => {
+                        let ratio = 2f32.powf(bw);
+                        ratio.sqrt() / (ratio - 1.0)
+                    }
+                    (None, None, Some(slope)) => (1.0 / (2.0 * slope / 12.0)).sqrt(),
+                    (None, None, None) => 0.707,
+                };
+                let band = make_band(
+                    filter_type,
+                    param("freq").unwrap_or(0.0),
+                    param("gain").unwrap_or(0.0),
+                    q_factor,
+                    true,
+                )
+                .map_err(|e| format!("Filter '{}': {}", name, e))?;
+                import.profile.bands.push(band);
+            }
+            other => import.warnings.push(format!(
+                "Filter '{}': unsupported type '{}' skipped",
+                name,
+                other.unwrap_or("?")
+            )),
+        }
+        Ok(())
+    }
+}
+
+impl PresetImporter for CamillaDsp {
+    fn id(&self) -> &'static str {
+        "camilladsp"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        content
+            .lines()
+            .any(|line| line.trim_end() == "filters:" || line.starts_with("filters: "))
+    }
+
+    fn parse(&self, name: &str, content: &str) -> Result<PresetImport, String> {
+        let yaml = parse_yaml(content);
+        let Some(Yaml::Map(filters)) = yaml.get("filters") else {
+            return Err("No filters section found".to_string());
+        };
+        let mut import = PresetImport::new(self.id(), name);
+
+        let steps: Vec<&Yaml> = match yaml.get("pipeline") {
+            Some(Yaml::List(steps)) => steps
+                .iter()
+                .filter(|s| s.get("type").and_then(Yaml::as_str) == Some("Filter"))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let names: Vec<String> = match steps.first().and_then(|s| s.get("names")) {
+            Some(Yaml::List(names)) => names
+                .iter()
+                .filter_map(Yaml::as_str)
+                .map(String::from)
+                .collect(),
+            _ => filters.iter().map(|(k, _)| k.clone()).collect(),
+        };
+        if steps
+            .iter()
+            .any(|s| s.get("names") != steps[0].get("names"))
+        {
+            import.warnings.push(
+                "Pipeline steps apply different filters; only the first step was imported"
+                    .to_string(),
+            );
+        }
+        if let Some(Yaml::Map(mixers)) = yaml.get("mixers") {
+            if !mixers.is_empty() {
+                import.warnings.push("Mixers were skipped".to_string());
+            }
+        }
+
+        for filter_name in &names {
+            let filter = filters
+                .iter()
+                .find(|(k, _)| k == filter_name)
+                .map(|(_, v)| v)
+                .ok_or_else(|| format!("Pipeline references unknown filter '{}'", filter_name))?;
+            Self::import_filter(filter_name, filter, &mut import)?;
+        }
+        import.finish()
+    }
+}
+
+// =============================================================================
+// Peace
+// =============================================================================
+
+/// Peace `.peace` preset (INI). Filter types are 1 (peak), 5 (low shelf)
+/// and 6 (high shelf); 0 marks an unused slot.
+pub struct Peace;
+
+impl PresetImporter for Peace {
+    fn id(&self) -> &'static str {
+        "peace"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        content
+            .lines()
+            .any(|l| matches!(l.trim(), "[General]" | "[Filters]"))
+    }
+
+    fn parse(&self, name: &str, content: &str) -> Result<PresetImport, String> {
+        let mut import = PresetImport::new(self.id(), name);
+        let mut section = String::new();
+        let mut values = std::collections::BTreeMap::new();
+
+        for line in content.lines().map(str::trim) {
+            if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = header.to_string();
+            } else if let Some((key, value)) = line.split_once('=') {
+                values.insert(
+                    format!("{}.{}", section, key.trim()),
+                    value.trim().to_string(),
+                );
+            }
+        }
+
+        let number = |key: &str| {
+            values
+                .get(key)
+                .and_then(|v| v.replace(',', ".").parse::<f32>().ok())
+        };
+        import.profile.preamp =
+            number("General.PreAmp").unwrap_or(0.0) + number("General.PostAmp").unwrap_or(0.0);
+
+        for n in 1.. {
+            let Some(code) = values.get(&format!("Filters.Filter{}", n)) else {
+                break;
+            };
+            let filter_type = match code.as_str() {
+                "0" => continue,
+                "1" => FilterType::Peaking,
+                "5" => FilterType::LowShelf,
+                "6" => FilterType::HighShelf,
+                other => {
+                    import
+                        .warnings
+                        .push(format!("Filter {}: unsupported type {} skipped", n, other));
+                    continue;
+                }
+            };
+            let band = make_band(
+                filter_type,
+                number(&format!("Filters.Frequency{}", n)).unwrap_or(0.0),
+                number(&format!("Filters.Gain{}", n)).unwrap_or(0.0),
+                number(&format!("Filters.Quality{}", n)).unwrap_or(0.707),
+                true,
+            )
+            .map_err(|e| format!("Filter {}: {}", n, e))?;
+            import.profile.bands.push(band);
+        }
+        import.finish()
+    }
+}
+
+// =============================================================================
+// foobar2000
+// =============================================================================
+
+/// foobar2000 `.feq` preset: one integer gain per line for the 18 bands of
+/// its graphic equalizer.
+pub struct Foobar2000;
+
+impl Foobar2000 {
+    fn gains(content: &str) -> Option<Vec<f32>> {
+        let gains: Vec<f32> = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(|l| l.parse::<i32>().ok().map(|g| g as f32))
+            .collect::<Option<_>>()?;
+        (gains.len() == FOOBAR_FREQUENCIES.len()).then_some(gains)
+    }
+}
+
+impl PresetImporter for Foobar2000 {
+    fn id(&self) -> &'static str {
+        "foobar2000"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        Self::gains(content).is_some()
+    }
+
+    fn parse(&self, name: &str, content: &str) -> Result<PresetImport, String> {
+        let gains = Self::gains(content).ok_or("Expected 18 integer gains, one per line")?;
+        let mut import = PresetImport::new(self.id(), name);
+        import.profile.graphic_eq = FOOBAR_FREQUENCIES
+            .iter()
+            .zip(gains)
+            .map(|(&frequency, gain)| GraphicEqPoint { frequency, gain })
+            .collect();
+        import.finish()
+    }
+}
+
+// =============================================================================
+// Wavelet
+// =============================================================================
+
+/// Wavelet (and other AutoEQ GraphicEQ consumers) `GraphicEQ:` text.
+pub struct Wavelet;
+
+impl PresetImporter for Wavelet {
+    fn id(&self) -> &'static str {
+        "wavelet"
+    }
+
+    fn detect(&self, content: &str) -> bool {
+        content
+            .lines()
+            .any(|l| l.trim_start().starts_with("GraphicEQ:"))
+    }
+
+    fn parse(&self, name: &str, content: &str) -> Result<PresetImport, String> {
+        let mut import = PresetImport::new(self.id(), name);
+        for line in content.lines().map(str::trim) {
+            if line.starts_with("GraphicEQ:") {
+                if !import.profile.graphic_eq.is_empty() {
+                    import
+                        .warnings
+                        .push("Several GraphicEQ lines found; only the last was kept".to_string());
+                }
+                import.profile.graphic_eq = parse_graphic_eq_line(line)?;
+            }
+        }
+        import.finish()
+    }
+}
+
+// =============================================================================
+// File Import
+// =============================================================================
+
+/// Parses preset content, detecting its format.
+///
+/// # Errors
+///
+/// Returns an error if the format is not recognized or the preset cannot
+/// be parsed.
+pub fn parse_preset(name: &str, content: &str) -> Result<PresetImport, String> {
+    let content = content.trim_start_matches('\u{feff}');
+    detect_importer(content)
+        .ok_or("Unrecognized preset format")?
+        .parse(name, content)
+}
+
+/// Reads a preset file, detecting its format from the content.
+///
+/// The profile is named after the file unless `name` is given, and records
+/// the file in its metadata.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, the format is not
+/// recognized, or the preset cannot be parsed.
+pub fn read_preset_file(path: &Path, name: Option<&str>) -> Result<PresetImport, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let name = sanitize_profile_name(
+        name.or_else(|| path.file_stem().and_then(|s| s.to_str()))
+            .unwrap_or_default(),
+    );
+    if name.is_empty() {
+        return Err(format!("Cannot derive a profile name from {:?}", path));
+    }
+
+    let mut import = parse_preset(&name, &content)?;
+    import.profile.metadata.source = Some(ProfileSource {
+        format: import.format.to_string(),
+        path: path.to_string_lossy().into_owned(),
+    });
+    Ok(import)
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Reads a preset from another equalizer application.
+///
+/// The profile is returned, not saved, so that the warnings can be
+/// reviewed first.
+///
+/// # Arguments
+///
+/// * `path` - Preset file
+/// * `name` - Profile name; derived from the file name if `None`
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, the format is not
+/// recognized, or the preset cannot be parsed.
+#[tauri::command]
+pub fn import_preset(path: String, name: Option<String>) -> Result<PresetImport, String> {
+    read_preset_file(Path::new(&path), name.as_deref())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(content: &str) -> &'static str {
+        detect_importer(content).map(|i| i.id()).unwrap_or("none")
+    }
+
+    #[test]
+    fn detects_formats_from_content() {
+        assert_eq!(
+            detected(include_str!("../tests/golden/export/easyeffects.json")),
+            "easyeffects"
+        );
+        assert_eq!(
+            detected(include_str!("../tests/golden/export/camilladsp.yml")),
+            "camilladsp"
+        );
+        assert_eq!(
+            detected(include_str!("../tests/golden/export/peace.peace")),
+            "peace"
+        );
+        assert_eq!(
+            detected(include_str!("../tests/golden/export/wavelet.txt")),
+            "wavelet"
+        );
+        assert_eq!(detected(r#"{"preamp": -3, "bands": []}"#), "poweramp");
+        assert_eq!(detected(&"0\n".repeat(18)), "foobar2000");
+        assert_eq!(detected("hello"), "none");
+    }
+
+    /// Bands of the export fixture that are enabled.
+    fn assert_export_fixture(import: &PresetImport) {
+        let bands = &import.profile.bands;
+        assert_eq!(import.profile.preamp, -6.5);
+        assert_eq!(bands.len(), 3);
+        assert_eq!(bands[0].filter_type, FilterType::LowShelf);
+        assert_eq!(bands[0].frequency, 105.0);
+        assert_eq!(bands[0].gain, 5.5);
+        assert_eq!(bands[0].q_factor, 0.7);
+        assert_eq!(bands[1].filter_type, FilterType::Peaking);
+        assert_eq!(bands[1].gain, -2.25);
+        assert_eq!(bands[2].filter_type, FilterType::HighShelf);
+        assert_eq!(bands[2].q_factor, 0.707);
+    }
+
+    #[test]
+    fn round_trips_exported_presets() {
+        for content in [
+            include_str!("../tests/golden/export/easyeffects.json"),
+            include_str!("../tests/golden/export/camilladsp.yml"),
+            include_str!("../tests/golden/export/peace.peace"),
+        ] {
+            let import = parse_preset("HD 600", content).unwrap();
+            assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+            assert_export_fixture(&import);
+        }
+    }
+
+    #[test]
+    fn easyeffects_warns_about_unsupported_parts() {
+        let content = r#"{"output": {
+            "blocklist": [],
+            "compressor#0": {},
+            "equalizer#0": {
+                "input-gain": -2.0, "output-gain": -1.0, "num-bands": 3,
+                "split-channels": false,
+                "left": {
+                    "band0": {"type": "Bell", "frequency": 100, "gain": 3, "q": 1.0, "mute": true},
+                    "band1": {"type": "Hi-pass", "frequency": 20, "gain": 0, "q": 0.7},
+                    "band2": {"type": "Off", "frequency": 1000, "gain": 0, "q": 1.0}
+                },
+                "right": {}
+            },
+            "plugins_order": ["compressor#0", "equalizer#0"]
+        }}"#;
+        let import = parse_preset("x", content).unwrap();
+        assert_eq!(import.profile.preamp, -3.0);
+        assert_eq!(import.profile.bands.len(), 1);
+        assert!(!import.profile.bands[0].enabled);
+        assert_eq!(import.warnings.len(), 2, "{:?}", import.warnings);
+    }
+
+    #[test]
+    fn camilladsp_follows_pipeline_and_converts_bandwidth() {
+        let content = "---\n\
+            devices:\n  samplerate: 48000\n\
+            filters:\n\
+            \x20 hp: # high-pass\n\
+            \x20   type: Biquad\n\
+            \x20   parameters:\n\
+            \x20     type: Highpass\n\
+            \x20     freq: 20\n\
+            \x20     q: 0.7\n\
+            \x20 bass:\n\
+            \x20   type: Biquad\n\
+            \x20   parameters: {}\n\
+            \x20 mid:\n\
+            \x20   type: Biquad\n\
+            \x20   parameters:\n\
+            \x20     type: Peaking\n\
+            \x20     freq: 1000\n\
+            \x20     gain: -3\n\
+            \x20     bandwidth: 1.0\n\
+            \x20 shelf:\n\
+            \x20   type: Biquad\n\
+            \x20   parameters:\n\
+            \x20     type: Lowshelf\n\
+            \x20     freq: 80\n\
+            \x20     gain: 4\n\
+            \x20     slope: 6\n\
+            pipeline:\n\
+            - type: Filter\n\
+            \x20 channel: 0\n\
+            \x20 names:\n\
+            \x20   - shelf\n\
+            \x20   - mid\n\
+            \x20   - hp\n\
+            - type: Filter\n\
+            \x20 channel: 1\n\
+            \x20 names:\n\
+            \x20   - shelf\n";
+        let import = parse_preset("x", content).unwrap();
+        let bands = &import.profile.bands;
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].filter_type, FilterType::LowShelf);
+        assert_eq!(bands[0].q_factor, 1.0);
+        assert!((bands[1].q_factor - 1.414).abs() < 0.001);
+        assert_eq!(import.warnings.len(), 2, "{:?}", import.warnings);
+    }
+
+    #[test]
+    fn poweramp_parametric_and_graphic() {
+        let content = r#"[{"name": "HD 600", "preamp": -6.2, "parametric": true, "bands": [
+            {"type": 1, "channels": 0, "frequency": 105, "q": 0.7, "gain": 5.5, "color": 0},
+            {"type": 3, "channels": 0, "frequency": 150, "q": 0.5, "gain": -2.1, "color": 0},
+            {"type": 9, "channels": 0, "frequency": 20, "q": 0.5, "gain": 0, "color": 0}
+        ]}]"#;
+        let import = parse_preset("x", content).unwrap();
+        assert_eq!(import.format, "poweramp");
+        assert_eq!(import.profile.preamp, -6.2);
+        assert_eq!(import.profile.bands.len(), 2);
+        assert_eq!(import.profile.bands[0].filter_type, FilterType::LowShelf);
+        assert_eq!(import.warnings.len(), 1);
+
+        let graphic = r#"{"preamp": 0, "parametric": false, "bands": [
+            {"frequency": 1000, "gain": 1}, {"frequency": 100, "gain": 2}]}"#;
+        let import = parse_preset("x", graphic).unwrap();
+        assert_eq!(import.profile.graphic_eq[0].frequency, 100.0);
+    }
+
+    #[test]
+    fn foobar_maps_to_graphic_eq() {
+        let content = (1..=18).map(|g| format!("{}\n", g - 9)).collect::<String>();
+        let import = parse_preset("x", &content).unwrap();
+        assert_eq!(import.profile.graphic_eq.len(), 18);
+        assert_eq!(import.profile.graphic_eq[0].frequency, 55.0);
+        assert_eq!(import.profile.graphic_eq[0].gain, -8.0);
+        assert_eq!(import.profile.graphic_eq[17].gain, 9.0);
+    }
+
+    #[test]
+    fn wavelet_reads_graphic_eq_line() {
+        let import = parse_preset("x", "GraphicEQ: 20 -1; 20000 -2\n").unwrap();
+        assert_eq!(import.profile.graphic_eq.len(), 2);
+        assert!(parse_preset("x", "[General]\nPreAmp=0\n").is_err());
+    }
+}
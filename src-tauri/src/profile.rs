@@ -8,8 +8,17 @@ use std::path::{Path, PathBuf};
 
 use tauri::AppHandle;
 
+use crate::actions::{notify_settings_changed, push_recent_profile};
+use crate::convolution;
+use crate::graphic_eq;
+use crate::loudness;
 use crate::tray::update_tray_menu;
-use crate::types::{AppSettings, AppState, EqProfile, ParametricBand};
+use crate::types::{
+    format_config_number, AppSettings, AppState, ApplyRequest, ConfigPrecision, ConvolutionStage,
+    EqProfile, GraphicEqPoint, LoudnessSettings, ParametricBand, ProfileSummary, SettingsUpdate,
+    MAX_FAVORITES,
+};
+use crate::validation::{self, ValidationReport};
 
 // =============================================================================
 // Directory and Path Utilities
@@ -20,6 +29,7 @@ use crate::types::{AppSettings, AppState, EqProfile, ParametricBand};
 /// The directory is located at `Documents/EQAPO GUI/` and contains:
 /// - `settings.json` - Persistent application settings
 /// - `profiles/` - Saved EQ profiles
+/// - `impulses/` - Impulse responses for convolution stages
 /// - `ab_results/` - A/B test results
 ///
 /// # Errors
@@ -197,6 +207,19 @@ fn run_icacls_grant(path: &Path, grant: &str) -> Result<(), String> {
     }
 }
 
+/// Replaces characters that are not allowed in profile file names.
+pub fn sanitize_profile_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
 // =============================================================================
 // Settings Persistence
 // =============================================================================
@@ -260,7 +283,8 @@ pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
 ///
 /// # Returns
 ///
-/// A vector of profile names, or an empty vector if no profiles exist.
+/// A vector of profile names sorted case-insensitively, or an empty vector
+/// if no profiles exist.
 ///
 /// # Errors
 ///
@@ -274,7 +298,7 @@ pub fn list_profiles() -> Result<Vec<String>, String> {
         return Ok(vec![]);
     }
 
-    let profiles = fs::read_dir(&profiles_dir)
+    let mut profiles = fs::read_dir(&profiles_dir)
         .map_err(|e| format!("Failed to read profiles directory: {}", e))?
         .filter_map(|entry| {
             let entry = entry.ok()?;
@@ -285,7 +309,8 @@ pub fn list_profiles() -> Result<Vec<String>, String> {
                 None
             }
         })
-        .collect();
+        .collect::<Vec<_>>();
+    profiles.sort_by_key(|name| name.to_lowercase());
 
     Ok(profiles)
 }
@@ -316,29 +341,91 @@ pub fn load_profile(name: String) -> Result<EqProfile, String> {
 
 /// Saves a profile with the given name, preamp, and bands.
 ///
-/// Creates or overwrites `Documents/EQAPO GUI/profiles/{name}.json`.
+/// Creates or overwrites `Documents/EQAPO GUI/profiles/{name}.json`. When
+/// overwriting, the existing profile's metadata (folder, tags, source) and
+/// its routing, mid/side, convolution, crossfeed and VST plugin stages are
+/// kept, as is its GraphicEQ curve unless a new one is given.
+///
+/// The bands are validated against the output sample rate first (see
+/// [`validation::check_bands`]).
 ///
 /// # Arguments
 ///
 /// * `name` - The profile name (used as filename)
 /// * `preamp` - Global preamp gain in dB
 /// * `bands` - Collection of EQ bands
+/// * `graphic_eq` - GraphicEQ curve; `None` keeps the existing curve, an
+///   empty list removes it
+/// * `clamp` - Whether to clamp invalid band values; defaults to the
+///   `auto_clamp` setting
+/// * `state` - Tauri managed state
+///
+/// # Returns
+///
+/// The validation report; with clamping, the saved bands have the clamped
+/// values.
 ///
 /// # Errors
 ///
-/// Returns an error if the file cannot be written or JSON serialization fails.
+/// Returns an error if a band is invalid and not clamped, the GraphicEQ
+/// curve is invalid, or the file cannot be written or JSON serialization
+/// fails.
 #[tauri::command]
-pub fn save_profile(name: String, preamp: f32, bands: Vec<ParametricBand>) -> Result<(), String> {
-    let app_dir = ensure_dirs()?;
-    let profile_path = app_dir.join("profiles").join(format!("{}.json", &name));
+pub fn save_profile(
+    name: String,
+    preamp: f32,
+    mut bands: Vec<ParametricBand>,
+    graphic_eq: Option<Vec<GraphicEqPoint>>,
+    clamp: Option<bool>,
+    state: tauri::State<AppState>,
+) -> Result<ValidationReport, String> {
+    let report = validate_for_write(&mut bands, clamp, &state)?;
+
+    let existing = load_profile(name.clone()).unwrap_or_default();
+    let graphic_eq = graphic_eq.unwrap_or(existing.graphic_eq);
+    graphic_eq::validate_points(&graphic_eq)?;
 
-    let profile = EqProfile {
+    write_profile(&EqProfile {
         name,
         preamp,
         bands,
-    };
+        graphic_eq,
+        metadata: existing.metadata,
+        convolution: existing.convolution,
+        crossfeed: existing.crossfeed,
+        mid_side: existing.mid_side,
+        routing: existing.routing,
+        vst_plugin: existing.vst_plugin,
+    })?;
+    Ok(report)
+}
 
-    let json = serde_json::to_string_pretty(&profile)
+/// Validates bands before they are saved or applied.
+///
+/// # Errors
+///
+/// Returns the error messages if a band is invalid and not clamped.
+fn validate_for_write(
+    bands: &mut [ParametricBand],
+    clamp: Option<bool>,
+    state: &AppState,
+) -> Result<ValidationReport, String> {
+    let clamp = clamp.unwrap_or_else(|| state.settings.lock().validation.auto_clamp);
+    validation::enforce_bands(bands, validation::resolve_sample_rate(state), clamp)
+}
+
+/// Writes a complete profile to `Documents/EQAPO GUI/profiles/{name}.json`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written or JSON serialization fails.
+pub fn write_profile(profile: &EqProfile) -> Result<(), String> {
+    let app_dir = ensure_dirs()?;
+    let profile_path = app_dir
+        .join("profiles")
+        .join(format!("{}.json", &profile.name));
+
+    let json = serde_json::to_string_pretty(profile)
         .map_err(|e| format!("Failed to serialize profile: {}", e))?;
 
     fs::write(&profile_path, json).map_err(|e| format!("Failed to write profile: {}", e))?;
@@ -346,6 +433,64 @@ pub fn save_profile(name: String, preamp: f32, bands: Vec<ParametricBand>) -> Re
     Ok(())
 }
 
+/// Sets the folder and tags of a saved profile.
+///
+/// # Arguments
+///
+/// * `name` - The profile name
+/// * `folder` - Folder to show the profile in, or `None` for the top level
+/// * `tags` - Tags; blank entries and duplicates are removed
+/// * `app` - Tauri app handle for tray updates
+///
+/// # Errors
+///
+/// Returns an error if the profile cannot be loaded or written.
+#[tauri::command]
+pub fn set_profile_metadata(
+    name: String,
+    folder: Option<String>,
+    tags: Vec<String>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let mut profile = load_profile(name)?;
+
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !cleaned.iter().any(|t| t == tag) {
+            cleaned.push(tag.to_string());
+        }
+    }
+
+    profile.metadata.folder = folder
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty());
+    profile.metadata.tags = cleaned;
+    write_profile(&profile)?;
+
+    let _ = update_tray_menu(&app);
+    Ok(())
+}
+
+/// Lists all profiles with their metadata.
+///
+/// Profiles that fail to parse are listed without metadata.
+///
+/// # Errors
+///
+/// Returns an error if the profiles directory cannot be read.
+#[tauri::command]
+pub fn list_profile_summaries() -> Result<Vec<ProfileSummary>, String> {
+    Ok(list_profiles()?
+        .into_iter()
+        .map(|name| {
+            let metadata = load_profile(name.clone())
+                .map(|p| p.metadata)
+                .unwrap_or_default();
+            ProfileSummary { name, metadata }
+        })
+        .collect())
+}
+
 /// Deletes a profile by name from the profiles directory.
 ///
 /// # Arguments
@@ -365,7 +510,241 @@ pub fn delete_profile(name: String) -> Result<(), String> {
     Ok(())
 }
 
-/// Writes EQ bands and preamp to an EqualizerAPO config file.
+/// Generates the EqualizerAPO configuration text for a profile.
+///
+/// The routing matrix, mid/side, crossfeed and convolution stages, if any,
+/// are written right after the preamp, followed by the GraphicEQ curve and
+/// the parametric filters. Downmixing comes first so every later stage sees
+/// the final channels, then mid/side processing, which acts on the
+/// recording itself; bands act as adjustments on top of a room or headphone
+/// correction impulse, and headphone corrections apply after crossfeed.
+/// A VST plugin follows the bands, so it sees the equalized signal. Bass
+/// management is written last, as the speaker crossover. Disabled
+/// bands are skipped. When `enabled` is `false`, a bypass config
+/// without any filters is produced instead. Lines are joined with `\r\n` as
+/// expected by EqualizerAPO on Windows.
+///
+/// # Arguments
+///
+/// * `profile` - The bands, GraphicEQ curve, stages and preamp to emit
+/// * `enabled` - Whether EQ is enabled (false = bypass mode)
+/// * `precision` - Decimal places for the preamp and band values
+#[must_use]
+pub fn build_config_content(
+    profile: &EqProfile,
+    enabled: bool,
+    precision: &ConfigPrecision,
+) -> String {
+    if enabled {
+        let mut lines = vec![
+            String::from("; EQAPO GUI Live Configuration"),
+            String::from("; Auto-generated - do not edit manually"),
+            String::from(""),
+            format!(
+                "Preamp: {} dB",
+                format_config_number(profile.preamp, precision.preamp, 1)
+            ),
+            String::from(""),
+        ];
+
+        if let Some(routing) = &profile.routing {
+            lines.extend(routing.matrix_lines());
+        }
+
+        if let Some(mid_side) = &profile.mid_side {
+            lines.extend(mid_side.config_lines(precision));
+        }
+
+        if let Some(crossfeed) = &profile.crossfeed {
+            lines.extend(crossfeed.config_lines(precision));
+        }
+
+        if let Some(stage) = &profile.convolution {
+            lines.push(convolution::config_line(stage));
+        }
+
+        if !profile.graphic_eq.is_empty() {
+            lines.push(graphic_eq::to_eapo_line(&profile.graphic_eq));
+        }
+
+        for band in &profile.bands {
+            if band.enabled {
+                lines.push(band.to_eapo_line_with(precision));
+            }
+        }
+
+        if let Some(plugin) = &profile.vst_plugin {
+            lines.push(plugin.config_line());
+        }
+
+        if let Some(routing) = &profile.routing {
+            lines.extend(routing.bass_management_lines());
+        }
+
+        lines.join("\r\n")
+    } else {
+        // EQ disabled - write empty config (bypassed)
+        [
+            "; EQAPO GUI Live Configuration",
+            "; EQ DISABLED - Bypass mode",
+            "",
+            "; No filters applied",
+        ]
+        .join("\r\n")
+    }
+}
+
+/// Writes EQ bands, GraphicEQ curve and preamp to an EqualizerAPO config file.
+///
+/// See [`write_config`] for details.
+///
+/// # Arguments
+///
+/// * `request` - Live EQ stages and options for this write
+/// * `state` - Tauri managed state
+///
+/// # Returns
+///
+/// The band validation report, as for [`save_profile`]. Compensation bands
+/// are appended after validation and are not part of the report. In bypass
+/// mode the bands are not checked and the report is empty.
+///
+/// # Errors
+///
+/// Returns an error if EQ is enabled and a band is invalid and not
+/// clamped, a stage is invalid (see [`prepare_config`]) or the loudness
+/// settings are out of range, or if the config cannot be written.
+#[tauri::command]
+pub fn apply_profile(
+    mut request: ApplyRequest,
+    state: tauri::State<AppState>,
+) -> Result<ValidationReport, String> {
+    let eq_enabled = request.eq_enabled.unwrap_or(true);
+    let sample_rate = validation::resolve_sample_rate(&state);
+    // The bypass config has no bands, so bypassing must not fail on them
+    let report = if eq_enabled {
+        validate_for_write(&mut request.bands, request.clamp, &state)?
+    } else {
+        ValidationReport {
+            sample_rate,
+            ..Default::default()
+        }
+    };
+    let (mut profile, precision, loudness_settings) = {
+        let settings = state.settings.lock();
+        (
+            requested_profile(&request, &settings),
+            settings.config_precision,
+            settings.loudness,
+        )
+    };
+
+    if eq_enabled {
+        loudness::apply_compensation(
+            &mut profile,
+            &LoudnessSettings {
+                enabled: request.loudness.unwrap_or(loudness_settings.enabled),
+                ..loudness_settings
+            },
+        )?;
+    }
+    write_config(
+        &profile,
+        request.config_path,
+        eq_enabled,
+        &precision,
+        sample_rate,
+    )?;
+    Ok(report)
+}
+
+/// Returns the profile [`apply_profile`] writes for a request.
+///
+/// Stages the request leaves out are taken from the current EQ in
+/// `settings`, where the commands that edit them store them.
+fn requested_profile(request: &ApplyRequest, settings: &AppSettings) -> EqProfile {
+    EqProfile {
+        preamp: request.preamp,
+        bands: request.bands.clone(),
+        graphic_eq: request
+            .graphic_eq
+            .clone()
+            .unwrap_or_else(|| settings.graphic_eq.clone()),
+        convolution: request
+            .convolution
+            .clone()
+            .or_else(|| settings.convolution.clone()),
+        crossfeed: request.crossfeed.or(settings.crossfeed),
+        mid_side: request
+            .mid_side
+            .clone()
+            .or_else(|| settings.mid_side.clone()),
+        routing: request.routing.clone().or_else(|| settings.routing.clone()),
+        vst_plugin: request
+            .vst_plugin
+            .clone()
+            .or_else(|| settings.vst_plugin.clone()),
+        ..Default::default()
+    }
+}
+
+/// Checks the stages of a profile that are validated before the config is
+/// written.
+///
+/// The convolution impulse is checked when it is staged instead (see
+/// [`stage_impulse`]), since that needs the app directory.
+///
+/// # Returns
+///
+/// One error per invalid stage; empty if all are valid:
+/// - The GraphicEQ curve is invalid
+/// - The crossfeed parameters are out of range
+/// - The mid/side width or bands are invalid at `sample_rate`
+/// - The routing has invalid channels, gains or crossover
+/// - The VST plugin library is missing or its state is not base64
+pub fn stage_errors(profile: &EqProfile, sample_rate: u32) -> Vec<String> {
+    let mut results = vec![graphic_eq::validate_points(&profile.graphic_eq)];
+    if let Some(crossfeed) = &profile.crossfeed {
+        results.push(crossfeed.validate());
+    }
+    if let Some(mid_side) = &profile.mid_side {
+        results.push(mid_side.validate(sample_rate));
+    }
+    if let Some(routing) = &profile.routing {
+        results.push(routing.validate());
+    }
+    if let Some(plugin) = &profile.vst_plugin {
+        results.push(plugin.validate());
+    }
+    results.into_iter().filter_map(Result::err).collect()
+}
+
+/// Validates the stages of a profile and generates its config text.
+///
+/// The stages are only checked while EQ is enabled: the bypass config
+/// contains none of them, so bypassing must work even if, say, the VST
+/// plugin has since been uninstalled.
+///
+/// # Errors
+///
+/// Returns the joined [`stage_errors`] if EQ is enabled and a stage is
+/// invalid.
+fn prepare_config(
+    profile: &EqProfile,
+    enabled: bool,
+    precision: &ConfigPrecision,
+    sample_rate: u32,
+) -> Result<String, String> {
+    if enabled {
+        let errors = stage_errors(profile, sample_rate);
+        if !errors.is_empty() {
+            return Err(errors.join("; "));
+        }
+    }
+    Ok(build_config_content(profile, enabled, precision))
+}
+
+/// Writes a profile to an EqualizerAPO config file.
 ///
 /// Generates an EqualizerAPO-compatible configuration and writes it to
 /// the specified path (or `live_config.txt` in the app directory by default).
@@ -375,26 +754,37 @@ pub fn delete_profile(name: String) -> Result<(), String> {
 /// - Grants read access to the Windows Audio Service (`NT SERVICE\AudioSrv`)
 /// - Retries with elevated permissions if initial write fails
 ///
+/// While EQ is enabled, the stages are validated first (see
+/// [`prepare_config`]), and the impulse of a convolution stage is copied
+/// next to the config (see [`stage_impulse`]). In bypass mode no stage is
+/// written, so none is checked.
+///
 /// # Arguments
 ///
-/// * `bands` - Collection of EQ bands to apply
-/// * `preamp` - Global preamp gain in dB
+/// * `profile` - The bands, GraphicEQ curve, stages and preamp to write
 /// * `config_path` - Optional custom config file path
-/// * `eq_enabled` - Whether EQ is enabled (false = bypass mode)
+/// * `enabled` - Whether EQ is enabled (false = bypass mode)
+/// * `precision` - Decimal places for the preamp and band values
+/// * `sample_rate` - Sample rate the mid/side bands are checked against
 ///
 /// # Errors
 ///
 /// Returns an error if:
+/// - EQ is enabled and a stage is invalid (see [`prepare_config`])
+/// - EQ is enabled and the impulse of the convolution stage is missing or
+///   invalid
 /// - The config path is outside allowed directories
 /// - File writing fails (even after permission fix attempt)
 /// - Permission modification fails
-#[tauri::command]
-pub fn apply_profile(
-    bands: Vec<ParametricBand>,
-    preamp: f32,
+pub fn write_config(
+    profile: &EqProfile,
     config_path: Option<String>,
-    eq_enabled: Option<bool>,
+    enabled: bool,
+    precision: &ConfigPrecision,
+    sample_rate: u32,
 ) -> Result<(), String> {
+    let content = prepare_config(profile, enabled, precision, sample_rate)?;
+
     let app_dir = ensure_dirs()?;
     let target_path = config_path
         .map(PathBuf::from)
@@ -402,34 +792,11 @@ pub fn apply_profile(
 
     let target_path = validate_config_path(&target_path, &app_dir)?;
 
-    // Build EqualizerAPO config content
-    let enabled = eq_enabled.unwrap_or(true);
-    let content = if enabled {
-        let mut lines = vec![
-            String::from("; EQAPO GUI Live Configuration"),
-            String::from("; Auto-generated - do not edit manually"),
-            String::from(""),
-            format!("Preamp: {:.1} dB", preamp),
-            String::from(""),
-        ];
-
-        for band in &bands {
-            if band.enabled {
-                lines.push(band.to_eapo_line());
-            }
+    if enabled {
+        if let Some(stage) = &profile.convolution {
+            stage_impulse(stage, &app_dir, &target_path)?;
         }
-
-        lines.join("\r\n")
-    } else {
-        // EQ disabled - write empty config (bypassed)
-        [
-            "; EQAPO GUI Live Configuration",
-            "; EQ DISABLED - Bypass mode",
-            "",
-            "; No filters applied",
-        ]
-        .join("\r\n")
-    };
+    }
 
     // Try to remove readonly attribute if file exists (Windows-specific behavior)
     #[allow(clippy::permissions_set_readonly_false)] // This is Windows-only, Unix warning N/A
@@ -474,6 +841,70 @@ pub fn apply_profile(
     Ok(())
 }
 
+/// Copies a convolution stage's impulse next to a config file.
+///
+/// The impulse is placed in an `impulses/` directory beside the config, which
+/// is where the relative `Convolution:` line points. The copy is skipped when
+/// the config lives in the app directory (the impulse is already there) or
+/// when the destination is up to date.
+///
+/// # Arguments
+///
+/// * `stage` - The convolution stage
+/// * `app_dir` - The application directory holding the source impulse
+/// * `config_path` - The validated config file path
+///
+/// # Errors
+///
+/// Returns an error if the impulse is invalid, the destination is outside
+/// the allowed config directories, or copying fails.
+fn stage_impulse(
+    stage: &ConvolutionStage,
+    app_dir: &Path,
+    config_path: &Path,
+) -> Result<(), String> {
+    convolution::check_stage(app_dir, stage)?;
+    let source = convolution::impulse_path(app_dir, stage)?;
+
+    let config_dir = config_path
+        .parent()
+        .ok_or("Config path has no parent directory")?;
+    let dest_dir = config_dir.join(convolution::IMPULSES_DIR);
+    fs::create_dir_all(&dest_dir)
+        .map_err(|e| format!("Failed to create impulses folder: {}", e))?;
+    let dest = validate_config_path(&dest_dir.join(&stage.file), app_dir)?;
+
+    let source = source
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve impulse path: {}", e))?;
+    if source == dest {
+        return Ok(());
+    }
+
+    let up_to_date = match (fs::metadata(&source), fs::metadata(&dest)) {
+        (Ok(src), Ok(dst)) => {
+            src.len() == dst.len()
+                && matches!(
+                    (src.modified(), dst.modified()),
+                    (Ok(src_time), Ok(dst_time)) if src_time <= dst_time
+                )
+        }
+        _ => false,
+    };
+    if !up_to_date {
+        fs::copy(&source, &dest).map_err(|e| format!("Failed to copy impulse: {}", e))?;
+    }
+
+    // The Windows Audio Service reads the impulse, like the config
+    #[cfg(target_os = "windows")]
+    {
+        ensure_regular_file(&dest)?;
+        run_icacls_grant(&dest, "NT SERVICE\\AudioSrv:R")?;
+    }
+
+    Ok(())
+}
+
 // =============================================================================
 // Tauri Commands - Settings Management
 // =============================================================================
@@ -490,8 +921,8 @@ pub fn get_current_profile(state: tauri::State<AppState>) -> Option<String> {
 
 /// Sets the current profile and persists the change.
 ///
-/// Updates the application state and settings file, then refreshes
-/// the system tray menu to reflect the new selection.
+/// Updates the application state and settings file, then notifies
+/// listeners and refreshes the system tray menu.
 ///
 /// # Arguments
 ///
@@ -510,10 +941,13 @@ pub fn set_current_profile(
 ) -> Result<(), String> {
     {
         let mut settings = state.settings.lock();
+        if let Some(name) = &name {
+            push_recent_profile(&mut settings, name);
+        }
         settings.current_profile = name;
         save_settings(&settings)?;
     }
-    let _ = update_tray_menu(&app);
+    notify_settings_changed(&app);
     Ok(())
 }
 
@@ -527,36 +961,101 @@ pub fn get_settings(state: tauri::State<AppState>) -> AppSettings {
     state.settings.lock().clone()
 }
 
+/// Sets the favorite profiles used by favorite hotkeys and quick menus.
+///
+/// # Arguments
+///
+/// * `favorites` - Profile names for slots 1–9, in order; an empty string
+///   leaves a slot empty
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for tray updates
+///
+/// # Errors
+///
+/// Returns an error if more than 9 favorites are given or settings cannot
+/// be saved.
+#[tauri::command]
+pub fn set_favorites(
+    favorites: Vec<String>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if favorites.len() > MAX_FAVORITES {
+        return Err(format!(
+            "At most {} favorites are supported, got {}",
+            MAX_FAVORITES,
+            favorites.len()
+        ));
+    }
+
+    {
+        let mut settings = state.settings.lock();
+        settings.favorites = favorites;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
 /// Updates application settings from the frontend UI.
 ///
 /// Called whenever the UI state changes to keep settings in sync.
-/// Persists changes to disk and updates the tray menu.
+/// Persists changes to disk, notifies listeners and updates the tray menu.
+/// When `current_profile` changes, the routing, mid/side, convolution,
+/// crossfeed and VST plugin stages are taken from the new profile.
 ///
 /// # Arguments
 ///
-/// * `bands` - Current EQ bands
-/// * `preamp` - Current preamp value
-/// * `current_profile` - Currently active profile name
-/// * `config_path` - Custom config file path
-/// * `eq_enabled` - Whether EQ is enabled
+/// * `update` - Current UI state
 /// * `state` - Tauri managed state
 /// * `app` - Tauri app handle for tray updates
 ///
 /// # Errors
 ///
-/// Returns an error if settings cannot be saved.
+/// Returns an error if the GraphicEQ curve is invalid or settings cannot be
+/// saved.
 #[tauri::command]
 pub fn update_settings(
-    bands: Vec<ParametricBand>,
-    preamp: f32,
-    current_profile: Option<String>,
-    config_path: Option<String>,
-    eq_enabled: Option<bool>,
+    update: SettingsUpdate,
     state: tauri::State<AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
+    let SettingsUpdate {
+        bands,
+        preamp,
+        current_profile,
+        config_path,
+        eq_enabled,
+        graphic_eq,
+    } = update;
+
+    if let Some(points) = &graphic_eq {
+        graphic_eq::validate_points(points)?;
+    }
+
     {
         let mut settings = state.settings.lock();
+        match graphic_eq {
+            Some(points) => settings.graphic_eq = points,
+            None if current_profile != settings.current_profile => {
+                settings.graphic_eq = current_profile
+                    .clone()
+                    .and_then(|name| load_profile(name).ok())
+                    .map(|profile| profile.graphic_eq)
+                    .unwrap_or_default();
+            }
+            None => {}
+        }
+        if current_profile != settings.current_profile {
+            let profile = current_profile
+                .clone()
+                .and_then(|name| load_profile(name).ok());
+            settings.convolution = profile.as_ref().and_then(|p| p.convolution.clone());
+            settings.crossfeed = profile.as_ref().and_then(|p| p.crossfeed);
+            settings.mid_side = profile.as_ref().and_then(|p| p.mid_side.clone());
+            settings.routing = profile.as_ref().and_then(|p| p.routing.clone());
+            settings.vst_plugin = profile.and_then(|p| p.vst_plugin);
+        }
         settings.bands = bands;
         settings.preamp = preamp;
         settings.current_profile = current_profile;
@@ -566,6 +1065,134 @@ pub fn update_settings(
         }
         save_settings(&settings)?;
     }
-    let _ = update_tray_menu(&app);
+    notify_settings_changed(&app);
     Ok(())
 }
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_band, FilterType};
+    use crate::vst::VstPlugin;
+
+    /// A request from the main window, which only sends bands and preamp.
+    fn bands_only_request() -> ApplyRequest {
+        ApplyRequest {
+            bands: vec![test_band(FilterType::Peaking, 1000.0, 3.0, 1.41)],
+            preamp: -3.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn apply_keeps_vst_plugin_from_settings() {
+        let settings = AppSettings {
+            vst_plugin: Some(VstPlugin {
+                library: "limiter.dll".to_string(),
+                chunk: None,
+            }),
+            ..Default::default()
+        };
+        let profile = requested_profile(&bands_only_request(), &settings);
+        assert_eq!(profile.vst_plugin, settings.vst_plugin);
+        assert_eq!(profile.bands.len(), 1);
+        assert_eq!(profile.preamp, -3.0);
+    }
+
+    #[test]
+    fn apply_keeps_graphic_eq_from_settings() {
+        let curve = vec![
+            GraphicEqPoint {
+                frequency: 100.0,
+                gain: 2.0,
+            },
+            GraphicEqPoint {
+                frequency: 1000.0,
+                gain: 0.0,
+            },
+        ];
+        let settings = AppSettings {
+            graphic_eq: curve.clone(),
+            ..Default::default()
+        };
+        let profile = requested_profile(&bands_only_request(), &settings);
+        assert_eq!(profile.graphic_eq, curve);
+        let content = build_config_content(&profile, true, &ConfigPrecision::default());
+        assert!(content.contains("GraphicEQ: "));
+
+        // A curve sent with the request replaces the stored one
+        let request = ApplyRequest {
+            graphic_eq: Some(Vec::new()),
+            ..bands_only_request()
+        };
+        assert!(requested_profile(&request, &settings).graphic_eq.is_empty());
+    }
+
+    #[test]
+    fn apply_keeps_convolution_from_settings() {
+        let settings = AppSettings {
+            convolution: Some(ConvolutionStage {
+                file: "room.wav".to_string(),
+            }),
+            ..Default::default()
+        };
+        let profile = requested_profile(&bands_only_request(), &settings);
+        assert_eq!(profile.convolution, settings.convolution);
+    }
+
+    #[test]
+    fn apply_keeps_crossfeed_from_settings() {
+        let settings = AppSettings {
+            crossfeed: Some(crate::crossfeed::Crossfeed::default()),
+            ..Default::default()
+        };
+        let profile = requested_profile(&bands_only_request(), &settings);
+        assert_eq!(profile.crossfeed, settings.crossfeed);
+    }
+
+    #[test]
+    fn apply_keeps_mid_side_from_settings() {
+        let settings = AppSettings {
+            mid_side: Some(crate::mid_side::MidSide {
+                width: 1.5,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let profile = requested_profile(&bands_only_request(), &settings);
+        assert_eq!(profile.mid_side.map(|m| m.width), Some(1.5));
+    }
+
+    #[test]
+    fn apply_keeps_routing_from_settings() {
+        let settings = AppSettings {
+            routing: Some(crate::routing::Routing::default()),
+            ..Default::default()
+        };
+        let profile = requested_profile(&bands_only_request(), &settings);
+        assert_eq!(profile.routing, settings.routing);
+    }
+
+    #[test]
+    fn bypass_succeeds_with_missing_plugin() {
+        let profile = EqProfile {
+            vst_plugin: Some(VstPlugin {
+                library: "missing-plugin.dll".to_string(),
+                chunk: None,
+            }),
+            ..Default::default()
+        };
+        let precision = ConfigPrecision::default();
+
+        let err = prepare_config(&profile, true, &precision, 48000).unwrap_err();
+        assert_eq!(err, "VST plugin 'missing-plugin.dll' not found");
+
+        let content = prepare_config(&profile, false, &precision, 48000).unwrap();
+        assert!(content.contains("EQ DISABLED"));
+        assert!(!content.contains("missing-plugin.dll"));
+    }
+}
@@ -0,0 +1,492 @@
+//! Import and export of Room EQ Wizard (REW) filter settings files.
+//!
+//! REW's "Export filter settings as text" writes a header followed by one
+//! line per filter slot:
+//!
+//! ```text
+//! Filter Settings file
+//!
+//! Room EQ V5.20
+//! Dated: 12-Jan-2022 10:32:00
+//!
+//! Notes:
+//!
+//! Equaliser: Generic
+//! Filter  1: ON  PK       Fc   50.00 Hz  Gain  -5.00 dB  Q  4.000
+//! Filter  2: ON  LS       Fc   105.0 Hz  Gain   3.00 dB
+//! Filter  3: ON  PK       Fc   1000 Hz   Gain  -2.00 dB  BW/60  10.00
+//! Filter  4: OFF None
+//! ```
+//!
+//! # Filter Mapping
+//!
+//! | REW type                         | Imported as                        |
+//! |----------------------------------|------------------------------------|
+//! | `PK`, `PEQ`, `Modal`             | Peaking (`T60` values are ignored) |
+//! | `LS`, `LSC`, `LSQ`, `LS 12dB`    | Low shelf (Q 0.707 if none given)  |
+//! | `HS`, `HSC`, `HSQ`, `HS 12dB`    | High shelf (Q 0.707 if none given) |
+//! | `LS 6dB`, `HS 6dB`               | Shelf with Q 0.707, with a warning |
+//! | `LP`, `HP`, `NO`, `AP`, ...      | Skipped, with a warning            |
+//! | `None`                           | Empty slot, skipped                |
+//!
+//! Bandwidth can be given as `Q`, `BW Oct` (octaves) or `BW/60`
+//! (1/60ths of an octave); bandwidths are converted to Q. `OFF` filters
+//! are imported as disabled bands.
+//!
+//! Exported files use the same layout with `PK`/`LSC`/`HSC` filters and
+//! always give Q. The preamp, which REW does not store, is written as an
+//! EqualizerAPO `Preamp:` line that REW ignores and this importer reads.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::profile::{list_profiles, load_profile, sanitize_profile_name, write_profile};
+use crate::tray::update_tray_menu;
+use crate::types::{EqProfile, FilterType, ParametricBand, ProfileSource};
+
+/// Format identifier stored in [`ProfileSource::format`].
+pub const REW_FORMAT_ID: &str = "rew-filters";
+
+/// Q used for shelves without an explicit Q.
+const DEFAULT_SHELF_Q: f32 = 0.707;
+
+/// A parsed filter settings file.
+#[derive(Debug, Clone, Serialize)]
+pub struct RewImport {
+    /// The imported profile.
+    pub profile: EqProfile,
+    /// Filters that were skipped or approximated, one message each.
+    pub warnings: Vec<String>,
+}
+
+/// Result of parsing a single filter line.
+#[derive(Debug, Clone)]
+pub enum RewFilter {
+    /// A filter the app can represent.
+    Band(ParametricBand),
+    /// An empty (`None`) slot.
+    Empty,
+    /// A filter type the app cannot represent.
+    Unsupported(String),
+}
+
+// =============================================================================
+// Parsing
+// =============================================================================
+
+/// Parses a number, accepting a comma as decimal separator.
+fn parse_number(token: Option<&str>, field: &str) -> Result<f32, String> {
+    let token = token.ok_or_else(|| format!("Missing value for {}", field))?;
+    token
+        .replace(',', ".")
+        .parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("Invalid {} value '{}'", field, token))
+}
+
+/// Converts a bandwidth in octaves to Q.
+pub fn bandwidth_to_q(octaves: f32) -> f32 {
+    let ratio = 2f32.powf(octaves);
+    ratio.sqrt() / (ratio - 1.0)
+}
+
+/// Returns the slot text after `Filter N:`, or `None` if `line` is not a
+/// filter line (e.g. the `Filter Settings file` header).
+fn filter_slot(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("Filter")?;
+    let (number, slot) = rest.split_once(':')?;
+    number
+        .trim()
+        .chars()
+        .all(|c| c.is_ascii_digit())
+        .then_some(slot)
+}
+
+/// Parses a REW filter line such as
+/// `Filter  1: ON  PK       Fc   50.00 Hz  Gain  -5.00 dB  Q  4.000`.
+///
+/// # Errors
+///
+/// Returns an error if the line is not a filter line or a value is missing
+/// or invalid.
+pub fn parse_rew_filter_line(line: &str) -> Result<RewFilter, String> {
+    let slot = filter_slot(line.trim()).ok_or_else(|| format!("Not a filter line: '{}'", line))?;
+
+    let mut tokens = slot.split_whitespace().peekable();
+    let enabled = match tokens.next() {
+        Some("ON") => true,
+        Some("OFF") => false,
+        other => {
+            return Err(format!(
+                "Expected ON or OFF, got '{}'",
+                other.unwrap_or_default()
+            ))
+        }
+    };
+
+    let type_code = tokens.next().unwrap_or("None");
+    let filter_type = match type_code {
+        "None" => return Ok(RewFilter::Empty),
+        "PK" | "PEQ" | "Modal" => FilterType::Peaking,
+        "LS" | "LSC" | "LSQ" => FilterType::LowShelf,
+        "HS" | "HSC" | "HSQ" => FilterType::HighShelf,
+        other => return Ok(RewFilter::Unsupported(other.to_string())),
+    };
+
+    // Shelf slope, e.g. `LS 12dB`; see `parse_rew_filters` for 6 dB shelves
+    tokens.next_if(|t| t.ends_with("dB"));
+
+    let (mut frequency, mut gain, mut q_factor) = (None, 0.0, None);
+    while let Some(token) = tokens.next() {
+        match token {
+            "Fc" => frequency = Some(parse_number(tokens.next(), "Fc")?),
+            "Gain" => gain = parse_number(tokens.next(), "Gain")?,
+            "Q" => q_factor = Some(parse_number(tokens.next(), "Q")?),
+            "BW/60" => {
+                let bw = parse_number(tokens.next(), "BW/60")?;
+                q_factor = Some(bandwidth_to_q(bw / 60.0));
+            }
+            "BW" => {
+                tokens.next_if_eq(&"Oct");
+                q_factor = Some(bandwidth_to_q(parse_number(tokens.next(), "BW")?));
+            }
+            // Units, and the T60 target of modal filters
+            _ => {}
+        }
+    }
+
+    let frequency = frequency
+        .filter(|f| *f > 0.0)
+        .ok_or("Missing or non-positive Fc")?;
+    let q_factor = match (q_factor, &filter_type) {
+        (Some(q), _) if q > 0.0 && q.is_finite() => q,
+        (Some(q), _) => return Err(format!("Q must be positive and finite, got {}", q)),
+        (None, FilterType::LowShelf | FilterType::HighShelf) => DEFAULT_SHELF_Q,
+        (None, FilterType::Peaking) => return Err("Missing Q or bandwidth".to_string()),
+    };
+
+    Ok(RewFilter::Band(ParametricBand {
+        filter_type,
+        frequency,
+        gain,
+        q_factor,
+        enabled,
+    }))
+}
+
+/// Parses the content of a REW filter settings file into a profile.
+///
+/// Header lines are ignored. Unsupported filters are skipped and 6 dB
+/// shelves approximated, each with a warning.
+///
+/// # Errors
+///
+/// Returns an error naming the line number if a filter line is malformed,
+/// or if the file contains no filter lines.
+pub fn parse_rew_filters(name: &str, content: &str) -> Result<RewImport, String> {
+    let mut profile = EqProfile {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    let mut warnings = Vec::new();
+    let mut found = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        let error = |e: String| format!("Line {}: {}", index + 1, e);
+
+        if let Some(rest) = line.strip_prefix("Preamp:") {
+            profile.preamp +=
+                parse_number(rest.split_whitespace().next(), "preamp").map_err(error)?;
+            continue;
+        }
+        if filter_slot(line).is_none() {
+            continue;
+        }
+
+        found = true;
+        match parse_rew_filter_line(line).map_err(error)? {
+            RewFilter::Band(band) => {
+                if line.split_whitespace().any(|t| t == "6dB") {
+                    warnings.push(format!(
+                        "Line {}: 6 dB/octave shelf approximated with Q {}",
+                        index + 1,
+                        DEFAULT_SHELF_Q
+                    ));
+                }
+                profile.bands.push(band);
+            }
+            RewFilter::Empty => {}
+            RewFilter::Unsupported(code) => warnings.push(format!(
+                "Line {}: unsupported filter type '{}' skipped",
+                index + 1,
+                code
+            )),
+        }
+    }
+
+    if !found {
+        return Err("No filters found".to_string());
+    }
+    Ok(RewImport { profile, warnings })
+}
+
+/// Reads and parses a REW filter settings file.
+///
+/// The profile is named after the file unless `name` is given, and records
+/// the file in its metadata.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, or no name can be
+/// derived.
+pub fn read_rew_file(path: &Path, name: Option<&str>) -> Result<RewImport, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+
+    let name = sanitize_profile_name(
+        name.or_else(|| path.file_stem().and_then(|s| s.to_str()))
+            .unwrap_or_default(),
+    );
+    if name.is_empty() {
+        return Err(format!("Cannot derive a profile name from {:?}", path));
+    }
+
+    let mut import = parse_rew_filters(&name, &content)?;
+    import.profile.metadata.source = Some(ProfileSource {
+        format: REW_FORMAT_ID.to_string(),
+        path: path.to_string_lossy().into_owned(),
+    });
+    Ok(import)
+}
+
+// =============================================================================
+// Export
+// =============================================================================
+
+/// Formats a profile as a REW filter settings file.
+///
+/// Lines are joined with `\r\n`, as REW writes them on Windows. The
+/// GraphicEQ curve, if any, has no REW equivalent and is not included.
+#[must_use]
+pub fn to_rew_filters(profile: &EqProfile) -> String {
+    let mut lines = vec![
+        "Filter Settings file".to_string(),
+        String::new(),
+        format!("Notes:{}", profile.name),
+        String::new(),
+        "Equaliser: Generic".to_string(),
+    ];
+    if profile.preamp != 0.0 {
+        lines.push(format!("Preamp: {} dB", profile.preamp));
+    }
+    for (i, band) in profile.bands.iter().enumerate() {
+        lines.push(format!(
+            "Filter {:>2}: {:<3} {:<4} Fc {} Hz  Gain {} dB  Q {}",
+            i + 1,
+            if band.enabled { "ON" } else { "OFF" },
+            band.filter_type.to_eapo_code(),
+            band.frequency,
+            band.gain,
+            band.q_factor
+        ));
+    }
+    lines.join("\r\n") + "\r\n"
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Imports a REW filter settings file and saves it as a profile.
+///
+/// # Arguments
+///
+/// * `path` - Path of the file to import
+/// * `name` - Profile name; derived from the file name if `None`
+/// * `overwrite` - Replace an existing profile with the same name
+/// * `app` - Tauri app handle for tray updates
+///
+/// # Returns
+///
+/// The saved profile and any warnings about skipped filters.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be parsed, or a profile with the same
+/// name exists and `overwrite` is `false`.
+#[tauri::command]
+pub fn import_rew_filters(
+    path: String,
+    name: Option<String>,
+    overwrite: bool,
+    app: AppHandle,
+) -> Result<RewImport, String> {
+    let import = read_rew_file(Path::new(&path), name.as_deref())?;
+    if !overwrite && list_profiles()?.contains(&import.profile.name) {
+        return Err(format!("Profile '{}' already exists", import.profile.name));
+    }
+    write_profile(&import.profile)?;
+
+    let _ = update_tray_menu(&app);
+    Ok(import)
+}
+
+/// Exports a saved profile as a REW filter settings file.
+///
+/// # Arguments
+///
+/// * `name` - The profile to export
+/// * `path` - Destination file
+///
+/// # Errors
+///
+/// Returns an error if the profile cannot be loaded or the file cannot be
+/// written.
+#[tauri::command]
+pub fn export_rew_filters(name: String, path: String) -> Result<(), String> {
+    let profile = load_profile(name)?;
+    fs::write(&path, to_rew_filters(&profile))
+        .map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REW_EXPORT: &str = "Filter Settings file\r\n\
+        \r\n\
+        Room EQ V5.20\r\n\
+        Dated: 12-Jan-2022 10:32:00\r\n\
+        \r\n\
+        Notes:\r\n\
+        \r\n\
+        Equaliser: Generic\r\n\
+        Jan 12 10:31:30\r\n\
+        Filter  1: ON  PK       Fc   50.00 Hz  Gain  -5.00 dB  Q  4.000\r\n\
+        Filter  2: ON  LS       Fc   105.0 Hz  Gain   3.00 dB\r\n\
+        Filter  3: OFF PK       Fc   1000 Hz   Gain  -2.00 dB  BW/60  60.00\r\n\
+        Filter  4: ON  HP       Fc   20.00 Hz\r\n\
+        Filter  5: ON  Modal    Fc   45.00 Hz  Gain  -6.00 dB  Q  5.000  T60 target  300 ms\r\n\
+        Filter  6: ON  None\r\n";
+
+    fn band(line: &str) -> ParametricBand {
+        match parse_rew_filter_line(line).unwrap() {
+            RewFilter::Band(band) => band,
+            other => panic!("expected a band, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_rew_export() {
+        let import = parse_rew_filters("Room", REW_EXPORT).unwrap();
+        let bands = &import.profile.bands;
+        assert_eq!(bands.len(), 4);
+
+        assert_eq!(bands[0].filter_type, FilterType::Peaking);
+        assert_eq!(bands[0].frequency, 50.0);
+        assert_eq!(bands[0].gain, -5.0);
+        assert_eq!(bands[0].q_factor, 4.0);
+
+        assert_eq!(bands[1].filter_type, FilterType::LowShelf);
+        assert_eq!(bands[1].q_factor, DEFAULT_SHELF_Q);
+
+        assert!(!bands[2].enabled);
+        assert!((bands[2].q_factor - 1.414).abs() < 0.001);
+
+        assert_eq!(bands[3].frequency, 45.0);
+        assert_eq!(bands[3].q_factor, 5.0);
+
+        assert_eq!(import.warnings.len(), 1);
+        assert!(import.warnings[0].contains("'HP'"));
+    }
+
+    #[test]
+    fn converts_bandwidth_variants() {
+        let one_octave = band("Filter 1: ON PK Fc 100 Hz Gain 1 dB BW Oct 1.0");
+        assert!((one_octave.q_factor - 1.414).abs() < 0.001);
+        let third = band("Filter 1: ON PK Fc 100 Hz Gain 1 dB BW/60 20");
+        assert!((third.q_factor - 4.318).abs() < 0.001);
+    }
+
+    #[test]
+    fn shelf_slope_variants() {
+        let shelf = band("Filter 1: ON LSC 12dB Fc 105 Hz Gain 5.5 dB Q 0.7");
+        assert_eq!(shelf.filter_type, FilterType::LowShelf);
+        assert_eq!(shelf.q_factor, 0.7);
+
+        let import = parse_rew_filters("x", "Filter 1: ON HS 6dB Fc 8000 Hz Gain -2 dB").unwrap();
+        assert_eq!(import.profile.bands[0].filter_type, FilterType::HighShelf);
+        assert_eq!(import.warnings.len(), 1);
+    }
+
+    #[test]
+    fn accepts_comma_decimals() {
+        let band = band("Filter 1: ON PK Fc 62,5 Hz Gain -3,5 dB Q 2,0");
+        assert_eq!(band.frequency, 62.5);
+        assert_eq!(band.gain, -3.5);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(parse_rew_filters("x", "Filter Settings file\nNotes:\n").is_err());
+        let err = parse_rew_filters("x", "Filter 1: ON PK Fc abc Hz Gain 1 dB Q 1").unwrap_err();
+        assert!(err.starts_with("Line 1"), "{}", err);
+        assert!(parse_rew_filter_line("Filter 1: ON PK Fc 100 Hz Gain 1 dB").is_err());
+    }
+
+    #[test]
+    fn rejects_zero_bandwidth() {
+        assert!(parse_rew_filter_line("Filter 1: ON PK Fc 100 Hz Gain 1 dB BW Oct 0").is_err());
+        assert!(parse_rew_filter_line("Filter 1: ON PK Fc 100 Hz Gain 1 dB BW/60 0").is_err());
+    }
+
+    #[test]
+    fn export_round_trips() {
+        let profile = EqProfile {
+            name: "Room".to_string(),
+            preamp: -4.5,
+            bands: vec![
+                ParametricBand {
+                    filter_type: FilterType::Peaking,
+                    frequency: 47.25,
+                    gain: -6.3,
+                    q_factor: 4.125,
+                    enabled: true,
+                },
+                ParametricBand {
+                    filter_type: FilterType::HighShelf,
+                    frequency: 9000.0,
+                    gain: 2.0,
+                    q_factor: 0.6,
+                    enabled: false,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let text = to_rew_filters(&profile);
+        assert!(text.starts_with("Filter Settings file\r\n"));
+        assert!(text.contains("Filter  1: ON  PK   Fc 47.25 Hz  Gain -6.3 dB  Q 4.125\r\n"));
+
+        let import = parse_rew_filters("Room", &text).unwrap();
+        assert!(import.warnings.is_empty());
+        assert_eq!(import.profile.preamp, profile.preamp);
+        assert_eq!(import.profile.bands.len(), 2);
+        for (a, b) in import.profile.bands.iter().zip(&profile.bands) {
+            assert_eq!(a.filter_type, b.filter_type);
+            assert_eq!(a.frequency, b.frequency);
+            assert_eq!(a.gain, b.gain);
+            assert_eq!(a.q_factor, b.q_factor);
+            assert_eq!(a.enabled, b.enabled);
+        }
+    }
+}
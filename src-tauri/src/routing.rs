@@ -0,0 +1,645 @@
+//! Channel routing and bass management for speaker setups.
+//!
+//! A profile's routing section is expanded into two blocks of EqualizerAPO
+//! directives:
+//!
+//! 1. **Matrix**, right after the preamp: a downmix/upmix preset, custom
+//!    matrix rows, channel swaps and mutes, all in a single `Copy:` line.
+//!    EqualizerAPO reads every source before assigning any target, so
+//!    `L=R R=L` swaps the channels.
+//! 2. **Bass management**, after all other filters: bass from the main
+//!    channels is redirected to the LFE channel with a 4th-order
+//!    Linkwitz-Riley crossover (two cascaded Butterworth sections),
+//!    low-passed on the sub and high-passed on the mains.
+//!
+//! ```text
+//! Copy: L=0.414*L+0.293*C+0.293*SL R=0.414*R+0.293*C+0.293*SR C=0 SL=0 SR=0
+//! …
+//! Copy: LFE=LFE+L+R
+//! Channel: LFE
+//! Filter: ON LPQ Fc 80 Hz Q 0.707
+//! Filter: ON LPQ Fc 80 Hz Q 0.707
+//! Channel: L R
+//! Filter: ON HPQ Fc 80 Hz Q 0.707
+//! Filter: ON HPQ Fc 80 Hz Q 0.707
+//! Channel: all
+//! ```
+//!
+//! Channel names are EqualizerAPO's (`L`, `R`, `C`, `LFE`, `RL`, `RR`,
+//! `SL`, `SR`, ...); the presets assume a 5.1 layout with side surrounds.
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::actions::notify_settings_changed;
+use crate::profile::{load_profile, save_settings, write_profile};
+use crate::types::{format_config_number, AppState};
+
+// =============================================================================
+// Constants
+// =============================================================================
+
+/// Channel that receives redirected bass.
+const LFE: &str = "LFE";
+
+/// Q of each Butterworth section of the Linkwitz-Riley crossover.
+const BUTTERWORTH_Q: f32 = 0.707;
+
+/// Allowed crossover frequency range in Hz.
+pub const CROSSOVER_RANGE: (f32, f32) = (40.0, 250.0);
+
+/// Largest magnitude of a matrix gain.
+pub const MAX_MATRIX_GAIN: f32 = 4.0;
+
+/// Largest magnitude of the redirected bass gain in dB.
+pub const MAX_LFE_GAIN_DB: f32 = 20.0;
+
+/// Decimal places of matrix gains in the config.
+const GAIN_DECIMALS: i32 = 4;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// A source channel and its gain in a matrix row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MixTerm {
+    pub channel: String,
+    pub gain: f32,
+}
+
+/// One output channel of the routing matrix as a sum of input channels.
+///
+/// A row without terms silences its channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatrixRow {
+    pub target: String,
+    #[serde(default)]
+    pub terms: Vec<MixTerm>,
+}
+
+/// Built-in downmix and upmix matrices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MixPreset {
+    /// 5.1 to front left/right (ITU-R BS.775 coefficients, normalized so
+    /// full-scale input cannot clip). Center and surrounds are silenced; LFE
+    /// is left alone.
+    Downmix51ToStereo,
+    /// Stereo to 5.1: center from the sum of left and right, surrounds from
+    /// left and right at −3 dB.
+    UpmixStereoTo51,
+}
+
+impl MixPreset {
+    /// Matrix rows of the preset.
+    pub fn rows(self) -> Vec<MatrixRow> {
+        let row = |target: &str, terms: &[(&str, f32)]| MatrixRow {
+            target: target.to_string(),
+            terms: terms
+                .iter()
+                .map(|&(channel, gain)| MixTerm {
+                    channel: channel.to_string(),
+                    gain,
+                })
+                .collect(),
+        };
+        match self {
+            Self::Downmix51ToStereo => vec![
+                row("L", &[("L", 0.414), ("C", 0.293), ("SL", 0.293)]),
+                row("R", &[("R", 0.414), ("C", 0.293), ("SR", 0.293)]),
+                row("C", &[]),
+                row("SL", &[]),
+                row("SR", &[]),
+            ],
+            Self::UpmixStereoTo51 => vec![
+                row("C", &[("L", 0.5), ("R", 0.5)]),
+                row("SL", &[("L", 0.708)]),
+                row("SR", &[("R", 0.708)]),
+            ],
+        }
+    }
+}
+
+/// Two channels to exchange.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelSwap {
+    pub first: String,
+    pub second: String,
+}
+
+fn default_crossover() -> f32 {
+    80.0
+}
+
+fn default_mains() -> Vec<String> {
+    vec!["L".to_string(), "R".to_string()]
+}
+
+/// Redirection of bass from the main channels to the LFE channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BassManagement {
+    /// Crossover frequency in Hz.
+    #[serde(default = "default_crossover")]
+    pub crossover: f32,
+
+    /// Channels whose bass is redirected; `L` and `R` by default. Add `C`,
+    /// `SL`, `SR`, ... for 5.1 and larger setups.
+    #[serde(default = "default_mains")]
+    pub mains: Vec<String>,
+
+    /// Gain of the redirected bass in dB.
+    #[serde(default)]
+    pub lfe_gain: f32,
+}
+
+impl Default for BassManagement {
+    fn default() -> Self {
+        Self {
+            crossover: default_crossover(),
+            mains: default_mains(),
+            lfe_gain: 0.0,
+        }
+    }
+}
+
+/// Routing section of a profile.
+///
+/// The matrix is applied in this order: preset, custom rows, swaps, mutes.
+/// Muted channels contribute no bass to the LFE channel either.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Routing {
+    /// Built-in downmix or upmix, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<MixPreset>,
+
+    /// Custom matrix rows, applied after the preset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matrix: Vec<MatrixRow>,
+
+    /// Channel pairs to exchange.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub swaps: Vec<ChannelSwap>,
+
+    /// Channels to silence.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub muted: Vec<String>,
+
+    /// Bass redirection to the LFE channel, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bass_management: Option<BassManagement>,
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// Checks that a channel name can be used in `Copy:` and `Channel:` lines.
+///
+/// # Errors
+///
+/// Returns an error if the name is empty or contains anything other than
+/// ASCII letters, digits and underscores.
+pub fn validate_channel(name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid channel name '{}'", name));
+    }
+    Ok(())
+}
+
+impl Routing {
+    /// Checks channel names, gains and the crossover.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first invalid entry.
+    pub fn validate(&self) -> Result<(), String> {
+        for row in &self.matrix {
+            validate_channel(&row.target)?;
+            for term in &row.terms {
+                validate_channel(&term.channel)?;
+                if !(term.gain.is_finite() && term.gain.abs() <= MAX_MATRIX_GAIN) {
+                    return Err(format!(
+                        "Gain of {} in channel {} must be between -{max} and {max}, got {}",
+                        term.channel,
+                        row.target,
+                        term.gain,
+                        max = MAX_MATRIX_GAIN
+                    ));
+                }
+            }
+        }
+
+        let mut swapped: Vec<&str> = Vec::new();
+        for swap in &self.swaps {
+            validate_channel(&swap.first)?;
+            validate_channel(&swap.second)?;
+            if swap.first == swap.second {
+                return Err(format!("Cannot swap channel {} with itself", swap.first));
+            }
+            for channel in [&swap.first, &swap.second] {
+                if swapped.contains(&channel.as_str()) {
+                    return Err(format!("Channel {} is swapped more than once", channel));
+                }
+                swapped.push(channel);
+            }
+        }
+
+        for channel in &self.muted {
+            validate_channel(channel)?;
+        }
+
+        if let Some(bass) = &self.bass_management {
+            let (min, max) = CROSSOVER_RANGE;
+            if !(min..=max).contains(&bass.crossover) {
+                return Err(format!(
+                    "Crossover must be between {} and {} Hz, got {}",
+                    min, max, bass.crossover
+                ));
+            }
+            if !(bass.lfe_gain.is_finite() && bass.lfe_gain.abs() <= MAX_LFE_GAIN_DB) {
+                return Err(format!(
+                    "LFE gain must be between -{max} and {max} dB, got {}",
+                    bass.lfe_gain,
+                    max = MAX_LFE_GAIN_DB
+                ));
+            }
+            if bass.mains.is_empty() {
+                return Err("Bass management needs at least one main channel".to_string());
+            }
+            for channel in &bass.mains {
+                validate_channel(channel)?;
+                if channel == LFE {
+                    return Err("The LFE channel cannot be a main channel".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Config Generation
+// =============================================================================
+
+/// Formats a matrix gain, rounded to [`GAIN_DECIMALS`] places.
+fn format_gain(gain: f32) -> String {
+    let factor = 10f32.powi(GAIN_DECIMALS);
+    format_config_number((gain * factor).round() / factor, None, 0)
+}
+
+/// Formats a sum of terms, e.g. `0.5*L+-0.5*R`, or `0` without terms.
+fn format_sum(terms: &[MixTerm]) -> String {
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+    terms
+        .iter()
+        .map(|term| {
+            if term.gain == 1.0 {
+                term.channel.clone()
+            } else {
+                format!("{}*{}", format_gain(term.gain), term.channel)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Appends a stage to a matrix, rewriting the stage's sources in terms of
+/// the matrix output so that the result still reads the original inputs.
+fn compose(rows: &mut Vec<MatrixRow>, stage: Vec<MatrixRow>) {
+    let previous = rows.clone();
+    let output_of = |channel: &str| -> Vec<MixTerm> {
+        previous
+            .iter()
+            .find(|row| row.target == channel)
+            .map(|row| row.terms.clone())
+            .unwrap_or_else(|| {
+                vec![MixTerm {
+                    channel: channel.to_string(),
+                    gain: 1.0,
+                }]
+            })
+    };
+
+    for row in stage {
+        let mut terms: Vec<MixTerm> = Vec::new();
+        for term in &row.terms {
+            for source in output_of(&term.channel) {
+                let gain = term.gain * source.gain;
+                match terms.iter_mut().find(|t| t.channel == source.channel) {
+                    Some(existing) => existing.gain += gain,
+                    None => terms.push(MixTerm {
+                        channel: source.channel,
+                        gain,
+                    }),
+                }
+            }
+        }
+        terms.retain(|t| t.gain != 0.0);
+        match rows.iter_mut().find(|r| r.target == row.target) {
+            Some(existing) => existing.terms = terms,
+            None => rows.push(MatrixRow {
+                target: row.target,
+                terms,
+            }),
+        }
+    }
+}
+
+impl Routing {
+    /// Combines the preset, custom rows, swaps and mutes into one matrix.
+    ///
+    /// Each stage is expressed in terms of the original input channels, so
+    /// the result can be written as a single `Copy:` line.
+    pub fn resolved_matrix(&self) -> Vec<MatrixRow> {
+        let mut rows: Vec<MatrixRow> = Vec::new();
+        if let Some(preset) = self.preset {
+            compose(&mut rows, preset.rows());
+        }
+        compose(&mut rows, self.matrix.clone());
+
+        let swaps = self
+            .swaps
+            .iter()
+            .flat_map(|swap| {
+                [(&swap.first, &swap.second), (&swap.second, &swap.first)].map(
+                    |(target, source)| MatrixRow {
+                        target: target.clone(),
+                        terms: vec![MixTerm {
+                            channel: source.clone(),
+                            gain: 1.0,
+                        }],
+                    },
+                )
+            })
+            .collect();
+        compose(&mut rows, swaps);
+
+        let mutes = self
+            .muted
+            .iter()
+            .map(|channel| MatrixRow {
+                target: channel.clone(),
+                terms: Vec::new(),
+            })
+            .collect();
+        compose(&mut rows, mutes);
+
+        // Drop rows that pass their channel through unchanged
+        rows.retain(|row| {
+            !(row.terms.len() == 1
+                && row.terms[0].channel == row.target
+                && row.terms[0].gain == 1.0)
+        });
+        rows
+    }
+
+    /// Returns the matrix directives, written right after the preamp.
+    ///
+    /// Empty if the routing does not change any channel.
+    #[must_use]
+    pub fn matrix_lines(&self) -> Vec<String> {
+        let rows = self.resolved_matrix();
+        if rows.is_empty() {
+            return Vec::new();
+        }
+        let assignments: Vec<String> = rows
+            .iter()
+            .map(|row| format!("{}={}", row.target, format_sum(&row.terms)))
+            .collect();
+        vec![
+            String::from("; Channel routing"),
+            format!("Copy: {}", assignments.join(" ")),
+        ]
+    }
+
+    /// Returns the bass management directives, written after all other
+    /// filters.
+    ///
+    /// The last line resets the channel selection to `all`. Empty without
+    /// bass management.
+    #[must_use]
+    pub fn bass_management_lines(&self) -> Vec<String> {
+        let Some(bass) = &self.bass_management else {
+            return Vec::new();
+        };
+        let mains: Vec<&String> = bass
+            .mains
+            .iter()
+            .filter(|channel| !self.muted.contains(channel))
+            .collect();
+        let gain = 10f32.powf(bass.lfe_gain / 20.0);
+        let mut terms = vec![MixTerm {
+            channel: LFE.to_string(),
+            gain: 1.0,
+        }];
+        terms.extend(mains.iter().map(|channel| MixTerm {
+            channel: channel.to_string(),
+            gain,
+        }));
+
+        let crossover = format_config_number(bass.crossover, None, 0);
+        let q = format_config_number(BUTTERWORTH_Q, None, 0);
+        let section = |kind: &str| format!("Filter: ON {} Fc {} Hz Q {}", kind, crossover, q);
+
+        let mut lines = vec![
+            format!("; Bass management: {} Hz", crossover),
+            format!("Copy: {}={}", LFE, format_sum(&terms)),
+            format!("Channel: {}", LFE),
+            section("LPQ"),
+            section("LPQ"),
+        ];
+        if !mains.is_empty() {
+            lines.push(format!(
+                "Channel: {}",
+                mains
+                    .iter()
+                    .map(|c| c.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ));
+            lines.push(section("HPQ"));
+            lines.push(section("HPQ"));
+        }
+        lines.push(String::from("Channel: all"));
+        lines
+    }
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Sets or removes the routing section of a saved profile.
+///
+/// # Arguments
+///
+/// * `name` - The profile name
+/// * `routing` - The new routing, or `None` to remove it
+///
+/// # Errors
+///
+/// Returns an error if the routing is invalid or the profile cannot be
+/// loaded or written.
+#[tauri::command]
+pub fn set_profile_routing(name: String, routing: Option<Routing>) -> Result<(), String> {
+    if let Some(routing) = &routing {
+        routing.validate()?;
+    }
+    let mut profile = load_profile(name)?;
+    profile.routing = routing;
+    write_profile(&profile)
+}
+
+/// Sets or removes the routing section of the current EQ.
+///
+/// The routing is written to the config on the next apply.
+///
+/// # Arguments
+///
+/// * `routing` - The new routing, or `None` to remove it
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for notifications
+///
+/// # Errors
+///
+/// Returns an error if the routing is invalid or settings cannot be saved.
+#[tauri::command]
+pub fn set_routing(
+    routing: Option<Routing>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if let Some(routing) = &routing {
+        routing.validate()?;
+    }
+    {
+        let mut settings = state.settings.lock();
+        settings.routing = routing;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swap(first: &str, second: &str) -> ChannelSwap {
+        ChannelSwap {
+            first: first.to_string(),
+            second: second.to_string(),
+        }
+    }
+
+    #[test]
+    fn downmix_preset_writes_one_copy_line() {
+        let routing = Routing {
+            preset: Some(MixPreset::Downmix51ToStereo),
+            ..Default::default()
+        };
+        assert_eq!(
+            routing.matrix_lines()[1],
+            "Copy: L=0.414*L+0.293*C+0.293*SL R=0.414*R+0.293*C+0.293*SR C=0 SL=0 SR=0"
+        );
+    }
+
+    #[test]
+    fn swaps_and_mutes_compose_with_the_preset() {
+        let routing = Routing {
+            preset: Some(MixPreset::UpmixStereoTo51),
+            swaps: vec![swap("L", "R")],
+            muted: vec!["SR".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            routing.matrix_lines()[1],
+            "Copy: C=0.5*L+0.5*R SL=0.708*L SR=0 L=R R=L"
+        );
+    }
+
+    #[test]
+    fn custom_rows_use_preset_output() {
+        let routing = Routing {
+            preset: Some(MixPreset::UpmixStereoTo51),
+            matrix: vec![MatrixRow {
+                target: "RL".to_string(),
+                terms: vec![MixTerm {
+                    channel: "SL".to_string(),
+                    gain: 0.5,
+                }],
+            }],
+            ..Default::default()
+        };
+        assert!(routing.matrix_lines()[1].ends_with(" RL=0.354*L"));
+    }
+
+    #[test]
+    fn identity_routing_writes_nothing() {
+        assert!(Routing::default().matrix_lines().is_empty());
+        assert!(Routing::default().bass_management_lines().is_empty());
+    }
+
+    #[test]
+    fn bass_management_uses_lr4_crossover() {
+        let routing = Routing {
+            bass_management: Some(BassManagement {
+                mains: vec!["L".into(), "R".into(), "C".into()],
+                ..Default::default()
+            }),
+            muted: vec!["C".to_string()],
+            ..Default::default()
+        };
+        let lines = routing.bass_management_lines();
+        assert_eq!(lines[1], "Copy: LFE=LFE+L+R");
+        assert_eq!(lines[2], "Channel: LFE");
+        assert_eq!(lines[3], "Filter: ON LPQ Fc 80 Hz Q 0.707");
+        assert_eq!(lines[4], lines[3]);
+        assert_eq!(lines[5], "Channel: L R");
+        assert_eq!(lines[6], "Filter: ON HPQ Fc 80 Hz Q 0.707");
+        assert_eq!(lines[7], lines[6]);
+        assert_eq!(lines[8], "Channel: all");
+    }
+
+    #[test]
+    fn rejects_invalid_routing() {
+        let invalid = [
+            Routing {
+                swaps: vec![swap("L", "L")],
+                ..Default::default()
+            },
+            Routing {
+                swaps: vec![swap("L", "R"), swap("R", "C")],
+                ..Default::default()
+            },
+            Routing {
+                muted: vec!["L R".to_string()],
+                ..Default::default()
+            },
+            Routing {
+                bass_management: Some(BassManagement {
+                    crossover: 20.0,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            Routing {
+                bass_management: Some(BassManagement {
+                    mains: vec!["LFE".to_string()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        ];
+        for routing in invalid {
+            assert!(routing.validate().is_err(), "{:?}", routing);
+        }
+    }
+}
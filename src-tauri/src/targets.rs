@@ -0,0 +1,557 @@
+//! Target curves for headphone and speaker correction.
+//!
+//! A target is the frequency response a correction should achieve. The
+//! built-in curves are tabulated at the 31 ISO third-octave frequencies and
+//! normalized to 0 dB at 1 kHz; they are close approximations of the
+//! published curves, intended for generating corrections rather than as
+//! reference data. Custom targets can be given as points or read from a
+//! CSV/text file in any format [`crate::measurement`] understands.
+//!
+//! # Room Curves and Saved Targets
+//!
+//! [`TiltParams`] describe a straight tilt in dB per octave around 1 kHz
+//! plus an optional bass shelf. On their own they generate a room curve;
+//! combined with a base target they make a [`SavedTarget`], stored as
+//! `Documents/EQAPO GUI/targets/{name}.json` next to the profiles folder.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::dsp;
+use crate::fitting::rasterize_grid;
+use crate::graphic_eq::{gain_at, GraphicEqPreset};
+use crate::measurement::read_curve_file;
+use crate::profile::{ensure_dirs, sanitize_profile_name};
+use crate::types::{FilterType, GraphicEqPoint, ParametricBand};
+
+/// Number of log-spaced points generated curves are sampled at
+/// (about 1/12 octave from 20 Hz to 20 kHz).
+const GENERATED_POINTS: usize = 121;
+
+/// Q of the bass shelf in generated curves.
+const BASS_SHELF_Q: f32 = 0.707;
+
+/// Harman over-ear target (2018), GRAS 43AG-style coupler.
+const HARMAN_OVER_EAR_2018: [f32; 31] = [
+    6.3, 6.4, 6.5, 6.4, 6.2, 5.8, 5.0, 4.0, 2.8, 1.6, 0.8, 0.3, 0.0, -0.1, -0.2, -0.2, -0.1, 0.0,
+    0.6, 2.0, 4.5, 7.5, 9.0, 8.5, 6.5, 4.0, 3.0, 1.5, -1.0, -4.0, -8.0,
+];
+
+/// Harman in-ear target (2019), 711 coupler.
+const HARMAN_IN_EAR_2019: [f32; 31] = [
+    9.5, 9.6, 9.6, 9.4, 9.0, 8.3, 7.2, 5.8, 4.3, 2.9, 1.7, 0.9, 0.4, 0.1, 0.0, -0.1, -0.1, 0.0,
+    0.8, 2.6, 5.6, 8.4, 9.7, 9.0, 6.8, 4.2, 2.2, 0.5, -1.5, -4.5, -9.0,
+];
+
+/// Diffuse-field response of an ear simulator.
+const DIFFUSE_FIELD: [f32; 31] = [
+    0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.1, 0.2, 0.2, 0.1, 0.0, 0.9,
+    3.0, 6.0, 10.0, 12.0, 11.0, 9.0, 7.5, 8.0, 6.0, 3.0, 0.0, -3.0,
+];
+
+/// Free-field (frontal incidence) response of an ear simulator.
+const FREE_FIELD: [f32; 31] = [
+    0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.1, 0.2, 0.3, 0.2, 0.1, 0.0, 0.8,
+    2.5, 5.5, 10.0, 14.0, 13.0, 10.0, 6.5, 3.0, 2.0, 1.0, -2.0, -6.0,
+];
+
+/// Tilt and bass shelf applied to generate or adjust a target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TiltParams {
+    /// Slope in dB per octave, pivoting at 1 kHz (negative = darker).
+    pub tilt_per_octave: f32,
+    /// Gain of the low-shelf filter in dB.
+    pub bass_gain: f32,
+    /// Corner frequency of the low-shelf filter in Hz.
+    pub bass_frequency: f32,
+}
+
+impl Default for TiltParams {
+    fn default() -> Self {
+        Self {
+            tilt_per_octave: -1.0,
+            bass_gain: 0.0,
+            bass_frequency: 105.0,
+        }
+    }
+}
+
+impl TiltParams {
+    /// Checks that the parameters are usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tilt exceeds ±6 dB/octave, the bass gain
+    /// exceeds ±20 dB, or the bass frequency is outside 20–1000 Hz.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-6.0..=6.0).contains(&self.tilt_per_octave) {
+            return Err(format!(
+                "Tilt must be between -6 and 6 dB/octave, got {}",
+                self.tilt_per_octave
+            ));
+        }
+        if !(-20.0..=20.0).contains(&self.bass_gain) {
+            return Err(format!(
+                "Bass gain must be between -20 and 20 dB, got {}",
+                self.bass_gain
+            ));
+        }
+        if !(20.0..=1000.0).contains(&self.bass_frequency) {
+            return Err(format!(
+                "Bass frequency must be between 20 and 1000 Hz, got {}",
+                self.bass_frequency
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the adjustment in dB at `freq` Hz, 0 dB at 1 kHz.
+    pub fn gain_at(&self, freq: f64) -> f64 {
+        let tilt = self.tilt_per_octave as f64 * (freq / 1000.0).log2();
+        if self.bass_gain == 0.0 {
+            return tilt;
+        }
+        let shelf = ParametricBand {
+            filter_type: FilterType::LowShelf,
+            frequency: self.bass_frequency,
+            gain: self.bass_gain,
+            q_factor: BASS_SHELF_Q,
+            enabled: true,
+        };
+        let response = dsp::response_db(
+            &[shelf],
+            &[],
+            0.0,
+            &[freq, 1000.0],
+            dsp::DEFAULT_SAMPLE_RATE,
+        );
+        tilt + response[0] - response[1]
+    }
+
+    /// Adds the adjustment to a curve, sampled at the curve's own points and
+    /// at [`GENERATED_POINTS`] log-spaced frequencies.
+    fn apply(&self, base: &[GraphicEqPoint]) -> Vec<GraphicEqPoint> {
+        let mut frequencies: Vec<f64> = base.iter().map(|p| p.frequency as f64).collect();
+        frequencies.extend(dsp::log_spaced(
+            GENERATED_POINTS,
+            dsp::FREQ_MIN,
+            dsp::FREQ_MAX,
+        ));
+        frequencies.sort_by(f64::total_cmp);
+        frequencies.dedup_by(|a, b| (*a / *b - 1.0).abs() < 1e-4);
+
+        frequencies
+            .into_iter()
+            .map(|f| GraphicEqPoint {
+                frequency: f as f32,
+                gain: (gain_at(base, f) + self.gain_at(f)) as f32,
+            })
+            .collect()
+    }
+}
+
+/// A user-created target: a base target with a tilt/bass-shelf adjustment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedTarget {
+    /// Target name (used as file name).
+    pub name: String,
+    /// Target the adjustment is applied to.
+    pub base: Target,
+    /// Tilt and bass shelf added to the base.
+    pub adjustment: TiltParams,
+}
+
+/// A target as listed for selection.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TargetInfo {
+    /// Display name.
+    pub name: String,
+    /// Whether the target ships with the application.
+    pub builtin: bool,
+    /// Value to pass to commands taking a [`Target`].
+    pub target: Target,
+}
+
+/// A target curve to correct towards.
+///
+/// Serialized with a `type` tag, e.g. `{"type": "harman_over_ear_2018"}` or
+/// `{"type": "custom_file", "path": "C:\\targets\\mine.csv"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Target {
+    /// 0 dB at all frequencies.
+    Flat,
+    /// Harman over-ear target (2018).
+    #[serde(rename = "harman_over_ear_2018")]
+    HarmanOverEar2018,
+    /// Harman in-ear target (2019).
+    #[serde(rename = "harman_in_ear_2019")]
+    HarmanInEar2019,
+    /// Diffuse-field ear response.
+    DiffuseField,
+    /// Free-field (frontal) ear response.
+    FreeField,
+    /// Room curve generated from a tilt and bass shelf.
+    RoomCurve(TiltParams),
+    /// A target saved in the app directory.
+    Saved { name: String },
+    /// A curve read from a CSV or text file.
+    CustomFile { path: String },
+    /// A curve given directly as points.
+    Custom { points: Vec<GraphicEqPoint> },
+}
+
+/// Pairs tabulated gains with the ISO third-octave frequencies.
+fn third_octave_curve(gains: &[f32; 31]) -> Vec<GraphicEqPoint> {
+    GraphicEqPreset::Iso31
+        .frequencies()
+        .iter()
+        .zip(gains)
+        .map(|(&frequency, &gain)| GraphicEqPoint { frequency, gain })
+        .collect()
+}
+
+impl Target {
+    /// Returns the built-in targets with their display names.
+    pub fn builtins() -> Vec<(&'static str, Target)> {
+        vec![
+            ("Flat", Target::Flat),
+            ("Harman Over-Ear 2018", Target::HarmanOverEar2018),
+            ("Harman In-Ear 2019", Target::HarmanInEar2019),
+            ("Diffuse Field", Target::DiffuseField),
+            ("Free Field", Target::FreeField),
+            ("Room Curve", Target::RoomCurve(TiltParams::default())),
+        ]
+    }
+
+    /// Returns the target curve as points sorted by frequency.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a custom file or saved target cannot be read or
+    /// parsed, custom points are empty, or tilt parameters are invalid.
+    pub fn points(&self) -> Result<Vec<GraphicEqPoint>, String> {
+        match self {
+            Target::Flat => Ok(vec![GraphicEqPoint {
+                frequency: 1000.0,
+                gain: 0.0,
+            }]),
+            Target::HarmanOverEar2018 => Ok(third_octave_curve(&HARMAN_OVER_EAR_2018)),
+            Target::HarmanInEar2019 => Ok(third_octave_curve(&HARMAN_IN_EAR_2019)),
+            Target::DiffuseField => Ok(third_octave_curve(&DIFFUSE_FIELD)),
+            Target::FreeField => Ok(third_octave_curve(&FREE_FIELD)),
+            Target::RoomCurve(params) => {
+                params.validate()?;
+                Ok(params.apply(&[]))
+            }
+            Target::Saved { name } => load_saved_target(name)?.points(),
+            Target::CustomFile { path } => read_curve_file(Path::new(path)),
+            Target::Custom { points } if points.is_empty() => {
+                Err("Custom target has no points".to_string())
+            }
+            Target::Custom { points } => {
+                let mut points = points.clone();
+                points.sort_by(|a, b| a.frequency.total_cmp(&b.frequency));
+                Ok(points)
+            }
+        }
+    }
+}
+
+impl SavedTarget {
+    /// Returns the adjusted curve.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the base is itself a saved target, the base
+    /// cannot be loaded, or the adjustment is invalid.
+    pub fn points(&self) -> Result<Vec<GraphicEqPoint>, String> {
+        if matches!(self.base, Target::Saved { .. }) {
+            return Err("A saved target cannot be based on another saved target".to_string());
+        }
+        self.adjustment.validate()?;
+        Ok(self.adjustment.apply(&self.base.points()?))
+    }
+}
+
+// =============================================================================
+// Saved Target Storage
+// =============================================================================
+
+/// Returns the path of a saved target, creating the targets folder.
+fn saved_target_path(name: &str) -> Result<PathBuf, String> {
+    let dir = ensure_dirs()?.join("targets");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create targets dir: {}", e))?;
+    Ok(dir.join(format!("{}.json", name)))
+}
+
+/// Loads a saved target by name.
+///
+/// # Errors
+///
+/// Returns an error if the file doesn't exist or cannot be parsed.
+pub fn load_saved_target(name: &str) -> Result<SavedTarget, String> {
+    let content = fs::read_to_string(saved_target_path(name)?)
+        .map_err(|e| format!("Failed to read target '{}': {}", name, e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse target '{}': {}", name, e))
+}
+
+/// Lists the names of saved targets, sorted alphabetically.
+///
+/// # Errors
+///
+/// Returns an error if the targets folder cannot be read.
+pub fn list_saved_targets() -> Result<Vec<String>, String> {
+    let dir = saved_target_path("_")?
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let mut names: Vec<String> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read targets dir: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Lists the built-in targets followed by the saved ones.
+///
+/// # Errors
+///
+/// Returns an error if the targets folder cannot be read.
+#[tauri::command]
+pub fn list_targets() -> Result<Vec<TargetInfo>, String> {
+    let builtins = Target::builtins()
+        .into_iter()
+        .map(|(name, target)| TargetInfo {
+            name: name.to_string(),
+            builtin: true,
+            target,
+        });
+    let saved = list_saved_targets()?.into_iter().map(|name| TargetInfo {
+        name: name.clone(),
+        builtin: false,
+        target: Target::Saved { name },
+    });
+    Ok(builtins.chain(saved).collect())
+}
+
+/// Samples a target on a frequency grid.
+///
+/// # Arguments
+///
+/// * `target` - Target to sample
+/// * `preset` - ISO layout to sample at; takes precedence over `points`
+/// * `points` - Number of log-spaced points (default
+///   [`crate::fitting::DEFAULT_GRAPHIC_POINTS`])
+///
+/// # Errors
+///
+/// Returns an error if the target cannot be loaded or `points` is out of
+/// range.
+#[tauri::command]
+pub fn get_target_curve(
+    target: Target,
+    preset: Option<GraphicEqPreset>,
+    points: Option<usize>,
+) -> Result<Vec<GraphicEqPoint>, String> {
+    let curve = target.points()?;
+    Ok(rasterize_grid(preset, points)?
+        .into_iter()
+        .map(|frequency| GraphicEqPoint {
+            frequency,
+            gain: (gain_at(&curve, frequency as f64) * 100.0).round() as f32 / 100.0,
+        })
+        .collect())
+}
+
+/// Creates or replaces a saved target.
+///
+/// # Arguments
+///
+/// * `name` - Target name; must be a valid file name
+/// * `base` - Target to adjust (default [`Target::Flat`])
+/// * `adjustment` - Tilt and bass shelf to apply
+///
+/// # Errors
+///
+/// Returns an error if the name is invalid, the target cannot be computed,
+/// or the file cannot be written.
+#[tauri::command]
+pub fn save_target(
+    name: String,
+    base: Option<Target>,
+    adjustment: TiltParams,
+) -> Result<SavedTarget, String> {
+    if name.trim().is_empty() || sanitize_profile_name(&name) != name {
+        return Err(format!("Invalid target name '{}'", name));
+    }
+    let target = SavedTarget {
+        name,
+        base: base.unwrap_or(Target::Flat),
+        adjustment,
+    };
+    target.points()?;
+
+    let json = serde_json::to_string_pretty(&target)
+        .map_err(|e| format!("Failed to serialize target: {}", e))?;
+    fs::write(saved_target_path(&target.name)?, json)
+        .map_err(|e| format!("Failed to write target: {}", e))?;
+    Ok(target)
+}
+
+/// Deletes a saved target.
+///
+/// # Errors
+///
+/// Returns an error if the file doesn't exist or cannot be deleted.
+#[tauri::command]
+pub fn delete_target(name: String) -> Result<(), String> {
+    fs::remove_file(saved_target_path(&name)?)
+        .map_err(|e| format!("Failed to delete target: {}", e))
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphic_eq::gain_at;
+
+    #[test]
+    fn builtin_targets_are_normalized_at_1khz() {
+        for target in [
+            Target::Flat,
+            Target::HarmanOverEar2018,
+            Target::HarmanInEar2019,
+            Target::DiffuseField,
+            Target::FreeField,
+            Target::RoomCurve(TiltParams::default()),
+        ] {
+            let points = target.points().unwrap();
+            assert!(gain_at(&points, 1000.0).abs() < 1e-3, "{:?}", target);
+        }
+    }
+
+    #[test]
+    fn target_serializes_with_type_tag() {
+        let json = serde_json::to_value(Target::HarmanInEar2019).unwrap();
+        assert_eq!(json["type"], "harman_in_ear_2019");
+
+        let target: Target =
+            serde_json::from_str(r#"{"type": "custom_file", "path": "t.csv"}"#).unwrap();
+        assert_eq!(
+            target,
+            Target::CustomFile {
+                path: "t.csv".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn custom_points_are_sorted() {
+        let target = Target::Custom {
+            points: vec![
+                GraphicEqPoint {
+                    frequency: 1000.0,
+                    gain: 1.0,
+                },
+                GraphicEqPoint {
+                    frequency: 100.0,
+                    gain: 2.0,
+                },
+            ],
+        };
+        let points = target.points().unwrap();
+        assert_eq!(points[0].frequency, 100.0);
+        assert!(Target::Custom { points: vec![] }.points().is_err());
+    }
+
+    #[test]
+    fn room_curve_tilts_per_octave() {
+        let params = TiltParams {
+            tilt_per_octave: -1.5,
+            ..Default::default()
+        };
+        let points = Target::RoomCurve(params).points().unwrap();
+        assert!((gain_at(&points, 2000.0) + 1.5).abs() < 0.01);
+        assert!((gain_at(&points, 250.0) - 3.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn bass_shelf_raises_low_end() {
+        let params = TiltParams {
+            tilt_per_octave: 0.0,
+            bass_gain: 6.0,
+            bass_frequency: 105.0,
+        };
+        assert!((params.gain_at(20.0) - 6.0).abs() < 0.3);
+        assert!(params.gain_at(1000.0).abs() < 1e-9);
+        assert!(params.gain_at(5000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn saved_target_adjusts_base() {
+        let target = SavedTarget {
+            name: "Warm Harman".to_string(),
+            base: Target::HarmanOverEar2018,
+            adjustment: TiltParams {
+                tilt_per_octave: 0.0,
+                bass_gain: 3.0,
+                bass_frequency: 105.0,
+            },
+        };
+        let base = Target::HarmanOverEar2018.points().unwrap();
+        let points = target.points().unwrap();
+        let diff = gain_at(&points, 20.0) - gain_at(&base, 20.0);
+        assert!((diff - 3.0).abs() < 0.3, "{}", diff);
+        assert!((gain_at(&points, 3150.0) - gain_at(&base, 3150.0)).abs() < 0.05);
+
+        let nested = SavedTarget {
+            base: Target::Saved {
+                name: "x".to_string(),
+            },
+            ..target
+        };
+        assert!(nested.points().is_err());
+    }
+
+    #[test]
+    fn tilt_params_are_validated() {
+        let params = TiltParams {
+            tilt_per_octave: 10.0,
+            ..Default::default()
+        };
+        assert!(Target::RoomCurve(params).points().is_err());
+        let params = TiltParams {
+            bass_frequency: 5.0,
+            ..Default::default()
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn room_curve_serializes_flat() {
+        let json = serde_json::to_value(Target::RoomCurve(TiltParams::default())).unwrap();
+        assert_eq!(json["type"], "room_curve");
+        assert_eq!(json["tilt_per_octave"], -1.0);
+        let target: Target = serde_json::from_str(r#"{"type": "room_curve"}"#).unwrap();
+        assert_eq!(target, Target::RoomCurve(TiltParams::default()));
+    }
+}
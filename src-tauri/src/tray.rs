@@ -1,22 +1,173 @@
 //! System tray handling for EQAPO GUI.
 //!
 //! This module manages the system tray icon, context menu, and profile switching
-//! from the tray. It allows users to quickly switch between EQ profiles without
-//! opening the main window.
+//! from the tray. It allows users to quickly switch between EQ profiles and
+//! adjust the EQ without opening the main window.
+//!
+//! # Menu Layout
+//!
+//! ```text
+//! ✓ EQ Enabled
+//!   Preamp: -3.0 dB      ▸  +1 dB / +0.5 dB / -0.5 dB / -1 dB / Reset to 0 dB
+//! ─────────────
+//!   ★ 1. Studio              (favorites, if any)
+//!   Recent               ▸   (recently applied profiles)
+//! ─────────────
+//!   Headphones           ▸   (one submenu per profile folder)
+//!   Tags                 ▸   (one submenu per tag)
+//! ✓ Flat                     (profiles without a folder)
+//! ─────────────
+//!   Show Window
+//!   Quit
+//! ```
+//!
+//! # Icon and Tooltip
+//!
+//! The icon shows the EQ state: the normal app icon while active, a faded
+//! grayscale icon while bypassed, and a red badge when the EQ is predicted to
+//! clip (peak gain above 0 dB) or the peak meter recently detected an over.
+//! The tooltip lists the active profile, preamp and predicted peak gain.
+//!
+//! # Menu Ids
+//!
+//! Menu item ids are namespaced (`profile:<name>`, `app:quit`, ...) so that a
+//! profile name can never collide with a built-in action; see [`parse_menu_id`].
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem},
+    image::Image,
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Emitter, Manager,
+    AppHandle, Listener, Manager, Wry,
 };
 
-use crate::profile::{apply_profile, list_profiles, load_profile, save_settings};
-use crate::types::AppState;
+use crate::actions::{dispatch, show_main_window, Action};
+use crate::dsp::profile_peak_gain_db;
+use crate::loudness;
+use crate::notifications::{report_error, ErrorSource};
+use crate::profile::list_profile_summaries;
+use crate::types::{AppSettings, AppState, ProfileSummary};
+use crate::validation::resolve_sample_rate;
+
+/// Preamp steps offered in the preamp submenu, in dB.
+const PREAMP_NUDGES: [f32; 4] = [1.0, 0.5, -0.5, -1.0];
+
+/// A command triggered by a tray menu item.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MenuCommand {
+    /// Run a shared action.
+    Action(Action),
+    /// Exit the application.
+    Quit,
+}
 
-/// Builds the tray menu with available profiles.
+/// Builds a namespaced menu id.
+fn menu_id(namespace: &str, value: impl std::fmt::Display) -> String {
+    format!("{}:{}", namespace, value)
+}
+
+/// Maps a menu item id back to the command it triggers.
+///
+/// Everything after the first `:` is the value, so profile names may
+/// themselves contain `:`.
+///
+/// # Returns
 ///
-/// Creates a context menu showing all available profiles with a checkmark
-/// next to the currently active one. Also includes "Show Window" and "Quit" options.
+/// `None` for unknown or non-interactive ids.
+pub fn parse_menu_id(id: &str) -> Option<MenuCommand> {
+    let (namespace, value) = id.split_once(':')?;
+
+    let action = match (namespace, value) {
+        ("profile", name) | ("recent", name) => Action::ApplyProfile {
+            name: name.to_string(),
+        },
+        ("favorite", slot) => Action::ApplyFavorite {
+            slot: slot.parse().ok()?,
+        },
+        ("eq", "toggle") => Action::ToggleEq,
+        ("preamp", "reset") => Action::SetPreamp { preamp: 0.0 },
+        ("preamp", delta) => Action::AdjustPreamp {
+            delta: delta.parse().ok()?,
+        },
+        ("app", "show") => Action::ShowWindow,
+        ("app", "quit") => return Some(MenuCommand::Quit),
+        _ => return None,
+    };
+
+    Some(MenuCommand::Action(action))
+}
+
+/// Profiles grouped for display.
+#[derive(Debug, Default, PartialEq)]
+struct ProfileGroups {
+    /// Profiles without a folder, shown at the top level.
+    ungrouped: Vec<String>,
+    /// Profiles by folder.
+    folders: BTreeMap<String, Vec<String>>,
+    /// Profiles by tag (a profile appears under each of its tags).
+    tags: BTreeMap<String, Vec<String>>,
+}
+
+/// Groups profiles by folder and tag, keeping the input order within groups.
+fn group_profiles(summaries: &[ProfileSummary]) -> ProfileGroups {
+    let mut groups = ProfileGroups::default();
+
+    for summary in summaries {
+        match &summary.metadata.folder {
+            Some(folder) => groups
+                .folders
+                .entry(folder.clone())
+                .or_default()
+                .push(summary.name.clone()),
+            None => groups.ungrouped.push(summary.name.clone()),
+        }
+        for tag in &summary.metadata.tags {
+            groups
+                .tags
+                .entry(tag.clone())
+                .or_default()
+                .push(summary.name.clone());
+        }
+    }
+
+    groups
+}
+
+/// Creates a check item for a profile, checked if it is the current one.
+fn profile_item(
+    app: &AppHandle,
+    namespace: &str,
+    name: &str,
+    settings: &AppSettings,
+) -> Result<CheckMenuItem<Wry>, tauri::Error> {
+    let checked = settings.current_profile.as_deref() == Some(name);
+    CheckMenuItem::with_id(
+        app,
+        menu_id(namespace, name),
+        name,
+        true,
+        checked,
+        None::<&str>,
+    )
+}
+
+/// Creates a submenu listing the given profiles.
+fn profile_submenu(
+    app: &AppHandle,
+    title: &str,
+    names: &[String],
+    settings: &AppSettings,
+) -> Result<Submenu<Wry>, tauri::Error> {
+    let submenu = Submenu::new(app, title, true)?;
+    for name in names {
+        submenu.append(&profile_item(app, "profile", name, settings)?)?;
+    }
+    Ok(submenu)
+}
+
+/// Builds the tray menu.
 ///
 /// # Arguments
 ///
@@ -29,49 +180,119 @@ use crate::types::AppState;
 /// # Errors
 ///
 /// Returns an error if menu items cannot be created.
-fn build_tray_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error> {
-    let profiles = list_profiles().unwrap_or_default();
-    let state = app.state::<AppState>();
-    let current = {
-        let settings = state.settings.lock();
-        settings.current_profile.clone()
-    };
+fn build_tray_menu(app: &AppHandle) -> Result<Menu<Wry>, tauri::Error> {
+    let summaries = list_profile_summaries().unwrap_or_default();
+    let settings = app.state::<AppState>().settings.lock().clone();
+    let menu = Menu::new(app)?;
 
-    let mut items: Vec<MenuItem<tauri::Wry>> = Vec::new();
+    // EQ state and preamp
+    let eq_item = CheckMenuItem::with_id(
+        app,
+        menu_id("eq", "toggle"),
+        "EQ Enabled",
+        true,
+        settings.eq_enabled,
+        None::<&str>,
+    )?;
+    menu.append(&eq_item)?;
 
-    // Add profile items with checkmark for current
-    for profile in profiles {
-        let label = if Some(&profile) == current.as_ref() {
-            format!("✓ {}", profile)
-        } else {
-            format!("   {}", profile)
-        };
+    let preamp_menu = Submenu::new(app, format!("Preamp: {:+.1} dB", settings.preamp), true)?;
+    for delta in PREAMP_NUDGES {
+        let item = MenuItem::with_id(
+            app,
+            menu_id("preamp", delta),
+            format!("{:+} dB", delta),
+            true,
+            None::<&str>,
+        )?;
+        preamp_menu.append(&item)?;
+    }
+    preamp_menu.append(&PredefinedMenuItem::separator(app)?)?;
+    preamp_menu.append(&MenuItem::with_id(
+        app,
+        menu_id("preamp", "reset"),
+        "Reset to 0 dB",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&preamp_menu)?;
 
-        let item = MenuItem::with_id(app, &profile, &label, true, None::<&str>)?;
-        items.push(item);
+    // Favorites and recent profiles
+    let exists = |name: &str| summaries.iter().any(|s| s.name == name);
+    let favorites: Vec<(usize, &String)> = settings
+        .favorites
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| exists(name))
+        .map(|(i, name)| (i + 1, name))
+        .collect();
+    let recent: Vec<&String> = settings
+        .recent_profiles
+        .iter()
+        .filter(|name| exists(name))
+        .collect();
+
+    if !favorites.is_empty() || !recent.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    for (slot, name) in favorites {
+        let item = MenuItem::with_id(
+            app,
+            menu_id("favorite", slot),
+            format!("★ {}. {}", slot, name),
+            true,
+            None::<&str>,
+        )?;
+        menu.append(&item)?;
+    }
+    if !recent.is_empty() {
+        let recent_menu = Submenu::new(app, "Recent", true)?;
+        for name in recent {
+            recent_menu.append(&profile_item(app, "recent", name, &settings)?)?;
+        }
+        menu.append(&recent_menu)?;
     }
 
-    // Build menu with profile items
-    let menu = if items.is_empty() {
+    // Profiles, grouped by folder and tag
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    if summaries.is_empty() {
         let no_profiles =
-            MenuItem::with_id(app, "no_profiles", "(No profiles)", false, None::<&str>)?;
-        Menu::with_items(app, &[&no_profiles])?
+            MenuItem::with_id(app, "none:profiles", "(No profiles)", false, None::<&str>)?;
+        menu.append(&no_profiles)?;
     } else {
-        let item_refs: Vec<&dyn tauri::menu::IsMenuItem<tauri::Wry>> =
-            items.iter().map(|i| i as _).collect();
-        Menu::with_items(app, &item_refs)?
-    };
+        let groups = group_profiles(&summaries);
 
-    // Add separator
-    let separator = PredefinedMenuItem::separator(app)?;
-    menu.append(&separator)?;
+        for (folder, names) in &groups.folders {
+            menu.append(&profile_submenu(app, folder, names, &settings)?)?;
+        }
+        if !groups.tags.is_empty() {
+            let tags_menu = Submenu::new(app, "Tags", true)?;
+            for (tag, names) in &groups.tags {
+                tags_menu.append(&profile_submenu(app, tag, names, &settings)?)?;
+            }
+            menu.append(&tags_menu)?;
+        }
 
-    // Add Show Window option
-    let show_item = MenuItem::with_id(app, "show_window", "Show Window", true, None::<&str>)?;
-    menu.append(&show_item)?;
+        let items = groups
+            .ungrouped
+            .iter()
+            .map(|name| profile_item(app, "profile", name, &settings))
+            .collect::<Result<Vec<_>, _>>()?;
+        let item_refs: Vec<&dyn IsMenuItem<Wry>> = items.iter().map(|i| i as _).collect();
+        menu.append_items(&item_refs)?;
+    }
 
-    // Add Quit option
-    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+    // Window and app actions
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    let show_item = MenuItem::with_id(
+        app,
+        menu_id("app", "show"),
+        "Show Window",
+        true,
+        None::<&str>,
+    )?;
+    menu.append(&show_item)?;
+    let quit_item = MenuItem::with_id(app, menu_id("app", "quit"), "Quit", true, None::<&str>)?;
     menu.append(&quit_item)?;
 
     Ok(menu)
@@ -98,82 +319,242 @@ pub fn update_tray_menu(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
-/// Tauri command to refresh the tray menu.
-///
-/// Called from the frontend when profiles are added or deleted
-/// to keep the tray menu in sync.
-///
-/// # Arguments
-///
-/// * `app` - The Tauri app handle
-///
-/// # Errors
-///
-/// Returns an error if the menu cannot be updated.
-#[tauri::command]
-pub fn refresh_tray_menu(app: AppHandle) -> Result<(), String> {
-    update_tray_menu(&app)
+// =============================================================================
+// Icon and Tooltip
+// =============================================================================
+
+/// How long a clipping warning from the peak meter stays visible.
+const CLIP_HOLD: Duration = Duration::from_secs(3);
+
+/// Longest tooltip Windows displays, in characters.
+const MAX_TOOLTIP_CHARS: usize = 127;
+
+/// Which icon variant the tray shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayIconState {
+    /// EQ is active and not clipping.
+    Active,
+    /// EQ is bypassed.
+    Bypassed,
+    /// EQ is active and clipping (predicted or measured).
+    Clipping,
 }
 
-/// Applies a profile by name when selected from the tray menu.
+/// The part of the settings shown by the tray indicator.
 ///
-/// Loads the profile, applies it to EqualizerAPO, updates the application
-/// state, persists settings, and notifies the frontend of the change.
+/// Cached in [`TrayStatus`] when the settings change, so peak meter readings
+/// don't need the settings.
+#[derive(Debug, Clone, Default)]
+struct TraySummary {
+    current_profile: Option<String>,
+    preamp: f32,
+    eq_enabled: bool,
+    /// Predicted peak gain of the live config.
+    predicted_peak: f32,
+}
+
+impl TraySummary {
+    fn new(settings: &AppSettings, sample_rate: u32) -> Self {
+        Self {
+            current_profile: settings.current_profile.clone(),
+            preamp: settings.preamp,
+            eq_enabled: settings.eq_enabled,
+            predicted_peak: predicted_peak(settings, sample_rate),
+        }
+    }
+}
+
+/// Runtime tray status, kept in [`AppState`].
+#[derive(Debug, Default)]
+pub struct TrayStatus {
+    /// When the peak meter last reported a level at or above 0 dBFS.
+    last_over: Option<Instant>,
+    /// Settings shown by the indicator, updated when settings change.
+    summary: TraySummary,
+    /// Icon state and tooltip currently shown, to skip redundant updates.
+    applied: Option<(TrayIconState, String)>,
+}
+
+/// Chooses the icon variant.
 ///
-/// # Arguments
+/// Bypass takes priority: while bypassed, any clipping is not caused by the EQ.
+fn icon_state(eq_enabled: bool, peak_gain: f32, metered_over: bool) -> TrayIconState {
+    if !eq_enabled {
+        TrayIconState::Bypassed
+    } else if metered_over || peak_gain > 0.0 {
+        TrayIconState::Clipping
+    } else {
+        TrayIconState::Active
+    }
+}
+
+/// Builds the tooltip text, truncated to what Windows can display.
+fn tooltip_text(summary: &TraySummary, metered_over: bool) -> String {
+    let mut lines = vec![
+        "EQAPO GUI".to_string(),
+        format!(
+            "Profile: {}",
+            summary.current_profile.as_deref().unwrap_or("(none)")
+        ),
+    ];
+    if summary.eq_enabled {
+        lines.push(format!("Preamp: {:+.1} dB", summary.preamp));
+        lines.push(format!("Predicted peak: {:+.1} dB", summary.predicted_peak));
+    } else {
+        lines.push("EQ bypassed".to_string());
+    }
+    if metered_over {
+        lines.push("⚠ Clipping detected".to_string());
+    }
+
+    lines.join("\n").chars().take(MAX_TOOLTIP_CHARS).collect()
+}
+
+/// Applies an icon variant to RGBA pixel data in place.
+fn render_variant(rgba: &mut [u8], width: u32, height: u32, state: TrayIconState) {
+    match state {
+        TrayIconState::Active => {}
+        TrayIconState::Bypassed => {
+            for px in rgba.chunks_exact_mut(4) {
+                let luma = 0.299 * px[0] as f32 + 0.587 * px[1] as f32 + 0.114 * px[2] as f32;
+                px[..3].fill(luma.round() as u8);
+                px[3] /= 2;
+            }
+        }
+        TrayIconState::Clipping => {
+            // Red dot with a white outline in the bottom-right corner
+            let radius = width.min(height) as f32 / 4.0;
+            let cx = width as f32 - radius - 1.0;
+            let cy = height as f32 - radius - 1.0;
+
+            for (i, px) in rgba.chunks_exact_mut(4).enumerate() {
+                let x = (i as u32 % width) as f32 + 0.5;
+                let y = (i as u32 / width) as f32 + 0.5;
+                let distance = ((x - cx).powi(2) + (y - cy).powi(2)).sqrt();
+                if distance <= radius {
+                    px.copy_from_slice(&[220, 38, 38, 255]);
+                } else if distance <= radius + 1.0 {
+                    px.copy_from_slice(&[255, 255, 255, 255]);
+                }
+            }
+        }
+    }
+}
+
+/// Renders an icon variant from the base app icon.
+fn render_icon(base: &Image<'_>, state: TrayIconState) -> Image<'static> {
+    let mut rgba = base.rgba().to_vec();
+    render_variant(&mut rgba, base.width(), base.height(), state);
+    Image::new_owned(rgba, base.width(), base.height())
+}
+
+/// Predicts the peak gain of the live config, including loudness
+/// compensation and the mid/side and crossfeed stages.
+fn predicted_peak(settings: &AppSettings, sample_rate: u32) -> f32 {
+    let mut profile = settings.live_profile();
+    // Out-of-range loudness settings can't be applied either, so the config
+    // is predicted without compensation
+    let _ = loudness::apply_compensation(&mut profile, &settings.loudness);
+    profile_peak_gain_db(&profile, sample_rate.into())
+}
+
+/// Recomputes the predicted peak gain and updates the tray icon and tooltip.
 ///
-/// * `app` - The Tauri app handle
-/// * `name` - The name of the profile to apply
+/// Call this whenever the settings change; peak meter readings only refresh
+/// the clipping indicator and reuse the cached prediction.
 ///
 /// # Errors
 ///
-/// Returns an error if the profile cannot be loaded or applied.
-fn apply_profile_by_name(app: &AppHandle, name: &str) -> Result<(), String> {
-    let profile = load_profile(name.to_string())?;
+/// Returns an error if the icon or tooltip cannot be set.
+pub fn update_tray_status(app: &AppHandle) -> Result<(), String> {
     let state = app.state::<AppState>();
+    // Resolved before locking the settings, which it locks itself
+    let sample_rate = resolve_sample_rate(&state);
+    let settings = state.settings.lock().clone();
+    let summary = TraySummary::new(&settings, sample_rate);
+    state.tray_status.lock().summary = summary;
+
+    refresh_tray_indicator(app)
+}
 
-    // Get eq_enabled from settings
-    let eq_enabled = {
-        let settings = state.settings.lock();
-        settings.eq_enabled
+/// Updates the tray icon and tooltip from the cached peak prediction and
+/// recent peak meter readings.
+///
+/// Does nothing if neither the icon variant nor the tooltip changed.
+fn refresh_tray_indicator(app: &AppHandle) -> Result<(), String> {
+    let Some(tray) = app.tray_by_id("main_tray") else {
+        return Ok(());
     };
 
-    // Apply the profile
-    let preamp = profile.preamp;
-    apply_profile(profile.bands.clone(), preamp, None, Some(eq_enabled))?;
+    let state = app.state::<AppState>();
+    // Only decide under the lock: off the main thread, setting the icon and
+    // tooltip blocks until the main thread runs it, and the main thread may
+    // be waiting for this lock in `update_tray_status`
+    let desired = {
+        let status = state.tray_status.lock();
+        let summary = &status.summary;
+        let metered_over = status.last_over.is_some_and(|t| t.elapsed() < CLIP_HOLD);
+        let desired = (
+            icon_state(summary.eq_enabled, summary.predicted_peak, metered_over),
+            tooltip_text(summary, metered_over),
+        );
+        if status.applied.as_ref() == Some(&desired) {
+            return Ok(());
+        }
+        desired
+    };
 
-    // Update state and settings
-    {
-        let mut settings = state.settings.lock();
-        settings.current_profile = Some(name.to_string());
-        settings.bands = profile.bands;
-        settings.preamp = profile.preamp;
-        let _ = save_settings(&settings);
+    if let Some(base) = app.default_window_icon() {
+        tray.set_icon(Some(render_icon(base, desired.0)))
+            .map_err(|e| format!("Failed to set tray icon: {}", e))?;
     }
+    tray.set_tooltip(Some(&desired.1))
+        .map_err(|e| format!("Failed to set tray tooltip: {}", e))?;
+    state.tray_status.lock().applied = Some(desired);
 
-    // Emit event to frontend so it can sync its state
-    let _ = app.emit("profile-changed-from-tray", name.to_string());
+    Ok(())
+}
 
-    // Update tray menu to show new selection
-    let _ = update_tray_menu(app);
+/// Records a peak meter reading and refreshes the clipping indicator.
+fn handle_peak_meter_update(app: &AppHandle, payload: &str) {
+    let peak_db = serde_json::from_str::<serde_json::Value>(payload)
+        .ok()
+        .and_then(|v| v["peak_db"].as_f64());
+    if peak_db.is_some_and(|db| db >= 0.0) {
+        app.state::<AppState>().tray_status.lock().last_over = Some(Instant::now());
+    }
+    let _ = refresh_tray_indicator(app);
+}
 
-    Ok(())
+/// Tauri command to refresh the tray menu.
+///
+/// Called from the frontend when profiles are added or deleted
+/// to keep the tray menu in sync.
+///
+/// # Arguments
+///
+/// * `app` - The Tauri app handle
+///
+/// # Errors
+///
+/// Returns an error if the menu cannot be updated.
+#[tauri::command]
+pub fn refresh_tray_menu(app: AppHandle) -> Result<(), String> {
+    update_tray_menu(&app)
 }
 
 /// Sets up the system tray icon and menu.
 ///
 /// Creates the tray icon with:
-/// - The application icon
-/// - A context menu with profiles and actions
+/// - The application icon, replaced by a state variant once set up
+/// - A context menu with EQ controls, profiles and actions
 /// - Click handlers for menu items and tray icon
 ///
 /// # Behavior
 ///
 /// - Left-click on tray icon: Shows and focuses the main window
 /// - Right-click on tray icon: Opens the context menu
-/// - Menu profile item: Applies that profile
-/// - "Show Window": Shows and focuses the main window
-/// - "Quit": Exits the application
+/// - Menu items: Run the command from [`parse_menu_id`]
 ///
 /// # Arguments
 ///
@@ -196,24 +577,18 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), tauri::Error> {
         .menu(&menu)
         .show_menu_on_left_click(false)
         .tooltip("EQAPO GUI")
-        .on_menu_event(move |app, event| {
-            let id = event.id.as_ref();
-            match id {
-                "show_window" => {
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.unminimize();
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                    }
-                }
-                "quit" => {
-                    app.exit(0);
-                }
-                "no_profiles" => {}
-                profile_name => {
-                    let _ = apply_profile_by_name(app, profile_name);
+        .on_menu_event(move |app, event| match parse_menu_id(event.id.as_ref()) {
+            Some(MenuCommand::Quit) => app.exit(0),
+            Some(MenuCommand::Action(action)) => {
+                let description = action.describe();
+                if let Err(e) = dispatch(app, action) {
+                    report_error(app, ErrorSource::Tray, &description, &e);
+                    // Check items toggle themselves when clicked; rebuild so
+                    // they match the actual state again
+                    let _ = update_tray_menu(app);
                 }
             }
+            None => {}
         })
         .on_tray_icon_event(|tray, event| {
             // Left-click shows the window
@@ -223,15 +598,154 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), tauri::Error> {
                 ..
             } = event
             {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.unminimize();
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+                show_main_window(tray.app_handle());
             }
         })
         .build(app)?;
 
+    let handle = app.clone();
+    app.listen_any("peak_meter_update", move |event| {
+        handle_peak_meter_update(&handle, event.payload());
+    });
+    let _ = update_tray_status(app);
+
     Ok(())
 }
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ProfileMetadata;
+
+    fn summary(name: &str, folder: Option<&str>, tags: &[&str]) -> ProfileSummary {
+        ProfileSummary {
+            name: name.to_string(),
+            metadata: ProfileMetadata {
+                folder: folder.map(String::from),
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                source: None,
+            },
+        }
+    }
+
+    #[test]
+    fn profile_named_like_builtin_does_not_collide() {
+        assert_eq!(
+            parse_menu_id(&menu_id("profile", "quit")),
+            Some(MenuCommand::Action(Action::ApplyProfile {
+                name: "quit".to_string()
+            }))
+        );
+        assert_eq!(parse_menu_id("app:quit"), Some(MenuCommand::Quit));
+        assert_eq!(parse_menu_id("quit"), None);
+    }
+
+    #[test]
+    fn profile_name_may_contain_separator() {
+        assert_eq!(
+            parse_menu_id("profile:HD600: warm"),
+            Some(MenuCommand::Action(Action::ApplyProfile {
+                name: "HD600: warm".to_string()
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_menu_id_controls() {
+        assert_eq!(
+            parse_menu_id("eq:toggle"),
+            Some(MenuCommand::Action(Action::ToggleEq))
+        );
+        assert_eq!(
+            parse_menu_id(&menu_id("preamp", -0.5)),
+            Some(MenuCommand::Action(Action::AdjustPreamp { delta: -0.5 }))
+        );
+        assert_eq!(
+            parse_menu_id("preamp:reset"),
+            Some(MenuCommand::Action(Action::SetPreamp { preamp: 0.0 }))
+        );
+        assert_eq!(
+            parse_menu_id("favorite:3"),
+            Some(MenuCommand::Action(Action::ApplyFavorite { slot: 3 }))
+        );
+        assert_eq!(parse_menu_id("favorite:x"), None);
+        assert_eq!(parse_menu_id("none:profiles"), None);
+    }
+
+    #[test]
+    fn icon_state_priorities() {
+        assert_eq!(icon_state(false, 3.0, true), TrayIconState::Bypassed);
+        assert_eq!(icon_state(true, 0.5, false), TrayIconState::Clipping);
+        assert_eq!(icon_state(true, -1.0, true), TrayIconState::Clipping);
+        assert_eq!(icon_state(true, 0.0, false), TrayIconState::Active);
+    }
+
+    #[test]
+    fn tooltip_lists_profile_preamp_and_peak() {
+        let summary = TraySummary {
+            current_profile: Some("Studio".to_string()),
+            preamp: -3.0,
+            eq_enabled: true,
+            predicted_peak: 1.24,
+        };
+        let tooltip = tooltip_text(&summary, false);
+        assert_eq!(
+            tooltip,
+            "EQAPO GUI\nProfile: Studio\nPreamp: -3.0 dB\nPredicted peak: +1.2 dB"
+        );
+    }
+
+    #[test]
+    fn tooltip_bypassed_and_truncated() {
+        let summary = TraySummary {
+            current_profile: Some("x".repeat(200)),
+            ..Default::default()
+        };
+        let tooltip = tooltip_text(&summary, false);
+        assert_eq!(tooltip.chars().count(), MAX_TOOLTIP_CHARS);
+
+        let summary = TraySummary::default();
+        assert!(tooltip_text(&summary, true).ends_with("EQ bypassed\n⚠ Clipping detected"));
+    }
+
+    #[test]
+    fn render_bypassed_is_gray_and_faded() {
+        let mut rgba = vec![255, 0, 0, 255];
+        render_variant(&mut rgba, 1, 1, TrayIconState::Bypassed);
+        assert_eq!(rgba, vec![76, 76, 76, 127]);
+    }
+
+    #[test]
+    fn render_clipping_adds_badge_in_corner() {
+        let (width, height) = (16u32, 16u32);
+        let mut rgba = vec![0u8; (width * height * 4) as usize];
+        render_variant(&mut rgba, width, height, TrayIconState::Clipping);
+
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * width + x) * 4) as usize;
+            rgba[i..i + 4].to_vec()
+        };
+        assert_eq!(pixel(11, 11), vec![220, 38, 38, 255]);
+        assert_eq!(pixel(0, 0), vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn group_profiles_by_folder_and_tag() {
+        let groups = group_profiles(&[
+            summary("Flat", None, &[]),
+            summary("HD600", Some("Headphones"), &["Open"]),
+            summary("HD800", Some("Headphones"), &["Open", "Bright"]),
+            summary("Room", None, &["Speakers"]),
+        ]);
+
+        assert_eq!(groups.ungrouped, vec!["Flat", "Room"]);
+        assert_eq!(groups.folders["Headphones"], vec!["HD600", "HD800"]);
+        assert_eq!(groups.tags["Open"], vec!["HD600", "HD800"]);
+        assert_eq!(groups.tags["Speakers"], vec!["Room"]);
+        assert_eq!(groups.tags.len(), 3);
+    }
+}
@@ -37,11 +37,13 @@
 //!     name: "My Profile".to_string(),
 //!     preamp: -3.0,
 //!     bands: vec![band],
+//!     ..Default::default()
 //! };
 //! ```
 
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
 
 #[cfg(windows)]
 use std::sync::Arc;
@@ -50,6 +52,14 @@ use std::sync::Arc;
 use crate::audio_monitor::AudioMonitor;
 
 use crate::ab_test::ABSession;
+use crate::automation::AutomationServer;
+use crate::crossfeed::Crossfeed;
+use crate::mid_side::MidSide;
+use crate::notifications::ErrorLogEntry;
+use crate::osc::OscServer;
+use crate::routing::Routing;
+use crate::tray::TrayStatus;
+use crate::vst::VstPlugin;
 
 /// Filter types supported by EqualizerAPO.
 ///
@@ -73,7 +83,7 @@ use crate::ab_test::ABSession;
 /// - `Peaking` → `"peaking"`
 /// - `LowShelf` → `"lowshelf"`
 /// - `HighShelf` → `"highshelf"`
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum FilterType {
     /// Bell/parametric filter centered on the frequency.
@@ -234,9 +244,8 @@ impl ParametricBand {
     ///
     /// Where:
     /// - `{type}` is the filter code (`PK`, `LSC`, or `HSC`)
-    /// - `{freq}` is the frequency as an integer (truncated, not rounded)
-    /// - `{gain}` is the gain with one decimal place
-    /// - `{q}` is the Q factor with two decimal places
+    /// - `{freq}`, `{gain}` and `{q}` are written losslessly, with at least
+    ///   0, 1 and 2 decimal places (see [`ConfigPrecision`])
     ///
     /// # Example
     ///
@@ -245,34 +254,96 @@ impl ParametricBand {
     ///
     /// let band = ParametricBand {
     ///     filter_type: FilterType::Peaking,
-    ///     frequency: 1000.0,
-    ///     gain: 3.5,
+    ///     frequency: 105.5,
+    ///     gain: 3.25,
     ///     q_factor: 1.41,
     ///     enabled: true,
     /// };
     ///
     /// assert_eq!(
     ///     band.to_eapo_line(),
-    ///     "Filter: ON PK Fc 1000 Hz Gain 3.5 dB Q 1.41"
+    ///     "Filter: ON PK Fc 105.5 Hz Gain 3.25 dB Q 1.41"
     /// );
     /// ```
-    ///
-    /// # Note
-    ///
-    /// The frequency is truncated to an integer because EqualizerAPO
-    /// does not support fractional Hz values in its configuration files.
     #[must_use]
     pub fn to_eapo_line(&self) -> String {
+        self.to_eapo_line_with(&ConfigPrecision::default())
+    }
+
+    /// Formats the band as an EqualizerAPO filter line with the given
+    /// precision.
+    #[must_use]
+    pub fn to_eapo_line_with(&self, precision: &ConfigPrecision) -> String {
         format!(
-            "Filter: ON {} Fc {} Hz Gain {:.1} dB Q {:.2}",
+            "Filter: ON {} Fc {} Hz Gain {} dB Q {}",
             self.filter_type.to_eapo_code(),
-            self.frequency as i32,
-            self.gain,
-            self.q_factor
+            format_config_number(self.frequency, precision.frequency, 0),
+            format_config_number(self.gain, precision.gain, 1),
+            format_config_number(self.q_factor, precision.q_factor, 2)
         )
     }
 }
 
+/// Builds an enabled band, for unit tests across the crate.
+#[cfg(test)]
+pub(crate) fn test_band(
+    filter_type: FilterType,
+    frequency: f32,
+    gain: f32,
+    q_factor: f32,
+) -> ParametricBand {
+    ParametricBand {
+        filter_type,
+        frequency,
+        gain,
+        q_factor,
+        enabled: true,
+    }
+}
+
+/// Number of decimal places written for each value in the generated config.
+///
+/// `None` (the default) writes the shortest representation that reads back
+/// as the same `f32`, padded to a minimum number of decimals for
+/// readability, so imported values such as 105.5 Hz or 0.25 dB survive a
+/// write and re-read unchanged. `Some(n)` rounds to exactly `n` decimals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigPrecision {
+    /// Decimals for band frequencies (lossless minimum: 0).
+    #[serde(default)]
+    pub frequency: Option<u8>,
+
+    /// Decimals for band gains (lossless minimum: 1).
+    #[serde(default)]
+    pub gain: Option<u8>,
+
+    /// Decimals for Q factors (lossless minimum: 2).
+    #[serde(default)]
+    pub q_factor: Option<u8>,
+
+    /// Decimals for the preamp (lossless minimum: 1).
+    #[serde(default)]
+    pub preamp: Option<u8>,
+}
+
+/// Formats a config value with a fixed number of decimals, or losslessly
+/// with at least `min_decimals` decimals when `decimals` is `None`.
+#[must_use]
+pub fn format_config_number(value: f32, decimals: Option<u8>, min_decimals: usize) -> String {
+    if let Some(decimals) = decimals {
+        return format!("{:.*}", usize::from(decimals), value);
+    }
+
+    // `Display` for floats is the shortest string that parses back exactly
+    let shortest = value.to_string();
+    let shown = shortest.split_once('.').map_or(0, |(_, frac)| frac.len());
+    if shown < min_decimals {
+        format!("{:.*}", min_decimals, value)
+    } else {
+        shortest
+    }
+}
+
 /// A complete EQ profile containing a name, preamp setting, and collection of bands.
 ///
 /// Profiles represent saved EQ configurations that users can create, load, and switch
@@ -323,9 +394,10 @@ impl ParametricBand {
 ///             enabled: true,
 ///         },
 ///     ],
+///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EqProfile {
     /// Human-readable name identifying the profile.
     ///
@@ -346,6 +418,171 @@ pub struct EqProfile {
     /// Bands are applied in order, though for linear-phase EQ the order
     /// typically doesn't affect the final result.
     pub bands: Vec<ParametricBand>,
+
+    /// GraphicEQ curve as frequency/gain points, sorted by frequency.
+    ///
+    /// Empty for purely parametric profiles, and omitted from the JSON file
+    /// in that case.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub graphic_eq: Vec<GraphicEqPoint>,
+
+    /// Organization metadata (folder, tags, import source).
+    ///
+    /// Omitted from the JSON file when empty, so profiles without metadata
+    /// keep the original format.
+    #[serde(default, skip_serializing_if = "ProfileMetadata::is_empty")]
+    pub metadata: ProfileMetadata,
+
+    /// Convolution stage applied after the preamp, if any.
+    ///
+    /// Omitted from the JSON file when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub convolution: Option<ConvolutionStage>,
+
+    /// Headphone crossfeed applied after the preamp, if any.
+    ///
+    /// Omitted from the JSON file when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crossfeed: Option<Crossfeed>,
+
+    /// Mid/side bands and stereo width, applied first, if any.
+    ///
+    /// Omitted from the JSON file when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mid_side: Option<MidSide>,
+
+    /// Channel matrix and bass management for speaker setups, if any.
+    ///
+    /// Omitted from the JSON file when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub routing: Option<Routing>,
+
+    /// VST plugin hosted after the bands, if any.
+    ///
+    /// Omitted from the JSON file when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vst_plugin: Option<VstPlugin>,
+}
+
+/// A convolution stage referencing an impulse response WAV file.
+///
+/// The impulse is stored in `Documents/EQAPO GUI/impulses/` and copied next
+/// to the EqualizerAPO config when the profile is applied, since the audio
+/// service can only read files inside the config directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConvolutionStage {
+    /// File name of the impulse inside the `impulses/` directory.
+    pub file: String,
+}
+
+/// Organization metadata for a profile, used to group large libraries in
+/// the tray menu.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileMetadata {
+    /// Folder the profile is shown in, e.g. `"Headphones"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+
+    /// Free-form tags, e.g. `["IEM", "Bass"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Where the profile was imported from, if it was imported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ProfileSource>,
+}
+
+impl ProfileMetadata {
+    /// Returns `true` if no metadata is set.
+    pub fn is_empty(&self) -> bool {
+        self.folder.is_none() && self.tags.is_empty() && self.source.is_none()
+    }
+}
+
+/// The file a profile was imported from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSource {
+    /// Format identifier, e.g. `"autoeq-parametric"` or `"autoeq-graphic"`.
+    pub format: String,
+
+    /// Path of the imported file at the time of import.
+    pub path: String,
+}
+
+/// A single point of a GraphicEQ curve.
+///
+/// EqualizerAPO interpolates linearly (in dB over log frequency) between
+/// points, and holds the first and last gain beyond the ends of the curve.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GraphicEqPoint {
+    /// Frequency in Hz.
+    pub frequency: f32,
+
+    /// Gain in dB.
+    pub gain: f32,
+}
+
+/// A profile name together with its metadata, without the bands.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProfileSummary {
+    pub name: String,
+    pub metadata: ProfileMetadata,
+}
+
+/// Live EQ sent by the frontend to the `apply_profile` command.
+///
+/// Carries the same stages as an [`EqProfile`], without a name or metadata,
+/// plus options that only apply to this write. Stages are edited through
+/// their own commands and kept in the settings, so a stage left out here is
+/// taken from the current EQ.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ApplyRequest {
+    pub bands: Vec<ParametricBand>,
+    #[serde(default)]
+    pub preamp: f32,
+    pub graphic_eq: Option<Vec<GraphicEqPoint>>,
+    pub convolution: Option<ConvolutionStage>,
+    pub crossfeed: Option<Crossfeed>,
+    pub mid_side: Option<MidSide>,
+    pub routing: Option<Routing>,
+    pub vst_plugin: Option<VstPlugin>,
+
+    /// Config file to write; `None` uses `live_config.txt`.
+    pub config_path: Option<String>,
+
+    /// Whether the EQ is enabled; defaults to `true`.
+    pub eq_enabled: Option<bool>,
+
+    /// Whether to clamp invalid band values; defaults to the `auto_clamp`
+    /// setting.
+    pub clamp: Option<bool>,
+
+    /// Whether to append loudness compensation bands; defaults to the
+    /// `loudness.enabled` setting.
+    pub loudness: Option<bool>,
+}
+
+/// UI state sent by the frontend to the `update_settings` command.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SettingsUpdate {
+    /// Current EQ bands.
+    pub bands: Vec<ParametricBand>,
+
+    /// Current preamp value in dB.
+    pub preamp: f32,
+
+    /// Currently active profile name.
+    pub current_profile: Option<String>,
+
+    /// Custom config file path.
+    pub config_path: Option<String>,
+
+    /// Whether the EQ is enabled; unchanged if `None`.
+    pub eq_enabled: Option<bool>,
+
+    /// Current GraphicEQ curve; if `None`, the curve is kept, or taken from
+    /// the new profile when `current_profile` changes.
+    pub graphic_eq: Option<Vec<GraphicEqPoint>>,
 }
 
 /// Persistent application settings saved to `settings.json`.
@@ -362,7 +599,9 @@ pub struct EqProfile {
 ///
 /// The settings store the *current* EQ configuration, which may differ from any
 /// saved profile. When a user:
-/// - Loads a profile: bands and preamp are copied to settings
+/// - Loads a profile: bands, GraphicEQ curve, routing, mid/side,
+///   convolution, crossfeed and VST plugin stages and preamp are copied to
+///   settings
 /// - Modifies the EQ: settings are updated, but the profile file is unchanged
 /// - Saves a profile: current bands/preamp are written to the profile file
 ///
@@ -372,8 +611,21 @@ pub struct EqProfile {
 /// - `current_profile`: `None`
 /// - `config_path`: `None` (uses default `live_config.txt`)
 /// - `bands`: Single flat band at 1 kHz
+/// - `graphic_eq`: Empty
+/// - `convolution`: `None`
+/// - `crossfeed`: `None`
+/// - `mid_side`: `None`
+/// - `routing`: `None`
+/// - `vst_plugin`: `None`
 /// - `preamp`: `0.0` dB
 /// - `eq_enabled`: `true`
+/// - `automation`: Disabled, port 8765
+/// - `osc`: Disabled, port 9000, loopback only
+/// - `favorites`: Empty
+/// - `recent_profiles`: Empty
+/// - `hotkeys`: Disabled, default `Ctrl+Alt` bindings
+/// - `validation`: Device sample rate, no clamping
+/// - `config_precision`: Lossless
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     /// Name of the currently active profile, if any.
@@ -409,6 +661,34 @@ pub struct AppSettings {
     #[serde(default = "default_bands")]
     pub bands: Vec<ParametricBand>,
 
+    /// Current GraphicEQ curve; empty if the active profile has none.
+    ///
+    /// Written to the config together with the bands.
+    #[serde(default)]
+    pub graphic_eq: Vec<GraphicEqPoint>,
+
+    /// Current convolution stage; `None` if the active profile has none.
+    ///
+    /// Written to the config together with the bands.
+    #[serde(default)]
+    pub convolution: Option<ConvolutionStage>,
+
+    /// Current crossfeed stage; `None` if the active profile has none.
+    #[serde(default)]
+    pub crossfeed: Option<Crossfeed>,
+
+    /// Current mid/side stage; `None` if the active profile has none.
+    #[serde(default)]
+    pub mid_side: Option<MidSide>,
+
+    /// Current routing section; `None` if the active profile has none.
+    #[serde(default)]
+    pub routing: Option<Routing>,
+
+    /// Current VST plugin stage; `None` if the active profile has none.
+    #[serde(default)]
+    pub vst_plugin: Option<VstPlugin>,
+
     /// Current preamp value in decibels.
     ///
     /// Applied globally before all filters. Used to prevent clipping
@@ -423,6 +703,338 @@ pub struct AppSettings {
     /// A/B comparison between processed and unprocessed audio.
     #[serde(default = "default_eq_enabled")]
     pub eq_enabled: bool,
+
+    /// Local automation API configuration.
+    ///
+    /// See [`AutomationSettings`]. Disabled by default.
+    #[serde(default)]
+    pub automation: AutomationSettings,
+
+    /// OSC control surface configuration.
+    ///
+    /// See [`OscSettings`]. Disabled by default.
+    #[serde(default)]
+    pub osc: OscSettings,
+
+    /// Favorite profile names; slot `n` (1–9) is `favorites[n - 1]`.
+    ///
+    /// Used by the favorite hotkeys and quick menus.
+    #[serde(default)]
+    pub favorites: Vec<String>,
+
+    /// Recently applied profiles, most recent first.
+    ///
+    /// Limited to [`MAX_RECENT_PROFILES`] entries.
+    #[serde(default)]
+    pub recent_profiles: Vec<String>,
+
+    /// Global keyboard shortcut configuration.
+    ///
+    /// See [`HotkeySettings`]. Disabled by default.
+    #[serde(default)]
+    pub hotkeys: HotkeySettings,
+
+    /// Band validation options.
+    ///
+    /// See [`ValidationSettings`]. Uses the device sample rate and reports
+    /// issues without clamping by default.
+    #[serde(default)]
+    pub validation: ValidationSettings,
+
+    /// Decimal places written to the EqualizerAPO config.
+    ///
+    /// See [`ConfigPrecision`]. Lossless by default.
+    #[serde(default)]
+    pub config_precision: ConfigPrecision,
+
+    /// Volume-dependent loudness compensation.
+    ///
+    /// See [`LoudnessSettings`]. Disabled by default.
+    #[serde(default)]
+    pub loudness: LoudnessSettings,
+}
+
+/// Options for validating bands when profiles are saved or applied.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationSettings {
+    /// Sample rate to validate against, in Hz. `None` uses the default
+    /// output device's rate, or 48 kHz if it cannot be queried.
+    #[serde(default)]
+    pub sample_rate: Option<u32>,
+
+    /// Whether out-of-range values are clamped instead of rejected.
+    #[serde(default)]
+    pub auto_clamp: bool,
+}
+
+/// Equal-loudness compensation for listening below the reference level.
+///
+/// When enabled, bands that make up the difference between the ISO 226
+/// equal-loudness contours at the reference level and at the current level
+/// are appended to the live config. See [`crate::loudness`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoudnessSettings {
+    /// Whether compensation is added to the live config.
+    pub enabled: bool,
+
+    /// Listening level, in phon, at which content sounds balanced without
+    /// compensation (typically the mixing level).
+    pub reference_level: f32,
+
+    /// Current volume in dB relative to the reference level; negative when
+    /// listening quieter.
+    pub volume: f32,
+}
+
+impl Default for LoudnessSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reference_level: 83.0,
+            volume: 0.0,
+        }
+    }
+}
+
+/// Configuration for the opt-in local automation API.
+///
+/// The server only ever binds to `127.0.0.1` and every request must carry
+/// the token, either as an `Authorization: Bearer <token>` header or as a
+/// `?token=<token>` query parameter (for WebSocket clients that can't set
+/// headers).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationSettings {
+    /// Whether the server is started with the application.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// TCP port on `127.0.0.1` to listen on.
+    #[serde(default = "default_automation_port")]
+    pub port: u16,
+
+    /// Shared secret required by every request.
+    ///
+    /// Generated automatically the first time the server is enabled.
+    #[serde(default)]
+    pub token: String,
+}
+
+/// Default TCP port for the automation API.
+pub const DEFAULT_AUTOMATION_PORT: u16 = 8765;
+
+/// Default value provider for `AutomationSettings::port` during deserialization.
+#[inline]
+fn default_automation_port() -> u16 {
+    DEFAULT_AUTOMATION_PORT
+}
+
+impl Default for AutomationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_AUTOMATION_PORT,
+            token: String::new(),
+        }
+    }
+}
+
+/// Configuration for the optional OSC (Open Sound Control) listener.
+///
+/// OSC controllers such as TouchOSC send UDP messages to `port`. State
+/// feedback is sent back to each controller's IP address, either to the port
+/// it sent from or to `feedback_port` if set (most controllers listen on a
+/// different port than they send from).
+///
+/// The listener binds to `127.0.0.1` unless `allow_lan` is set. OSC has no
+/// authentication, so a LAN listener only accepts messages from loopback and
+/// from the addresses in `allowed_hosts`; everything else is dropped without
+/// a reply.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OscSettings {
+    /// Whether the listener is started with the application.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// UDP port to listen on.
+    #[serde(default = "default_osc_port")]
+    pub port: u16,
+
+    /// UDP port controllers listen on for feedback, if different from
+    /// their source port.
+    #[serde(default)]
+    pub feedback_port: Option<u16>,
+
+    /// Whether to listen on all interfaces so that tablets and other
+    /// devices on the network can connect.
+    #[serde(default)]
+    pub allow_lan: bool,
+
+    /// Addresses of LAN controllers allowed to send messages when
+    /// `allow_lan` is set.
+    #[serde(default)]
+    pub allowed_hosts: Vec<IpAddr>,
+}
+
+/// Default UDP port for the OSC listener.
+pub const DEFAULT_OSC_PORT: u16 = 9000;
+
+/// Default value provider for `OscSettings::port` during deserialization.
+#[inline]
+fn default_osc_port() -> u16 {
+    DEFAULT_OSC_PORT
+}
+
+impl Default for OscSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_OSC_PORT,
+            feedback_port: None,
+            allow_lan: false,
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
+/// Maximum number of favorite profile slots.
+pub const MAX_FAVORITES: usize = 9;
+
+/// Maximum number of entries in [`AppSettings::recent_profiles`].
+pub const MAX_RECENT_PROFILES: usize = 5;
+
+/// A command that can be bound to a global keyboard shortcut.
+///
+/// Serialized as a snake_case id such as `"toggle_eq"` or `"favorite_3"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum HotkeyCommand {
+    /// Apply the next profile in alphabetical order.
+    NextProfile,
+    /// Apply the previous profile in alphabetical order.
+    PreviousProfile,
+    /// Toggle EQ bypass.
+    ToggleEq,
+    /// Raise the preamp by one step.
+    PreampUp,
+    /// Lower the preamp by one step.
+    PreampDown,
+    /// Apply the favorite in the given slot (1–9).
+    Favorite(u8),
+}
+
+impl HotkeyCommand {
+    /// Returns every bindable command, in display order.
+    pub fn all() -> Vec<Self> {
+        let mut commands = vec![
+            Self::NextProfile,
+            Self::PreviousProfile,
+            Self::ToggleEq,
+            Self::PreampUp,
+            Self::PreampDown,
+        ];
+        commands.extend((1..=MAX_FAVORITES as u8).map(Self::Favorite));
+        commands
+    }
+
+    /// Returns the stable id used in settings and commands.
+    pub fn id(&self) -> String {
+        match self {
+            Self::NextProfile => "next_profile".to_string(),
+            Self::PreviousProfile => "previous_profile".to_string(),
+            Self::ToggleEq => "toggle_eq".to_string(),
+            Self::PreampUp => "preamp_up".to_string(),
+            Self::PreampDown => "preamp_down".to_string(),
+            Self::Favorite(slot) => format!("favorite_{}", slot),
+        }
+    }
+
+    /// Returns a human-readable label for the settings UI.
+    pub fn label(&self) -> String {
+        match self {
+            Self::NextProfile => "Next profile".to_string(),
+            Self::PreviousProfile => "Previous profile".to_string(),
+            Self::ToggleEq => "Toggle EQ bypass".to_string(),
+            Self::PreampUp => "Preamp up".to_string(),
+            Self::PreampDown => "Preamp down".to_string(),
+            Self::Favorite(slot) => format!("Favorite {}", slot),
+        }
+    }
+}
+
+impl TryFrom<String> for HotkeyCommand {
+    type Error = String;
+
+    fn try_from(id: String) -> Result<Self, Self::Error> {
+        Self::all()
+            .into_iter()
+            .find(|command| command.id() == id)
+            .ok_or_else(|| format!("Unknown hotkey command: {}", id))
+    }
+}
+
+impl From<HotkeyCommand> for String {
+    fn from(command: HotkeyCommand) -> Self {
+        command.id()
+    }
+}
+
+/// A keyboard shortcut bound to a command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    /// The command to run.
+    pub command: HotkeyCommand,
+
+    /// Accelerator string, e.g. `"Ctrl+Alt+B"`.
+    pub shortcut: String,
+}
+
+/// Configuration for global keyboard shortcuts.
+///
+/// Shortcuts are registered system-wide, so they are off by default to
+/// avoid taking over key combinations other applications use.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeySettings {
+    /// Whether shortcuts are registered with the OS.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Shortcut bindings; commands without a binding are unbound.
+    #[serde(default = "default_hotkey_bindings")]
+    pub bindings: Vec<HotkeyBinding>,
+}
+
+/// Default value provider for `HotkeySettings::bindings` during deserialization.
+///
+/// Binds everything to `Ctrl+Alt` combinations: arrows for profiles and
+/// preamp, `B` for bypass and digits for favorites.
+fn default_hotkey_bindings() -> Vec<HotkeyBinding> {
+    let binding = |command, shortcut: &str| HotkeyBinding {
+        command,
+        shortcut: shortcut.to_string(),
+    };
+
+    let mut bindings = vec![
+        binding(HotkeyCommand::NextProfile, "Ctrl+Alt+Right"),
+        binding(HotkeyCommand::PreviousProfile, "Ctrl+Alt+Left"),
+        binding(HotkeyCommand::ToggleEq, "Ctrl+Alt+B"),
+        binding(HotkeyCommand::PreampUp, "Ctrl+Alt+Up"),
+        binding(HotkeyCommand::PreampDown, "Ctrl+Alt+Down"),
+    ];
+    bindings.extend(
+        (1..=MAX_FAVORITES as u8)
+            .map(|slot| binding(HotkeyCommand::Favorite(slot), &format!("Ctrl+Alt+{}", slot))),
+    );
+    bindings
+}
+
+impl Default for HotkeySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bindings: default_hotkey_bindings(),
+        }
+    }
 }
 
 /// Default value provider for `eq_enabled` field during deserialization.
@@ -483,19 +1095,81 @@ impl Default for AppSettings {
     /// | current_profile | `None`                     |
     /// | config_path     | `None`                     |
     /// | bands           | Single band at 1 kHz       |
+    /// | graphic_eq      | Empty                      |
+    /// | convolution     | `None`                     |
+    /// | crossfeed       | `None`                     |
+    /// | mid_side        | `None`                     |
+    /// | routing         | `None`                     |
+    /// | vst_plugin      | `None`                     |
     /// | preamp          | 0.0 dB                     |
     /// | eq_enabled      | `true`                     |
+    /// | automation      | Disabled, port 8765        |
+    /// | osc             | Disabled, port 9000, local |
+    /// | favorites       | Empty                      |
+    /// | recent_profiles | Empty                      |
+    /// | hotkeys         | Disabled, `Ctrl+Alt` keys  |
+    /// | validation      | Device rate, no clamping   |
+    /// | config_precision| Lossless                   |
+    /// | loudness        | Disabled, 83 phon, 0 dB    |
     fn default() -> Self {
         Self {
             current_profile: None,
             config_path: None,
             bands: default_bands(),
+            graphic_eq: Vec::new(),
+            convolution: None,
+            crossfeed: None,
+            mid_side: None,
+            routing: None,
+            vst_plugin: None,
             preamp: 0.0,
             eq_enabled: true,
+            automation: AutomationSettings::default(),
+            osc: OscSettings::default(),
+            favorites: Vec::new(),
+            recent_profiles: Vec::new(),
+            hotkeys: HotkeySettings::default(),
+            validation: ValidationSettings::default(),
+            config_precision: ConfigPrecision::default(),
+            loudness: LoudnessSettings::default(),
         }
     }
 }
 
+impl AppSettings {
+    /// Returns the current EQ as a profile named after the current profile.
+    ///
+    /// This is what gets written to the EqualizerAPO config.
+    pub fn live_profile(&self) -> EqProfile {
+        EqProfile {
+            name: self.current_profile.clone().unwrap_or_default(),
+            preamp: self.preamp,
+            bands: self.bands.clone(),
+            graphic_eq: self.graphic_eq.clone(),
+            convolution: self.convolution.clone(),
+            crossfeed: self.crossfeed,
+            mid_side: self.mid_side.clone(),
+            routing: self.routing.clone(),
+            vst_plugin: self.vst_plugin.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the current EQ with the contents of a profile.
+    ///
+    /// Does not change `current_profile`.
+    pub fn load_live_profile(&mut self, profile: EqProfile) {
+        self.bands = profile.bands;
+        self.graphic_eq = profile.graphic_eq;
+        self.convolution = profile.convolution;
+        self.crossfeed = profile.crossfeed;
+        self.mid_side = profile.mid_side;
+        self.routing = profile.routing;
+        self.vst_plugin = profile.vst_plugin;
+        self.preamp = profile.preamp;
+    }
+}
+
 /// Runtime application state managed by Tauri's state management system.
 ///
 /// This struct holds all mutable state that needs to persist across Tauri command
@@ -540,6 +1214,22 @@ pub struct AppState {
     /// progress, randomization, and user responses.
     pub ab_session: Mutex<Option<ABSession>>,
 
+    /// Running local automation server, if enabled.
+    ///
+    /// Dropping the server stops it and disconnects its WebSocket clients.
+    pub automation: Mutex<Option<AutomationServer>>,
+
+    /// Running OSC listener, if enabled.
+    ///
+    /// Dropping the listener stops it.
+    pub osc: Mutex<Option<OscServer>>,
+
+    /// Tray icon state and recent clipping detections.
+    pub tray_status: Mutex<TrayStatus>,
+
+    /// Failed background actions, oldest first; mirrored to `error_log.json`.
+    pub error_log: Mutex<Vec<ErrorLogEntry>>,
+
     /// Audio monitoring interface for peak metering (Windows only).
     ///
     /// Provides real-time audio level monitoring via WASAPI loopback capture.
@@ -0,0 +1,432 @@
+//! Sample-rate-aware validation of parametric bands.
+//!
+//! Bands are checked against the output sample rate and sane ranges before
+//! they are saved or applied. Problems are reported as structured
+//! [`BandIssue`]s; with clamping enabled the offending values are replaced
+//! by the nearest usable value instead.
+//!
+//! | Check          | Range                                       | Severity |
+//! |----------------|---------------------------------------------|----------|
+//! | Numeric sanity | finite values                               | Error    |
+//! | Frequency      | [`MIN_FREQUENCY`] to [`NYQUIST_MARGIN`] × Nyquist | Error |
+//! | Q factor       | at least [`MIN_Q`]                          | Error    |
+//! | Q factor       | at most [`MAX_Q`]                           | Warning  |
+//! | Gain           | ±[`MAX_GAIN`] dB                            | Warning  |
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::actions::notify_settings_changed;
+use crate::dsp::DEFAULT_SAMPLE_RATE;
+use crate::profile::save_settings;
+use crate::types::{AppState, ParametricBand, ValidationSettings};
+
+// =============================================================================
+// Limits
+// =============================================================================
+
+/// Lowest usable band frequency in Hz.
+pub const MIN_FREQUENCY: f32 = 1.0;
+
+/// Fraction of Nyquist above which bilinear warping makes bands unreliable.
+pub const NYQUIST_MARGIN: f32 = 0.95;
+
+/// Lowest usable Q factor; smaller values are effectively flat or unstable.
+pub const MIN_Q: f32 = 0.05;
+
+/// Highest sensible Q factor.
+pub const MAX_Q: f32 = 40.0;
+
+/// Highest sensible band gain magnitude in dB.
+pub const MAX_GAIN: f32 = 30.0;
+
+/// Q used to replace a non-finite Q when clamping.
+const FALLBACK_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Frequency used to replace a non-finite frequency when clamping.
+const FALLBACK_FREQUENCY: f32 = 1000.0;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Band field an issue refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BandField {
+    Frequency,
+    Gain,
+    QFactor,
+}
+
+impl BandField {
+    /// Name used in messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Frequency => "frequency",
+            Self::Gain => "gain",
+            Self::QFactor => "Q",
+        }
+    }
+}
+
+/// How serious an issue is.
+///
+/// Errors make the filter unstable or meaningless and block saving or
+/// applying unless clamping is enabled; warnings are informational.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem with one field of one band.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BandIssue {
+    /// Index of the band in the list.
+    pub band: usize,
+    pub field: BandField,
+    pub severity: Severity,
+    /// Value as given; `None` if it was not finite.
+    pub value: Option<f32>,
+    /// Value the field was clamped to, if clamping was applied.
+    pub clamped_to: Option<f32>,
+    pub message: String,
+}
+
+/// Result of validating a set of bands.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationReport {
+    /// Sample rate the bands were checked against, in Hz.
+    pub sample_rate: u32,
+    pub issues: Vec<BandIssue>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if any issue is an error.
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.severity == Severity::Error)
+    }
+
+    /// Joins the error messages into a single string.
+    pub fn error_message(&self) -> String {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| i.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// Allowed range of one field.
+struct Bounds {
+    min: f32,
+    max: f32,
+    /// Whether a value below `min` is an error rather than a warning.
+    error_below: bool,
+    /// Whether a value above `max` is an error rather than a warning.
+    error_above: bool,
+    /// Replacement for non-finite values.
+    fallback: f32,
+}
+
+/// Checks one field and optionally clamps it in place.
+fn check_field(
+    issues: &mut Vec<BandIssue>,
+    band: usize,
+    field: BandField,
+    value: &mut f32,
+    bounds: Bounds,
+    clamp: bool,
+    describe: impl Fn(f32) -> String,
+) {
+    let original = *value;
+    let severity = |error: bool| {
+        if error {
+            Severity::Error
+        } else {
+            Severity::Warning
+        }
+    };
+    let (severity, target, message) = if !original.is_finite() {
+        (
+            Severity::Error,
+            bounds.fallback,
+            format!("Band {}: {} is not a number", band + 1, field.label()),
+        )
+    } else if original < bounds.min {
+        (severity(bounds.error_below), bounds.min, describe(original))
+    } else if original > bounds.max {
+        (severity(bounds.error_above), bounds.max, describe(original))
+    } else {
+        return;
+    };
+
+    if clamp {
+        *value = target;
+    }
+    issues.push(BandIssue {
+        band,
+        field,
+        severity,
+        value: Some(original).filter(|v| v.is_finite()),
+        clamped_to: clamp.then_some(target),
+        message,
+    });
+}
+
+/// Validates bands against a sample rate, optionally clamping them.
+///
+/// Disabled bands are checked too, since they can be re-enabled later.
+///
+/// # Arguments
+///
+/// * `bands` - Bands to check; modified in place when `clamp` is `true`
+/// * `sample_rate` - Output sample rate in Hz
+/// * `clamp` - Whether to replace out-of-range values
+#[must_use]
+pub fn check_bands(
+    bands: &mut [ParametricBand],
+    sample_rate: u32,
+    clamp: bool,
+) -> ValidationReport {
+    let nyquist = sample_rate as f32 / 2.0;
+    let max_frequency = nyquist * NYQUIST_MARGIN;
+    let mut issues = Vec::new();
+
+    for (index, band) in bands.iter_mut().enumerate() {
+        check_field(
+            &mut issues,
+            index,
+            BandField::Frequency,
+            &mut band.frequency,
+            Bounds {
+                min: MIN_FREQUENCY,
+                max: max_frequency,
+                error_below: true,
+                error_above: true,
+                fallback: FALLBACK_FREQUENCY,
+            },
+            clamp,
+            |f| {
+                if f < MIN_FREQUENCY {
+                    format!("Band {}: frequency {} Hz is too low", index + 1, f)
+                } else {
+                    format!(
+                        "Band {}: frequency {} Hz is too close to Nyquist ({} Hz at {} Hz sample rate)",
+                        index + 1,
+                        f,
+                        nyquist,
+                        sample_rate
+                    )
+                }
+            },
+        );
+
+        check_field(
+            &mut issues,
+            index,
+            BandField::QFactor,
+            &mut band.q_factor,
+            Bounds {
+                min: MIN_Q,
+                max: MAX_Q,
+                error_below: true,
+                error_above: false,
+                fallback: FALLBACK_Q,
+            },
+            clamp,
+            |q| format!("Band {}: Q {} is outside {}–{}", index + 1, q, MIN_Q, MAX_Q),
+        );
+
+        check_field(
+            &mut issues,
+            index,
+            BandField::Gain,
+            &mut band.gain,
+            Bounds {
+                min: -MAX_GAIN,
+                max: MAX_GAIN,
+                error_below: false,
+                error_above: false,
+                fallback: 0.0,
+            },
+            clamp,
+            |g| format!("Band {}: gain {} dB exceeds ±{} dB", index + 1, g, MAX_GAIN),
+        );
+    }
+
+    ValidationReport {
+        sample_rate,
+        issues,
+    }
+}
+
+/// Validates bands and fails on errors unless clamping.
+///
+/// # Errors
+///
+/// Returns the joined error messages if `clamp` is `false` and any band has
+/// an error-level issue.
+pub fn enforce_bands(
+    bands: &mut [ParametricBand],
+    sample_rate: u32,
+    clamp: bool,
+) -> Result<ValidationReport, String> {
+    let report = check_bands(bands, sample_rate, clamp);
+    if !clamp && report.has_errors() {
+        return Err(report.error_message());
+    }
+    Ok(report)
+}
+
+/// Returns the sample rate bands are validated against.
+///
+/// Uses the configured rate if set, otherwise the default output device's
+/// rate (Windows only), falling back to 48 kHz.
+pub fn resolve_sample_rate(state: &AppState) -> u32 {
+    if let Some(rate) = state.settings.lock().validation.sample_rate {
+        return rate;
+    }
+
+    #[cfg(windows)]
+    {
+        if let Ok(info) = state.audio_monitor.get_audio_output_info() {
+            return info.sample_rate;
+        }
+    }
+
+    DEFAULT_SAMPLE_RATE as u32
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Validates bands without saving or applying them.
+///
+/// # Arguments
+///
+/// * `bands` - Bands to check
+/// * `sample_rate` - Sample rate to check against; `None` resolves it as
+///   described in [`resolve_sample_rate`]
+/// * `state` - Tauri managed state
+#[tauri::command]
+pub fn validate_bands(
+    mut bands: Vec<ParametricBand>,
+    sample_rate: Option<u32>,
+    state: tauri::State<AppState>,
+) -> ValidationReport {
+    let sample_rate = sample_rate.unwrap_or_else(|| resolve_sample_rate(&state));
+    check_bands(&mut bands, sample_rate, false)
+}
+
+/// Sets the validation options and persists them.
+///
+/// # Arguments
+///
+/// * `validation` - Sample rate override and auto-clamp flag
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for notifications
+///
+/// # Errors
+///
+/// Returns an error if the sample rate is zero or settings cannot be saved.
+#[tauri::command]
+pub fn set_validation_settings(
+    validation: ValidationSettings,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if validation.sample_rate == Some(0) {
+        return Err("Sample rate must be positive".to_string());
+    }
+    {
+        let mut settings = state.settings.lock();
+        settings.validation = validation;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{test_band, FilterType};
+
+    #[test]
+    fn accepts_ordinary_bands() {
+        let mut bands = vec![
+            test_band(FilterType::Peaking, 1000.0, 3.0, 1.41),
+            test_band(FilterType::Peaking, 20000.0, -6.0, 0.7),
+        ];
+        assert!(check_bands(&mut bands, 44100, false).issues.is_empty());
+    }
+
+    #[test]
+    fn band_at_nyquist_is_an_error() {
+        let mut bands = vec![test_band(FilterType::Peaking, 22000.0, 3.0, 1.0)];
+        let report = check_bands(&mut bands, 44100, false);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].field, BandField::Frequency);
+        assert_eq!(report.issues[0].severity, Severity::Error);
+        assert_eq!(bands[0].frequency, 22000.0);
+
+        // Fine at 48 kHz, but inside the warped zone below Nyquist at 44.1 kHz
+        let mut bands = vec![test_band(FilterType::Peaking, 21500.0, 3.0, 1.0)];
+        assert!(check_bands(&mut bands, 48000, false).issues.is_empty());
+        assert!(check_bands(&mut bands, 44100, false).has_errors());
+    }
+
+    #[test]
+    fn zero_q_and_nan_are_errors() {
+        let mut bands = vec![
+            test_band(FilterType::Peaking, 1000.0, 3.0, 0.0),
+            test_band(FilterType::Peaking, f32::NAN, f32::INFINITY, 1.0),
+        ];
+        let report = check_bands(&mut bands, 48000, false);
+        assert!(report.has_errors());
+        assert_eq!(report.issues.len(), 3);
+        assert_eq!(report.issues[1].value, None);
+        assert!(enforce_bands(&mut bands, 48000, false).is_err());
+    }
+
+    #[test]
+    fn large_gain_and_q_are_warnings() {
+        let mut bands = vec![test_band(FilterType::Peaking, 1000.0, 40.0, 60.0)];
+        let report = enforce_bands(&mut bands, 48000, false).unwrap();
+        assert_eq!(report.issues.len(), 2);
+        assert!(!report.has_errors());
+        assert_eq!(bands[0].gain, 40.0);
+    }
+
+    #[test]
+    fn clamping_fixes_every_issue() {
+        let mut bands = vec![
+            test_band(FilterType::Peaking, 22000.0, -45.0, 0.0),
+            test_band(FilterType::Peaking, f32::NAN, f32::NAN, f32::INFINITY),
+        ];
+        let report = enforce_bands(&mut bands, 44100, true).unwrap();
+        assert_eq!(report.issues.len(), 6);
+        assert!(report.issues.iter().all(|i| i.clamped_to.is_some()));
+
+        assert_eq!(bands[0].frequency, 22050.0 * NYQUIST_MARGIN);
+        assert_eq!(bands[0].gain, -MAX_GAIN);
+        assert_eq!(bands[0].q_factor, MIN_Q);
+        assert_eq!(bands[1].frequency, FALLBACK_FREQUENCY);
+        assert_eq!(bands[1].gain, 0.0);
+        assert_eq!(bands[1].q_factor, FALLBACK_Q);
+        assert!(check_bands(&mut bands, 44100, false).issues.is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+//! VST plugin stages hosted by EqualizerAPO.
+//!
+//! EqualizerAPO loads VST 2 plugins from `VSTPlugin:` lines that name the
+//! plugin library and carry the plugin's saved state as base64:
+//!
+//! ```text
+//! VSTPlugin: Library "C:\VST\Limiter.dll" ChunkData "VkMyU3RhdGU="
+//! ```
+//!
+//! The app never interprets the state; it only keeps it intact, so a
+//! profile can be edited, exported and re-imported without losing the
+//! plugin's settings. The line is written after the parametric bands, so
+//! the plugin sees the equalized signal (e.g. for a limiter).
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::actions::notify_settings_changed;
+use crate::profile::{load_profile, save_settings, write_profile};
+use crate::types::AppState;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// A VST plugin stage of a profile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VstPlugin {
+    /// Path of the plugin library (`.dll`), as EqualizerAPO should load it.
+    pub library: String,
+
+    /// Opaque plugin state, base64-encoded; `None` for the plugin defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<String>,
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// Returns whether `data` only contains base64 characters with valid
+/// padding.
+fn is_base64(data: &str) -> bool {
+    let body = data.trim_end_matches('=');
+    data.len().is_multiple_of(4)
+        && data.len() - body.len() <= 2
+        && body
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/')
+}
+
+impl VstPlugin {
+    /// Checks the values that end up in the config line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the library path is empty, contains a quote or
+    /// line break, or is not a `.dll`, or if the chunk is not base64.
+    pub fn validate_format(&self) -> Result<(), String> {
+        let library = self.library.trim();
+        if library.is_empty() {
+            return Err("VST plugin library path is empty".to_string());
+        }
+        if library.contains(['"', '\r', '\n']) {
+            return Err(format!("Invalid VST plugin library path '{}'", library));
+        }
+        let is_dll = Path::new(library)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("dll"));
+        if !is_dll {
+            return Err(format!("VST plugin '{}' is not a .dll file", library));
+        }
+        if let Some(chunk) = &self.chunk {
+            if !is_base64(chunk) {
+                return Err("VST plugin state is not valid base64".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the values and that the plugin library exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`VstPlugin::validate_format`] fails or the
+    /// library file does not exist.
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_format()?;
+        if !Path::new(self.library.trim()).is_file() {
+            return Err(format!("VST plugin '{}' not found", self.library.trim()));
+        }
+        Ok(())
+    }
+
+    /// Returns the EqualizerAPO line for the plugin.
+    #[must_use]
+    pub fn config_line(&self) -> String {
+        let mut line = format!("VSTPlugin: Library \"{}\"", self.library.trim());
+        if let Some(chunk) = &self.chunk {
+            line.push_str(&format!(" ChunkData \"{}\"", chunk));
+        }
+        line
+    }
+}
+
+// =============================================================================
+// Parsing
+// =============================================================================
+
+/// Splits the arguments of a `VSTPlugin:` line into words and quoted
+/// strings.
+fn tokenize(text: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut token = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => token.push(c),
+                    None => return Err("Unterminated quote".to_string()),
+                }
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses a `VSTPlugin: Library "..." ChunkData "..."` line.
+///
+/// The plugin file is not required to exist.
+///
+/// # Errors
+///
+/// Returns an error if the line is not a `VSTPlugin:` line, has options
+/// other than `Library` and `ChunkData` (whose values would be lost), or
+/// fails [`VstPlugin::validate_format`].
+pub fn parse_config_line(line: &str) -> Result<VstPlugin, String> {
+    let rest = line
+        .trim()
+        .strip_prefix("VSTPlugin:")
+        .ok_or_else(|| format!("Not a VSTPlugin line: '{}'", line))?;
+
+    let tokens = tokenize(rest)?;
+    let (mut library, mut chunk) = (None, None);
+    for pair in tokens.chunks(2) {
+        match pair {
+            [key, value] if key == "Library" => library = Some(value.clone()),
+            [key, value] if key == "ChunkData" => chunk = Some(value.clone()),
+            [key, _] => return Err(format!("Unsupported VSTPlugin option '{}'", key)),
+            [key] => return Err(format!("Missing value for {}", key)),
+            _ => unreachable!("chunks(2) yields one or two tokens"),
+        }
+    }
+
+    let plugin = VstPlugin {
+        library: library.ok_or("Missing VSTPlugin library")?,
+        chunk,
+    };
+    plugin.validate_format()?;
+    Ok(plugin)
+}
+
+// =============================================================================
+// Tauri Commands
+// =============================================================================
+
+/// Sets or removes the VST plugin stage of a saved profile.
+///
+/// # Arguments
+///
+/// * `name` - The profile name
+/// * `vst_plugin` - The new stage, or `None` to remove it
+///
+/// # Errors
+///
+/// Returns an error if the plugin is invalid or missing, or the profile
+/// cannot be loaded or written.
+#[tauri::command]
+pub fn set_profile_vst_plugin(name: String, vst_plugin: Option<VstPlugin>) -> Result<(), String> {
+    if let Some(plugin) = &vst_plugin {
+        plugin.validate()?;
+    }
+    let mut profile = load_profile(name)?;
+    profile.vst_plugin = vst_plugin;
+    write_profile(&profile)
+}
+
+/// Sets or removes the VST plugin stage of the current EQ.
+///
+/// The stage is written to the config on the next apply.
+///
+/// # Arguments
+///
+/// * `vst_plugin` - The new stage, or `None` to remove it
+/// * `state` - Tauri managed state
+/// * `app` - Tauri app handle for notifications
+///
+/// # Errors
+///
+/// Returns an error if the plugin is invalid or missing, or settings cannot
+/// be saved.
+#[tauri::command]
+pub fn set_vst_plugin(
+    vst_plugin: Option<VstPlugin>,
+    state: tauri::State<AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if let Some(plugin) = &vst_plugin {
+        plugin.validate()?;
+    }
+    {
+        let mut settings = state.settings.lock();
+        settings.vst_plugin = vst_plugin;
+        save_settings(&settings)?;
+    }
+    notify_settings_changed(&app);
+    Ok(())
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(library: &str, chunk: Option<&str>) -> VstPlugin {
+        VstPlugin {
+            library: library.to_string(),
+            chunk: chunk.map(String::from),
+        }
+    }
+
+    #[test]
+    fn config_line_round_trips() {
+        let original = plugin(r"C:\VST Plugins\Limiter.dll", Some("VkMyU3RhdGU="));
+        let line = original.config_line();
+        assert_eq!(
+            line,
+            r#"VSTPlugin: Library "C:\VST Plugins\Limiter.dll" ChunkData "VkMyU3RhdGU=""#
+        );
+        assert_eq!(parse_config_line(&line).unwrap(), original);
+
+        let defaults = plugin("limiter.dll", None);
+        assert_eq!(
+            parse_config_line(&defaults.config_line()).unwrap(),
+            defaults
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_options_and_bad_values() {
+        for line in [
+            r#"VSTPlugin: Library "a.dll" Param0 0.5"#,
+            r#"VSTPlugin: Library "a.dll" ChunkData"#,
+            r#"VSTPlugin: ChunkData "AAAA""#,
+            r#"VSTPlugin: Library "a.dll" ChunkData "not base64!""#,
+            r#"VSTPlugin: Library "a.vst3""#,
+            r#"VSTPlugin: Library "a.dll"#,
+            "Filter: ON PK Fc 100 Hz Gain 1 dB Q 1",
+        ] {
+            assert!(parse_config_line(line).is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn validate_requires_existing_library() {
+        let dir = std::env::temp_dir().join("eqapo-gui-vst-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let library = dir.join("plugin.dll");
+        std::fs::write(&library, b"MZ").unwrap();
+
+        let existing = plugin(library.to_str().unwrap(), Some("AAAA"));
+        assert!(existing.validate().is_ok());
+        let missing = plugin(dir.join("missing.dll").to_str().unwrap(), None);
+        assert!(missing.validate_format().is_ok());
+        assert!(missing.validate().is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
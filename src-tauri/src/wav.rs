@@ -0,0 +1,265 @@
+//! Minimal WAV file reading and writing for impulse responses.
+//!
+//! Samples are written as 32-bit IEEE float (`WAVE_FORMAT_IEEE_FLOAT`),
+//! which EqualizerAPO's convolution filter and most convolution engines
+//! read without loss. Reading is limited to the header, which is enough to
+//! validate impulse files before they are referenced from a config.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+/// `WAVE_FORMAT_PCM` format tag.
+const FORMAT_PCM: u16 = 1;
+
+/// `WAVE_FORMAT_IEEE_FLOAT` format tag.
+const FORMAT_IEEE_FLOAT: u16 = 3;
+
+/// `WAVE_FORMAT_EXTENSIBLE` format tag; the real format is in the sub-format.
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Sample encoding of a WAV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SampleFormat {
+    /// Signed integer PCM
+    Pcm,
+    /// IEEE floating point
+    Float,
+}
+
+/// Header information of a WAV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct WavInfo {
+    pub format: SampleFormat,
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    /// Number of sample frames in the data chunk
+    pub frames: u64,
+}
+
+impl WavInfo {
+    /// Duration of the audio in seconds.
+    pub fn duration(&self) -> f64 {
+        self.frames as f64 / f64::from(self.sample_rate.max(1))
+    }
+}
+
+/// Encodes interleaved samples as a 32-bit float WAV file.
+///
+/// # Arguments
+///
+/// * `samples` - Interleaved samples (`frames * channels` values)
+/// * `channels` - Number of channels
+/// * `sample_rate` - Sample rate in Hz
+pub fn encode_float(samples: &[f32], channels: u16, sample_rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 4) as u32;
+    let block_align = channels * 4;
+    let frames = samples.len() as u32 / channels.max(1) as u32;
+
+    let mut out = Vec::with_capacity(58 + samples.len() * 4);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(50 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVE");
+
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&18u32.to_le_bytes());
+    out.extend_from_slice(&FORMAT_IEEE_FLOAT.to_le_bytes());
+    out.extend_from_slice(&channels.to_le_bytes());
+    out.extend_from_slice(&sample_rate.to_le_bytes());
+    out.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    out.extend_from_slice(&block_align.to_le_bytes());
+    out.extend_from_slice(&32u16.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+
+    // Non-PCM formats carry a fact chunk with the frame count
+    out.extend_from_slice(b"fact");
+    out.extend_from_slice(&4u32.to_le_bytes());
+    out.extend_from_slice(&frames.to_le_bytes());
+
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    out
+}
+
+/// Writes interleaved samples to a 32-bit float WAV file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_float(
+    path: &Path,
+    samples: &[f32],
+    channels: u16,
+    sample_rate: u32,
+) -> Result<(), String> {
+    fs::write(path, encode_float(samples, channels, sample_rate))
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+/// Parses the header of a WAV file.
+///
+/// Walks the RIFF chunks for `fmt ` and `data`; other chunks are skipped.
+/// Only PCM and IEEE float encodings (plain or `WAVE_FORMAT_EXTENSIBLE`)
+/// are accepted.
+///
+/// # Errors
+///
+/// Returns an error if the bytes are not a RIFF/WAVE file, a required chunk
+/// is missing or truncated, or the encoding is not supported.
+pub fn parse_info(bytes: &[u8]) -> Result<WavInfo, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("Not a RIFF/WAVE file".to_string());
+    }
+
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+
+    let mut fmt: Option<(SampleFormat, u16, u32, u16, u16)> = None;
+    let mut data_len: Option<u64> = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32_at(pos + 4) as usize;
+        let body = pos + 8;
+
+        if id == b"fmt " {
+            if size < 16 || body + size > bytes.len() {
+                return Err("Truncated fmt chunk".to_string());
+            }
+            let mut tag = u16_at(body);
+            if tag == FORMAT_EXTENSIBLE {
+                if size < 40 {
+                    return Err("Truncated WAVE_FORMAT_EXTENSIBLE fmt chunk".to_string());
+                }
+                // The sub-format GUID starts with the plain format tag
+                tag = u16_at(body + 24);
+            }
+            let format = match tag {
+                FORMAT_PCM => SampleFormat::Pcm,
+                FORMAT_IEEE_FLOAT => SampleFormat::Float,
+                other => return Err(format!("Unsupported WAV format tag {}", other)),
+            };
+            fmt = Some((
+                format,
+                u16_at(body + 2),
+                u32_at(body + 4),
+                u16_at(body + 12),
+                u16_at(body + 14),
+            ));
+        } else if id == b"data" {
+            // Streamed files may leave the size unset; use what is present
+            data_len = Some(size.min(bytes.len() - body) as u64);
+            break;
+        }
+
+        // Chunks are padded to an even size
+        pos = body.saturating_add(size).saturating_add(size & 1);
+    }
+
+    let (format, channels, sample_rate, block_align, bits_per_sample) =
+        fmt.ok_or("WAV file has no fmt chunk")?;
+    let data_len = data_len.ok_or("WAV file has no data chunk")?;
+    if channels == 0 || block_align == 0 || sample_rate == 0 {
+        return Err("WAV file has an invalid fmt chunk".to_string());
+    }
+
+    Ok(WavInfo {
+        format,
+        channels,
+        sample_rate,
+        bits_per_sample,
+        frames: data_len / u64::from(block_align),
+    })
+}
+
+/// Reads the header of a WAV file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a supported WAV
+/// file.
+pub fn read_info(path: &Path) -> Result<WavInfo, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    parse_info(&bytes).map_err(|e| format!("{:?}: {}", path, e))
+}
+
+// =============================================================================
+// Unit Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_float_header() {
+        let bytes = encode_float(&[1.0, -0.5], 1, 48000);
+        assert_eq!(bytes.len(), 58 + 8);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(bytes[4..8].try_into().unwrap()), 58);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(
+            u16::from_le_bytes([bytes[20], bytes[21]]),
+            FORMAT_IEEE_FLOAT
+        );
+        assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 48000);
+        assert_eq!(&bytes[38..42], b"fact");
+        assert_eq!(&bytes[50..54], b"data");
+        assert_eq!(f32::from_le_bytes(bytes[62..66].try_into().unwrap()), -0.5);
+    }
+
+    #[test]
+    fn parses_encoded_header() {
+        let info = parse_info(&encode_float(&[0.0; 6], 2, 44100)).unwrap();
+        assert_eq!(
+            info,
+            WavInfo {
+                format: SampleFormat::Float,
+                channels: 2,
+                sample_rate: 44100,
+                bits_per_sample: 32,
+                frames: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_pcm_with_extra_chunks() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF\0\0\0\0WAVE");
+        // Odd-sized chunk before fmt, padded to an even size
+        bytes.extend_from_slice(b"LIST");
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&[1, 2, 3, 0]);
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&FORMAT_PCM.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&96000u32.to_le_bytes());
+        bytes.extend_from_slice(&(96000u32 * 3).to_le_bytes());
+        bytes.extend_from_slice(&3u16.to_le_bytes());
+        bytes.extend_from_slice(&24u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&12u32.to_le_bytes());
+        bytes.extend_from_slice(&[0; 12]);
+
+        let info = parse_info(&bytes).unwrap();
+        assert_eq!(info.format, SampleFormat::Pcm);
+        assert_eq!(info.channels, 1);
+        assert_eq!(info.sample_rate, 96000);
+        assert_eq!(info.bits_per_sample, 24);
+        assert_eq!(info.frames, 4);
+    }
+
+    #[test]
+    fn rejects_non_wav_data() {
+        assert!(parse_info(b"not a wav file").is_err());
+        assert!(parse_info(b"RIFF\0\0\0\0WAVE").is_err());
+    }
+}